@@ -11,6 +11,7 @@ fn main() {
         flop: flop_from_str("Td9d6h").unwrap(),
         turn: card_from_str("Qc").unwrap(),
         river: NOT_DEALT,
+        deck: DeckType::Standard,
     };
 
     let bet_sizes = BetSizeOptions::try_from(("60%, e, a", "2.5x")).unwrap();
@@ -19,6 +20,9 @@ fn main() {
         initial_state: BoardState::Turn,
         starting_pot: 200,
         effective_stack: 900,
+        stacks: [0, 0],
+        dead_money: 0,
+        big_blind: 0,
         rake_rate: 0.0,
         rake_cap: 0.0,
         flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
@@ -29,6 +33,8 @@ fn main() {
         add_allin_threshold: 1.5,
         force_allin_threshold: 0.15,
         merging_threshold: 0.1,
+        pot_limit: false,
+        fixed_limit: None,
     };
 
     let action_tree = ActionTree::new(tree_config).unwrap();
@@ -80,13 +86,16 @@ fn main() {
     
     let mut file = File::create("solver_results.txt").unwrap();
     
+    let card_config = game.card_config().clone();
+    let tree_config = game.tree_config().clone();
+
     writeln!(file, "\nGame Configuration:").unwrap();
     
     // For the flop, extract the individual cards from the flop
     let flop_str = format!("{}{}{}", 
-        card_to_string((card_config.flop >> 16) as u8).unwrap_or_default(),
-        card_to_string((card_config.flop >> 8) as u8).unwrap_or_default(),
-        card_to_string(card_config.flop as u8).unwrap_or_default()
+        card_to_string(card_config.flop[0]).unwrap_or_default(),
+        card_to_string(card_config.flop[1]).unwrap_or_default(),
+        card_to_string(card_config.flop[2]).unwrap_or_default()
     );
     writeln!(file, "Board: {}", flop_str).unwrap();
     
@@ -11,12 +11,16 @@ fn normal_node_locking() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: DeckType::Standard,
     };
 
     let tree_config = TreeConfig {
         initial_state: BoardState::River,
         starting_pot: 20,
         effective_stack: 10,
+        stacks: [0, 0],
+        dead_money: 0,
+        big_blind: 0,
         river_bet_sizes: [("a", "").try_into().unwrap(), ("a", "").try_into().unwrap()],
         ..Default::default()
     };
@@ -62,12 +66,16 @@ fn partial_node_locking() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: DeckType::Standard,
     };
 
     let tree_config = TreeConfig {
         initial_state: BoardState::River,
         starting_pot: 10,
         effective_stack: 10,
+        stacks: [0, 0],
+        dead_money: 0,
+        big_blind: 0,
         river_bet_sizes: [("a", "").try_into().unwrap(), ("a", "").try_into().unwrap()],
         ..Default::default()
     };
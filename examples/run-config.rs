@@ -4,37 +4,12 @@ use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 #[derive(Serialize, Deserialize, Debug)]
 struct SolverConfig {
-    oop_range: String,
-    ip_range: String,
-    flop: String,
-    turn: Option<String>,
-    river: Option<String>,
-    initial_state: String,
-    starting_pot: i32,
-    effective_stack: i32,
-    rake_rate: f64,
-    rake_cap: f64,
-    flop_oop_bet_sizes: String,
-    flop_oop_raise_sizes: String,
-    flop_ip_bet_sizes: String,
-    flop_ip_raise_sizes: String,
-    turn_oop_bet_sizes: String,
-    turn_oop_raise_sizes: String,
-    turn_ip_bet_sizes: String,
-    turn_ip_raise_sizes: String,
-    river_oop_bet_sizes: String,
-    river_oop_raise_sizes: String,
-    river_ip_bet_sizes: String,
-    river_ip_raise_sizes: String,
-    turn_donk_sizes: Option<String>,
-    river_donk_sizes: Option<String>,
-    add_allin_threshold: f64,
-    force_allin_threshold: f64,
-    merging_threshold: f64,
+    card_config: CardConfig,
+    tree_config: TreeConfig,
     max_iterations: u32,
     target_exploitability: f32,
     use_compression: bool,
@@ -42,6 +17,7 @@ struct SolverConfig {
     saved_folder: Option<String>,
     removed_lines: Option<Vec<Vec<String>>>,
     added_lines: Option<Vec<Vec<String>>>,
+    amount_unit: Option<String>,
 }
 
 //cargo run --release --example run-config -- examples/config_file.json
@@ -54,98 +30,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let config = load_config(&args[1])?;
-    let flop = flop_from_str(&config.flop).unwrap();
-    let turn = if let Some(turn_str) = &config.turn {
-        card_from_str(turn_str).unwrap()
-    } else {
-        NOT_DEALT
-    };
-    let river = if let Some(river_str) = &config.river {
-        card_from_str(river_str).unwrap()
-    } else {
-        NOT_DEALT
-    };
-
-    let card_config = CardConfig {
-        range: [
-            config.oop_range.parse().unwrap(),
-            config.ip_range.parse().unwrap(),
-        ],
-        flop,
-        turn,
-        river,
-    };
-
-    let flop_oop_bet_sizes = BetSizeOptions::try_from((
-        config.flop_oop_bet_sizes.as_str(),
-        config.flop_oop_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let flop_ip_bet_sizes = BetSizeOptions::try_from((
-        config.flop_ip_bet_sizes.as_str(),
-        config.flop_ip_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let turn_oop_bet_sizes = BetSizeOptions::try_from((
-        config.turn_oop_bet_sizes.as_str(),
-        config.turn_oop_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let turn_ip_bet_sizes = BetSizeOptions::try_from((
-        config.turn_ip_bet_sizes.as_str(),
-        config.turn_ip_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let river_oop_bet_sizes = BetSizeOptions::try_from((
-        config.river_oop_bet_sizes.as_str(),
-        config.river_oop_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let river_ip_bet_sizes = BetSizeOptions::try_from((
-        config.river_ip_bet_sizes.as_str(),
-        config.river_ip_raise_sizes.as_str(),
-    ))
-    .unwrap();
-
-    let turn_donk_sizes = config
-        .turn_donk_sizes
-        .as_ref()
-        .map(|s| DonkSizeOptions::try_from(s.as_str()).unwrap());
-
-    let river_donk_sizes = config
-        .river_donk_sizes
-        .as_ref()
-        .map(|s| DonkSizeOptions::try_from(s.as_str()).unwrap());
-
-    let initial_state = match config.initial_state.to_lowercase().as_str() {
-        "flop" => BoardState::Flop,
-        "turn" => BoardState::Turn,
-        "river" => BoardState::River,
-        _ => BoardState::Flop,
-    };
-
-    let tree_config = TreeConfig {
-        initial_state,
-        starting_pot: config.starting_pot,
-        effective_stack: config.effective_stack,
-        rake_rate: config.rake_rate,
-        rake_cap: config.rake_cap,
-        flop_bet_sizes: [flop_oop_bet_sizes, flop_ip_bet_sizes],
-        turn_bet_sizes: [turn_oop_bet_sizes, turn_ip_bet_sizes],
-        river_bet_sizes: [river_oop_bet_sizes, river_ip_bet_sizes],
-        turn_donk_sizes,
-        river_donk_sizes,
-        add_allin_threshold: config.add_allin_threshold,
-        force_allin_threshold: config.force_allin_threshold,
-        merging_threshold: config.merging_threshold,
-    };
-
-    let mut action_tree = ActionTree::new(tree_config.clone())?;
+    let mut action_tree = ActionTree::new(config.tree_config.clone())?;
 
     if let Some(removed_lines) = &config.removed_lines {
         remove_lines_simple(&mut action_tree, removed_lines);
@@ -155,49 +40,51 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         add_lines_simple(&mut action_tree, added_lines);
     }
 
-    let mut game = PostFlopGame::with_config(card_config, action_tree)?;
+    let mut game = PostFlopGame::with_config(config.card_config.clone(), action_tree)?;
     game.allocate_memory(config.use_compression);
 
     let max_iterations = config.max_iterations;
     let target_exploitability = config.target_exploitability * 100.0;
-    let mut exploitability = compute_exploitability(&game);
-    let start_time = Instant::now();
-    let timeout = config
-        .max_runtime_seconds
-        .map(|secs| Duration::from_secs(secs));
+    let timeout = config.max_runtime_seconds.map(Duration::from_secs);
+    let mut timed_out = false;
+    let mut last_progress = None;
 
     // log_game_state(&game, &config);
-    for current_iteration in 0..max_iterations {
-        if let Some(max_duration) = timeout {
-            if start_time.elapsed() >= max_duration {
-                println!(
-                    "Solver stopped due to time limit ({} seconds), iteration: {}, exploitability: {:.2}%",
-                    max_duration.as_secs(), current_iteration, exploitability
-                );
-                break;
+    let exploitability = solve_with_callback(
+        &mut game,
+        &DiscountedCfr::default(),
+        max_iterations,
+        target_exploitability,
+        |_, progress| {
+            last_progress = Some(*progress);
+            match timeout {
+                Some(max_duration) if progress.elapsed >= max_duration => {
+                    timed_out = true;
+                    SolveControl::Stop
+                }
+                _ => SolveControl::Continue,
             }
-        }
-
-        if exploitability <= target_exploitability {
-            println!(
-                "Solver stopped due to target exploitability reached ({:.2}%), iteration: {}, time: {:.2} seconds",
-                exploitability, current_iteration, start_time.elapsed().as_secs_f64()
-            );
-            break;
-        }
-
-        solve_step(&mut game, current_iteration);
-
-        if (current_iteration + 1) % 10 == 0 || current_iteration + 1 == max_iterations {
-            exploitability = compute_exploitability(&game);
-        }
+        },
+    );
 
-        if current_iteration + 1 == max_iterations {
-            println!(
-                "Solver stopped due to max iterations reached: {}, exploitability: {:.2}%, time: {:.2} seconds",
-                max_iterations, exploitability, start_time.elapsed().as_secs_f64()
-            );
-        }
+    let progress = last_progress.unwrap();
+    if timed_out {
+        println!(
+            "Solver stopped due to time limit ({} seconds), iteration: {}, exploitability: {:.2}%",
+            timeout.unwrap().as_secs(),
+            progress.iteration,
+            exploitability
+        );
+    } else if exploitability <= target_exploitability {
+        println!(
+            "Solver stopped due to target exploitability reached ({:.2}%), iteration: {}, time: {:.2} seconds",
+            exploitability, progress.iteration, progress.elapsed.as_secs_f64()
+        );
+    } else {
+        println!(
+            "Solver stopped due to max iterations reached: {}, exploitability: {:.2}%, time: {:.2} seconds",
+            max_iterations, exploitability, progress.elapsed.as_secs_f64()
+        );
     }
 
     // println!(
@@ -206,7 +93,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     //     exploitability
     // );
 
-    finalize(&mut game);
     game.back_to_root();
 
     let output_folder = match &config.saved_folder {
@@ -214,7 +100,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         None => "results".to_string(),
     };
 
-    explore_and_save_ranges(&mut game, output_folder.as_str(), 10)?;
+    let amount_unit = match &config.amount_unit {
+        Some(unit) => unit.parse::<AmountUnit>()?,
+        None => AmountUnit::Chips,
+    };
+
+    explore_and_save_ranges(&mut game, output_folder.as_str(), 10, amount_unit)?;
     // run_bet_call_turn_scenario(&mut game)?;
     // explore_game_tree(&mut game);
     Ok(())
@@ -80,6 +80,7 @@ fn main() {
         flop: flop_from_str("Td5d3h").unwrap(),
         turn: NOT_DEALT, // card_from_str("Qc").unwrap(),
         river: NOT_DEALT,
+        deck: DeckType::Standard,
     };
 
     let bet_sizes = BetSizeOptions::try_from(("50%", "60%")).unwrap();
@@ -88,6 +89,9 @@ fn main() {
         initial_state: BoardState::Flop,
         starting_pot: 20,
         effective_stack: 200,
+        stacks: [0, 0],
+        dead_money: 0,
+        big_blind: 0,
         rake_rate: 0.0,
         rake_cap: 0.0,
         flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
@@ -98,6 +102,8 @@ fn main() {
         add_allin_threshold: 1.5,
         force_allin_threshold: 0.20,
         merging_threshold: 0.1,
+        pot_limit: false,
+        fixed_limit: None,
     };
 
     // Construction et résolution du jeu
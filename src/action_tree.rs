@@ -1,6 +1,10 @@
 use crate::bet_size::*;
 use crate::card::*;
+use crate::error::*;
 use crate::mutex_like::*;
+use crate::tree_io::*;
+use crate::tree_lint::*;
+use serde::{Deserialize, Serialize};
 
 // #[cfg(feature = "bincode")]
 // use bincode::{Decode, Encode};
@@ -21,7 +25,9 @@ pub(crate) const PLAYER_TERMINAL_FLAG: u8 = 8;
 pub(crate) const PLAYER_FOLD_FLAG: u8 = 24;
 
 /// Available actions of the postflop game.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// With serde, an action is represented by its name, e.g., `"Check"` and `{"Bet": 50}`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub enum Action {
     /// (Default value)
@@ -51,7 +57,10 @@ pub enum Action {
 }
 
 /// An enum representing the board state.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// With serde, the board state is represented by `"flop"`, `"turn"`, or `"river"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 #[repr(u8)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub enum BoardState {
@@ -74,6 +83,9 @@ pub enum BoardState {
 ///     initial_state: BoardState::Turn,
 ///     starting_pot: 200,
 ///     effective_stack: 900,
+///     stacks: [900, 1500],
+///     dead_money: 20,
+///     big_blind: 10,
 ///     rake_rate: 0.05,
 ///     rake_cap: 30.0,
 ///     flop_bet_sizes: Default::default(),
@@ -84,9 +96,33 @@ pub enum BoardState {
 ///     add_allin_threshold: 1.5,
 ///     force_allin_threshold: 0.15,
 ///     merging_threshold: 0.1,
+///     pot_limit: false,
+///     fixed_limit: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Default)]
+///
+/// With serde, the bet sizes are represented by the strings accepted by `try_from()`, and the
+/// missing fields are filled with the default values:
+///
+/// ```
+/// use postflop_solver::*;
+///
+/// let json = r#"{
+///     "initial_state": "turn",
+///     "starting_pot": 200,
+///     "effective_stack": 900,
+///     "turn_bet_sizes": [{"bet": "60%, e, a", "raise": "2.5x"}, {"bet": "60%", "raise": "3x"}],
+///     "river_donk_sizes": "50%"
+/// }"#;
+///
+/// let tree_config: TreeConfig = serde_json::from_str(json).unwrap();
+/// assert_eq!(tree_config.initial_state, BoardState::Turn);
+/// assert_eq!(tree_config.turn_bet_sizes[1].raise, [BetSize::PrevBetRelative(3.0)]);
+/// assert_eq!(tree_config.river_donk_sizes.unwrap().donk, [BetSize::PotRelative(0.5)]);
+/// assert_eq!(tree_config.rake_rate, 0.0);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub struct TreeConfig {
     /// Initial state of the game tree (flop, turn, or river).
@@ -98,6 +134,22 @@ pub struct TreeConfig {
     /// Initial effective stack. Must be greater than `0`.
     pub effective_stack: i32,
 
+    /// Initial stacks of OOP and IP behind the starting pot (set `[0, 0]` to use the effective
+    /// stack for both players). If set, the effective stack must be the smaller of the two.
+    ///
+    /// The chips of the deeper player exceeding the effective stack do not affect the game tree;
+    /// they are only used for displaying the stacks.
+    pub stacks: [i32; 2],
+
+    /// Dead money contained in the starting pot (e.g., the chips of players who have already
+    /// folded). The rest of the starting pot is considered to be committed equally by OOP and IP.
+    /// Must be between `0` and the starting pot, inclusive.
+    pub dead_money: i32,
+
+    /// Big blind size used by the "bb" bet sizes and for displaying amounts in big blinds (set `0`
+    /// if not used). Must be non-negative, and must be positive if any "bb" bet size is specified.
+    pub big_blind: i32,
+
     /// Rake rate. Must be between `0.0` and `1.0`, inclusive.
     pub rake_rate: f64,
 
@@ -138,6 +190,114 @@ pub struct TreeConfig {
     ///
     /// Personal recommendation: around `0.1`
     pub merging_threshold: f64,
+
+    /// Cap the bet and raise amounts at the pot size (pot-limit betting).
+    ///
+    /// The maximum raise is the size of the pot after calling. All-in actions exceeding the limit
+    /// are replaced with the maximum bet or raise.
+    pub pot_limit: bool,
+
+    /// Fixed-limit betting structure (set `None` for no-limit or pot-limit betting).
+    ///
+    /// If set, the bet size options, the donk size options, and the all-in and merging thresholds
+    /// are ignored.
+    pub fixed_limit: Option<FixedLimit>,
+}
+
+/// A struct representing a fixed-limit betting structure.
+///
+/// Each bet or raise on a street is exactly the bet unit of the street. A player who cannot afford
+/// a full bet or raise can only go all-in.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// // 10/20 limit hold'em with a cap of four bets per street
+/// let fixed_limit = FixedLimit::new(10, 20, 3);
+/// assert_eq!(fixed_limit.bet_units, [10, 20, 20]);
+///
+/// let tree_config = TreeConfig {
+///     starting_pot: 60,
+///     effective_stack: 500,
+///     fixed_limit: Some(fixed_limit),
+///     ..Default::default()
+/// };
+///
+/// let mut action_tree = ActionTree::new(tree_config).unwrap();
+/// assert_eq!(action_tree.available_actions(), [Action::Check, Action::Bet(10)]);
+///
+/// action_tree.play(Action::Bet(10)).unwrap();
+/// assert_eq!(
+///     action_tree.available_actions(),
+///     [Action::Fold, Action::Call, Action::Raise(20)]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct FixedLimit {
+    /// Bet unit of each street (flop, turn, river). Each must be greater than `0`.
+    pub bet_units: [i32; 3],
+
+    /// Maximum number of raises per street (e.g., `3` for bet - raise - 3-bet - cap). Must be
+    /// non-negative.
+    pub raise_cap: i32,
+}
+
+impl FixedLimit {
+    /// Creates a fixed-limit betting structure with the small bet on the flop and the big bet on
+    /// the turn and the river.
+    #[inline]
+    pub fn new(small_bet: i32, big_bet: i32, raise_cap: i32) -> Self {
+        Self {
+            bet_units: [small_bet, big_bet, big_bet],
+            raise_cap,
+        }
+    }
+}
+
+impl TreeConfig {
+    /// Sets the starting pot, the effective stack, the stacks, and the dead money from the stacks
+    /// of OOP and IP at the start of the hand.
+    ///
+    /// `committed` is the amount that each of OOP and IP has already put into the pot, and
+    /// `dead_money` is the amount put into the pot by other players.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// // 3-bet pot: the SB folded, and the BTN (2500 chips) called a 3-bet of the BB (1800 chips)
+    /// let mut tree_config = TreeConfig::default();
+    /// tree_config.set_stacks([1800, 2500], 240, 10);
+    /// assert_eq!(tree_config.starting_pot, 490);
+    /// assert_eq!(tree_config.effective_stack, 1560);
+    /// assert_eq!(tree_config.stacks, [1560, 2260]);
+    /// assert_eq!(tree_config.committed_amount(), 240.0);
+    /// ```
+    #[inline]
+    pub fn set_stacks(&mut self, hand_stacks: [i32; 2], committed: i32, dead_money: i32) {
+        self.stacks = hand_stacks.map(|stack| stack - committed);
+        self.starting_pot = 2 * committed + dead_money;
+        self.effective_stack = self.stacks[0].min(self.stacks[1]);
+        self.dead_money = dead_money;
+    }
+
+    /// Returns the initial stacks of OOP and IP behind the starting pot.
+    #[inline]
+    pub fn player_stacks(&self) -> [i32; 2] {
+        if self.stacks == [0, 0] {
+            [self.effective_stack; 2]
+        } else {
+            self.stacks
+        }
+    }
+
+    /// Returns the amount that each of OOP and IP has committed to the starting pot.
+    #[inline]
+    pub fn committed_amount(&self) -> f64 {
+        (self.starting_pot - self.dead_money) as f64 * 0.5
+    }
 }
 
 /// A struct representing an abstract game tree.
@@ -201,8 +361,8 @@ type EjectedActionTree = (
 impl ActionTree {
     /// Creates a new [`ActionTree`] with the specified configuration.
     #[inline]
-    pub fn new(config: TreeConfig) -> Result<Self, String> {
-        Self::check_config(&config)?;
+    pub fn new(config: TreeConfig) -> Result<Self, Error> {
+        Self::check_config(&config).map_err(Error::InvalidConfig)?;
         let mut ret = Self {
             config,
             ..Default::default()
@@ -211,6 +371,42 @@ impl ActionTree {
         Ok(ret)
     }
 
+    /// Creates a new [`ActionTree`] from an explicit tree description.
+    ///
+    /// Unlike [`new`], the actions of each node are taken from `root` (see [`ActionTreeNodeData`])
+    /// instead of being generated from the bet sizes. Therefore, the bet sizes and the thresholds
+    /// in `config` are not used; the other fields, such as the starting pot, the effective stack,
+    /// and the betting structure, are used to validate the actions.
+    ///
+    /// - Every action must be legal at its node, and every non-terminal node must have at least one
+    ///   action and the same number of children.
+    /// - The `player`, `board_state`, and `amount` of each node must match the values implied by
+    ///   the action sequence.
+    /// - A bet or raise action of the whole stack is converted into an all-in action.
+    ///
+    /// [`new`]: #method.new
+    pub fn from_data(config: TreeConfig, root: &ActionTreeNodeData) -> Result<Self, Error> {
+        Self::check_config(&config).map_err(Error::InvalidConfig)?;
+        let ret = Self {
+            config,
+            ..Default::default()
+        };
+
+        {
+            let mut root_node = ret.root.lock();
+            root_node.board_state = ret.config.initial_state;
+            ret.import_recursive(
+                &mut root_node,
+                root,
+                BuildTreeInfo::new(ret.config.effective_stack),
+                &mut Vec::new(),
+            )
+            .map_err(Error::InvalidTreeData)?;
+        }
+
+        Ok(ret)
+    }
+
     /// Obtains the configuration of the game tree.
     #[inline]
     pub fn config(&self) -> &TreeConfig {
@@ -238,6 +434,26 @@ impl ActionTree {
         ret
     }
 
+    /// Returns a report of degenerate and suspicious lines in the action tree.
+    ///
+    /// The report contains the invalid terminals, the configured bet sizes that are adjusted when
+    /// building the tree (see [`LintKind`] for the details), and if `max_raises` is `Some(n)`, the
+    /// lines where a single street contains more than `n` raises. The bet sizes are checked against
+    /// the configuration, so the actions added by [`add_line`] are not reported.
+    ///
+    /// [`add_line`]: #method.add_line
+    pub fn lint(&self, max_raises: Option<i32>) -> Vec<LintIssue> {
+        let mut ret = Vec::new();
+        self.lint_recursive(
+            &self.root.lock(),
+            BuildTreeInfo::new(self.config.effective_stack),
+            max_raises,
+            &mut Vec::new(),
+            &mut ret,
+        );
+        ret
+    }
+
     /// Adds a given line to the action tree.
     ///
     /// - `line` except the last action must exist in the current tree.
@@ -246,14 +462,16 @@ impl ActionTree {
     ///   must be a bet action (including raise and all-in action).
     /// - Chance actions (i.e., dealing turn and river cards) must be omitted from the `line`.
     #[inline]
-    pub fn add_line(&mut self, line: &[Action]) -> Result<(), String> {
+    pub fn add_line(&mut self, line: &[Action]) -> Result<(), Error> {
         let removed_index = self.removed_lines.iter().position(|x| x == line);
-        let is_replaced = self.add_line_recursive(
-            &mut self.root.lock(),
-            line,
-            removed_index.is_some(),
-            BuildTreeInfo::new(self.config.effective_stack),
-        )?;
+        let is_replaced = self
+            .add_line_recursive(
+                &mut self.root.lock(),
+                line,
+                removed_index.is_some(),
+                BuildTreeInfo::new(self.config.effective_stack),
+            )
+            .map_err(Error::InvalidAction)?;
         if let Some(index) = removed_index {
             self.removed_lines.remove(index);
         } else {
@@ -275,8 +493,8 @@ impl ActionTree {
     /// - If the current node is removed by this method, the current node is moved to the nearest
     ///   ancestor node that is not removed.
     #[inline]
-    pub fn remove_line(&mut self, line: &[Action]) -> Result<(), String> {
-        Self::remove_line_recursive(&mut self.root.lock(), line)?;
+    pub fn remove_line(&mut self, line: &[Action]) -> Result<(), Error> {
+        Self::remove_line_recursive(&mut self.root.lock(), line).map_err(Error::InvalidAction)?;
         let was_added = self.added_lines.iter().any(|l| l == line);
         self.added_lines.retain(|l| !l.starts_with(line));
         self.removed_lines.retain(|l| !l.starts_with(line));
@@ -303,7 +521,7 @@ impl ActionTree {
 
     /// Applies the given action history from the root node.
     #[inline]
-    pub fn apply_history(&mut self, history: &[Action]) -> Result<(), String> {
+    pub fn apply_history(&mut self, history: &[Action]) -> Result<(), Error> {
         self.back_to_root();
         for &action in history {
             self.play(action)?;
@@ -337,10 +555,12 @@ impl ActionTree {
     /// If the current node is a chance node, the chance action is automatically played before
     /// playing the given action.
     #[inline]
-    pub fn play(&mut self, action: Action) -> Result<(), String> {
+    pub fn play(&mut self, action: Action) -> Result<(), Error> {
         let node = self.current_node_skip_chance();
         if !node.actions.contains(&action) {
-            return Err(format!("Action `{action:?}` is not available"));
+            return Err(Error::InvalidAction(format!(
+                "Action `{action:?}` is not available"
+            )));
         }
 
         self.history.push(action);
@@ -349,9 +569,9 @@ impl ActionTree {
 
     /// Undoes the last action. Returns `Ok(())` if the action is successfully undone.
     #[inline]
-    pub fn undo(&mut self) -> Result<(), String> {
+    pub fn undo(&mut self) -> Result<(), Error> {
         if self.history.is_empty() {
-            return Err("No action to undo".to_string());
+            return Err(Error::InvalidState("No action to undo".to_string()));
         }
 
         self.history.pop();
@@ -365,7 +585,7 @@ impl ActionTree {
     ///
     /// [`add_line`]: #method.add_line
     #[inline]
-    pub fn add_action(&mut self, action: Action) -> Result<(), String> {
+    pub fn add_action(&mut self, action: Action) -> Result<(), Error> {
         let mut action_line = self.history.clone();
         action_line.push(action);
         self.add_line(&action_line)
@@ -378,7 +598,7 @@ impl ActionTree {
     ///
     /// [`remove_line`]: #method.remove_line
     #[inline]
-    pub fn remove_action(&mut self, action: Action) -> Result<(), String> {
+    pub fn remove_action(&mut self, action: Action) -> Result<(), Error> {
         let mut action_line = self.history.clone();
         action_line.push(action);
         self.remove_line(&action_line)
@@ -391,7 +611,7 @@ impl ActionTree {
     ///
     /// [`remove_line`]: #method.remove_line
     #[inline]
-    pub fn remove_current_node(&mut self) -> Result<(), String> {
+    pub fn remove_current_node(&mut self) -> Result<(), Error> {
         let history = self.history.clone();
         self.remove_line(&history)
     }
@@ -409,17 +629,23 @@ impl ActionTree {
         (self.config, self.added_lines, self.removed_lines, self.root)
     }
 
+    /// Returns the root node.
+    #[inline]
+    pub(crate) fn root(&self) -> MutexGuardLike<'_, ActionTreeNode> {
+        self.root.lock()
+    }
+
     /// Returns the reference to the current node.
     #[inline]
-    fn current_node(&self) -> &ActionTreeNode {
+    pub(crate) fn current_node(&self) -> &ActionTreeNode {
         unsafe {
             let mut node = &*self.root.lock() as *const ActionTreeNode;
             for action in &self.history {
                 while (*node).is_chance() {
-                    node = &*(*node).children[0].lock();
+                    node = &*(&(*node).children)[0].lock();
                 }
                 let index = (*node).actions.iter().position(|x| x == action).unwrap();
-                node = &*(*node).children[index].lock();
+                node = &*(&(*node).children)[index].lock();
             }
             &*node
         }
@@ -431,7 +657,7 @@ impl ActionTree {
         unsafe {
             let mut node = self.current_node() as *const ActionTreeNode;
             while (*node).is_chance() {
-                node = &*(*node).children[0].lock();
+                node = &*(&(*node).children)[0].lock();
             }
             &*node
         }
@@ -454,6 +680,33 @@ impl ActionTree {
             ));
         }
 
+        if config.stacks != [0, 0]
+            && config.stacks[0].min(config.stacks[1]) != config.effective_stack
+        {
+            return Err(format!(
+                "Effective stack must be the smaller of the stacks: effective_stack = {}, stacks = {:?}",
+                config.effective_stack, config.stacks
+            ));
+        }
+
+        if config.dead_money < 0 || config.dead_money > config.starting_pot {
+            return Err(format!(
+                "Dead money must be between 0 and the starting pot: {}",
+                config.dead_money
+            ));
+        }
+
+        if config.big_blind < 0 {
+            return Err(format!(
+                "Big blind must be non-negative: {}",
+                config.big_blind
+            ));
+        }
+
+        if config.big_blind == 0 && Self::has_big_blind_sizes(config) {
+            return Err("Big blind must be specified to use \"bb\" bet sizes".to_string());
+        }
+
         if config.rake_rate < 0.0 {
             return Err(format!(
                 "Rake rate must be non-negative: {}",
@@ -475,6 +728,26 @@ impl ActionTree {
             ));
         }
 
+        if let Some(fixed_limit) = &config.fixed_limit {
+            if config.pot_limit {
+                return Err("Fixed-limit and pot-limit betting cannot be combined".to_string());
+            }
+
+            if fixed_limit.bet_units.iter().any(|&unit| unit <= 0) {
+                return Err(format!(
+                    "Bet units must be positive: {:?}",
+                    fixed_limit.bet_units
+                ));
+            }
+
+            if fixed_limit.raise_cap < 0 {
+                return Err(format!(
+                    "Raise cap must be non-negative: {}",
+                    fixed_limit.raise_cap
+                ));
+            }
+        }
+
         if config.add_allin_threshold < 0.0 {
             return Err(format!(
                 "Add all-in threshold must be non-negative: {}",
@@ -499,6 +772,29 @@ impl ActionTree {
         Ok(())
     }
 
+    /// Returns whether any bet size of the configuration is specified in big blinds.
+    fn has_big_blind_sizes(config: &TreeConfig) -> bool {
+        let is_big_blind = |size: &BetSize| matches!(size, BetSize::BigBlindRelative(_));
+        let bet_sizes = config
+            .flop_bet_sizes
+            .iter()
+            .chain(&config.turn_bet_sizes)
+            .chain(&config.river_bet_sizes)
+            .any(|options| {
+                options.bet.iter().any(is_big_blind)
+                    || options.raise.iter().any(is_big_blind)
+                    || options
+                        .rules
+                        .iter()
+                        .any(|rule| rule.sizes.iter().any(is_big_blind))
+            });
+        let donk_sizes = [&config.turn_donk_sizes, &config.river_donk_sizes]
+            .into_iter()
+            .flatten()
+            .any(|options| options.donk.iter().any(is_big_blind));
+        bet_sizes || donk_sizes
+    }
+
     /// Builds the action tree.
     #[inline]
     fn build_tree(&mut self) {
@@ -552,6 +848,61 @@ impl ActionTree {
     fn push_actions(&self, node: &mut ActionTreeNode, info: &BuildTreeInfo) {
         let player = node.player;
         let opponent = node.player ^ 1;
+        let to_call = info.stack[player as usize] - info.stack[opponent as usize];
+
+        let actions = self.compute_actions(node, info, None);
+
+        let player_after_call = match node.board_state {
+            BoardState::River => PLAYER_TERMINAL_FLAG,
+            _ => PLAYER_CHANCE_FLAG | player,
+        };
+
+        let player_after_check = match player {
+            PLAYER_OOP => opponent,
+            _ => player_after_call,
+        };
+
+        // push actions
+        for action in actions {
+            let mut amount = node.amount;
+            let next_player = match action {
+                Action::Fold => PLAYER_FOLD_FLAG | player,
+                Action::Check => player_after_check,
+                Action::Call => {
+                    amount += to_call;
+                    player_after_call
+                }
+                Action::Bet(_) | Action::Raise(_) | Action::AllIn(_) => {
+                    amount += to_call;
+                    opponent
+                }
+                _ => panic!("Unexpected action: {action:?}"),
+            };
+
+            node.actions.push(action);
+            node.children.push(MutexLike::new(ActionTreeNode {
+                player: next_player,
+                board_state: node.board_state,
+                amount,
+                ..Default::default()
+            }));
+        }
+
+        node.actions.shrink_to_fit();
+        node.children.shrink_to_fit();
+    }
+
+    /// Computes the actions of the given node from the configuration.
+    ///
+    /// If `lints` is given, the adjustments made to the configured bet sizes are recorded.
+    fn compute_actions(
+        &self,
+        node: &ActionTreeNode,
+        info: &BuildTreeInfo,
+        mut lints: Option<&mut Vec<LintKind>>,
+    ) -> Vec<Action> {
+        let player = node.player;
+        let opponent = node.player ^ 1;
 
         let player_stack = info.stack[player as usize];
         let opponent_stack = info.stack[opponent as usize];
@@ -560,7 +911,8 @@ impl ActionTree {
 
         let pot = self.config.starting_pot + 2 * (node.amount + to_call);
         let max_amount = opponent_stack + prev_amount;
-        let min_amount = (prev_amount + to_call).clamp(1, max_amount);
+        let limit_amount = self.limit_amount(node.board_state, max_amount, prev_amount, pot);
+        let min_amount = (prev_amount + to_call).clamp(1, limit_amount);
 
         let spr_after_call = opponent_stack as f64 / pot as f64;
         let compute_geometric = |num_streets: i32, max_ratio: f64| {
@@ -580,7 +932,22 @@ impl ActionTree {
 
         let mut actions = Vec::new();
 
-        if donk_options.is_some()
+        if let Some(fixed_limit) = &self.config.fixed_limit {
+            let bet_unit = fixed_limit.bet_units[node.board_state as usize];
+            if matches!(
+                info.prev_action,
+                Action::None | Action::Check | Action::Chance(_)
+            ) {
+                actions.push(Action::Check);
+                actions.push(Action::Bet(bet_unit));
+            } else {
+                actions.push(Action::Fold);
+                actions.push(Action::Call);
+                if !info.allin_flag && info.num_bets <= fixed_limit.raise_cap {
+                    actions.push(Action::Raise(prev_amount + bet_unit));
+                }
+            }
+        } else if donk_options.is_some()
             && matches!(info.prev_action, Action::Chance(_))
             && info.oop_call_flag
         {
@@ -596,6 +963,9 @@ impl ActionTree {
                     }
                     BetSize::PrevBetRelative(_) => panic!("Unexpected `PrevBetRelative`"),
                     BetSize::Additive(adder, _) => actions.push(Action::Bet(adder)),
+                    BetSize::BigBlindRelative(big_blinds) => {
+                        actions.push(Action::Bet(self.big_blind_amount(big_blinds)));
+                    }
                     BetSize::Geometric(num_streets, max_ratio) => {
                         let num_streets = match num_streets {
                             0 => num_remaining_streets,
@@ -620,7 +990,7 @@ impl ActionTree {
             actions.push(Action::Check);
 
             // bet
-            for &bet_size in &bet_options[player as usize].bet {
+            for &bet_size in bet_options[player as usize].sizes(0, spr_after_call) {
                 match bet_size {
                     BetSize::PotRelative(ratio) => {
                         let amount = (pot as f64 * ratio).round() as i32;
@@ -628,6 +998,9 @@ impl ActionTree {
                     }
                     BetSize::PrevBetRelative(_) => panic!("Unexpected `PrevBetRelative`"),
                     BetSize::Additive(adder, _) => actions.push(Action::Bet(adder)),
+                    BetSize::BigBlindRelative(big_blinds) => {
+                        actions.push(Action::Bet(self.big_blind_amount(big_blinds)));
+                    }
                     BetSize::Geometric(num_streets, max_ratio) => {
                         let num_streets = match num_streets {
                             0 => num_remaining_streets,
@@ -653,7 +1026,8 @@ impl ActionTree {
 
             if !info.allin_flag {
                 // raise
-                for &bet_size in &bet_options[player as usize].raise {
+                let raise_options = &bet_options[player as usize];
+                for &bet_size in raise_options.sizes(info.num_bets, spr_after_call) {
                     match bet_size {
                         BetSize::PotRelative(ratio) => {
                             let amount = prev_amount + (pot as f64 * ratio).round() as i32;
@@ -668,6 +1042,10 @@ impl ActionTree {
                                 actions.push(Action::Raise(prev_amount + adder));
                             }
                         }
+                        BetSize::BigBlindRelative(big_blinds) => {
+                            let amount = prev_amount + self.big_blind_amount(big_blinds);
+                            actions.push(Action::Raise(amount));
+                        }
                         BetSize::Geometric(num_streets, max_ratio) => {
                             let num_streets = match num_streets {
                                 0 => i32::max(num_remaining_streets - info.num_bets + 1, 1),
@@ -688,18 +1066,54 @@ impl ActionTree {
             }
         }
 
+        let force_allin_threshold = match self.config.fixed_limit {
+            Some(_) => 0.0,
+            None => self.config.force_allin_threshold,
+        };
+
         let is_above_threshold = |amount: i32| {
             let new_amount_diff = amount - prev_amount;
             let new_pot = pot + 2 * new_amount_diff;
-            let threshold = (new_pot as f64 * self.config.force_allin_threshold).round() as i32;
+            let threshold = (new_pot as f64 * force_allin_threshold).round() as i32;
             max_amount <= amount + threshold
         };
 
+        let mut num_sizes = 0;
+        if let Some(lints) = lints.as_mut() {
+            let mut amounts = actions.iter().filter_map(bet_amount).collect::<Vec<_>>();
+            amounts.sort_unstable();
+            amounts.dedup();
+            num_sizes = amounts.len();
+
+            let mut amounts = actions
+                .iter()
+                .filter_map(|&action| match action {
+                    Action::Bet(amount) | Action::Raise(amount) => Some(amount),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            amounts.sort_unstable();
+            amounts.dedup();
+
+            for amount in amounts {
+                let clamped = amount.clamp(min_amount, limit_amount);
+                if amount < min_amount {
+                    lints.push(LintKind::BelowMinRaise { amount, min_amount });
+                }
+                if clamped < max_amount && is_above_threshold(clamped) {
+                    lints.push(LintKind::ForcedAllIn {
+                        amount: clamped,
+                        allin: max_amount,
+                    });
+                }
+            }
+        }
+
         // clamp bet amounts
         for action in actions.iter_mut() {
             match *action {
                 Action::Bet(amount) => {
-                    let clamped = amount.clamp(min_amount, max_amount);
+                    let clamped = amount.clamp(min_amount, limit_amount);
                     if is_above_threshold(clamped) {
                         *action = Action::AllIn(max_amount);
                     } else if clamped != amount {
@@ -707,7 +1121,7 @@ impl ActionTree {
                     }
                 }
                 Action::Raise(amount) => {
-                    let clamped = amount.clamp(min_amount, max_amount);
+                    let clamped = amount.clamp(min_amount, limit_amount);
                     if is_above_threshold(clamped) {
                         *action = Action::AllIn(max_amount);
                     } else if clamped != amount {
@@ -718,51 +1132,77 @@ impl ActionTree {
             }
         }
 
+        // replace all-in actions exceeding the pot limit
+        if limit_amount < max_amount {
+            for action in actions.iter_mut() {
+                if let Action::AllIn(_) = *action {
+                    *action = match prev_amount {
+                        0 => Action::Bet(limit_amount),
+                        _ => Action::Raise(limit_amount),
+                    };
+                }
+            }
+        }
+
         // remove duplicates
         actions.sort_unstable();
         actions.dedup();
 
         // merge bet actions with close amounts
-        actions = merge_bet_actions(actions, pot, prev_amount, self.config.merging_threshold);
-
-        let player_after_call = match node.board_state {
-            BoardState::River => PLAYER_TERMINAL_FLAG,
-            _ => PLAYER_CHANCE_FLAG | player,
+        let lints = match lints {
+            Some(lints) => lints,
+            None => {
+                return merge_bet_actions(actions, pot, prev_amount, self.config.merging_threshold)
+            }
         };
 
-        let player_after_check = match player {
-            PLAYER_OOP => opponent,
-            _ => player_after_call,
-        };
+        let merged = merge_bet_actions(
+            actions.clone(),
+            pot,
+            prev_amount,
+            self.config.merging_threshold,
+        );
 
-        // push actions
-        for action in actions {
-            let mut amount = node.amount;
-            let next_player = match action {
-                Action::Fold => PLAYER_FOLD_FLAG | player,
-                Action::Check => player_after_check,
-                Action::Call => {
-                    amount += to_call;
-                    player_after_call
-                }
-                Action::Bet(_) | Action::Raise(_) | Action::AllIn(_) => {
-                    amount += to_call;
-                    opponent
-                }
-                _ => panic!("Unexpected action: {action:?}"),
-            };
+        let num_bets = actions.iter().filter_map(bet_amount).count();
+        if num_sizes > 1 && num_bets == 1 {
+            let amount = actions.iter().find_map(bet_amount).unwrap();
+            lints.push(LintKind::SameAmount { amount });
+        }
 
-            node.actions.push(action);
-            node.children.push(MutexLike::new(ActionTreeNode {
-                player: next_player,
-                board_state: node.board_state,
-                amount,
-                ..Default::default()
-            }));
+        for &action in actions.iter().filter(|a| !merged.contains(a)) {
+            let amount = bet_amount(&action).unwrap();
+            let into = merged.iter().find(|a| bet_amount(a) > Some(amount));
+            lints.push(LintKind::Merged {
+                action,
+                into: *into.unwrap(),
+            });
         }
 
-        node.actions.shrink_to_fit();
-        node.children.shrink_to_fit();
+        merged
+    }
+
+    /// Converts the bet size in big blinds into chips.
+    #[inline]
+    fn big_blind_amount(&self, big_blinds: f64) -> i32 {
+        (self.config.big_blind as f64 * big_blinds).round() as i32
+    }
+
+    /// Returns the maximum bet amount allowed by the betting structure.
+    #[inline]
+    fn limit_amount(
+        &self,
+        board_state: BoardState,
+        max_amount: i32,
+        prev_amount: i32,
+        pot: i32,
+    ) -> i32 {
+        if let Some(fixed_limit) = &self.config.fixed_limit {
+            max_amount.min(prev_amount + fixed_limit.bet_units[board_state as usize])
+        } else if self.config.pot_limit {
+            max_amount.min(prev_amount + pot)
+        } else {
+            max_amount
+        }
     }
 
     /// Recursive function to enumerate all invalid terminal nodes.
@@ -786,6 +1226,58 @@ impl ActionTree {
         }
     }
 
+    /// Recursive function to find degenerate and suspicious lines.
+    fn lint_recursive(
+        &self,
+        node: &ActionTreeNode,
+        info: BuildTreeInfo,
+        max_raises: Option<i32>,
+        line: &mut Vec<Action>,
+        result: &mut Vec<LintIssue>,
+    ) {
+        if node.is_terminal() {
+            // do nothing
+        } else if node.children.is_empty() {
+            result.push(LintIssue {
+                line: line.clone(),
+                kind: LintKind::InvalidTerminal,
+            });
+        } else if node.is_chance() {
+            let next_info = info.create_next(0, Action::Chance(0));
+            self.lint_recursive(
+                &node.children[0].lock(),
+                next_info,
+                max_raises,
+                line,
+                result,
+            );
+        } else {
+            let mut lints = Vec::new();
+            self.compute_actions(node, &info, Some(&mut lints));
+            result.extend(lints.into_iter().map(|kind| LintIssue {
+                line: line.clone(),
+                kind,
+            }));
+
+            for (&action, child) in node.actions.iter().zip(node.children.iter()) {
+                let next_info = info.create_next(node.player, action);
+                line.push(action);
+
+                let is_bet_action = matches!(action, Action::Raise(_) | Action::AllIn(_));
+                let num_raises = next_info.num_bets - 1;
+                if is_bet_action && max_raises == Some(num_raises - 1) {
+                    result.push(LintIssue {
+                        line: line.clone(),
+                        kind: LintKind::TooManyRaises { num_raises },
+                    });
+                }
+
+                self.lint_recursive(&child.lock(), next_info, max_raises, line, result);
+                line.pop();
+            }
+        }
+    }
+
     /// Recursive function to add a given line to the tree.
     fn add_line_recursive(
         &self,
@@ -834,59 +1326,10 @@ impl ActionTree {
             return Err(format!("Action already exists: {action:?}"));
         }
 
-        let is_bet_action = matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn(_));
-        if info.allin_flag && is_bet_action {
-            return Err(format!("Bet action after all-in: {action:?}"));
-        }
-
-        let player_stack = info.stack[player as usize];
-        let opponent_stack = info.stack[opponent as usize];
-        let prev_amount = info.prev_amount;
-        let to_call = player_stack - opponent_stack;
-
-        let max_amount = opponent_stack + prev_amount;
-        let min_amount = (prev_amount + to_call).clamp(1, max_amount);
-
-        let mut is_replaced = false;
-        let action = match action {
-            Action::Bet(amount) | Action::Raise(amount) if amount == max_amount => {
-                is_replaced = true;
-                Action::AllIn(amount)
-            }
-            _ => action,
-        };
-
-        let is_valid_bet = match action {
-            Action::Bet(amount) if amount >= min_amount && amount < max_amount => {
-                matches!(
-                    info.prev_action,
-                    Action::None | Action::Check | Action::Chance(_)
-                )
-            }
-            Action::Raise(amount) if amount >= min_amount && amount < max_amount => {
-                matches!(info.prev_action, Action::Bet(_) | Action::Raise(_))
-            }
-            Action::AllIn(amount) => amount == max_amount,
-            _ => false,
-        };
-
-        if !was_removed && !is_valid_bet {
-            match action {
-                Action::Bet(amount) | Action::Raise(amount) => {
-                    return Err(format!(
-                        "Invalid bet amount: {amount} (min: {min_amount}, max: {max_amount})"
-                    ));
-                }
-                Action::AllIn(amount) => {
-                    return Err(format!(
-                        "Invalid all-in amount: {amount} (expected: {max_amount})"
-                    ));
-                }
-                _ => {
-                    return Err(format!("Invalid action: {action:?}"));
-                }
-            };
-        }
+        let to_call = info.stack[player as usize] - info.stack[opponent as usize];
+        let checked_action = self.check_bet_action(node, action, &info, was_removed)?;
+        let is_replaced = checked_action != action;
+        let action = checked_action;
 
         let player_after_call = match node.board_state {
             BoardState::River => PLAYER_TERMINAL_FLAG,
@@ -936,6 +1379,244 @@ impl ActionTree {
         Ok(is_replaced)
     }
 
+    /// Recursive function to import a tree description.
+    fn import_recursive(
+        &self,
+        node: &mut ActionTreeNode,
+        data: &ActionTreeNodeData,
+        info: BuildTreeInfo,
+        line: &mut Vec<Action>,
+    ) -> Result<(), String> {
+        let expected_player = player_to_string(node);
+        let expected_state = board_state_to_string(node.board_state);
+        if data.player != expected_player || data.board_state != expected_state {
+            return Err(format!(
+                "Node mismatch at {line:?}: expected {expected_player} on the {expected_state}, \
+                 found {} on the {}",
+                data.player, data.board_state
+            ));
+        }
+
+        if data.amount != node.amount {
+            return Err(format!(
+                "Amount mismatch at {line:?}: expected {}, found {}",
+                node.amount, data.amount
+            ));
+        }
+
+        if node.is_terminal() {
+            if !data.actions.is_empty() {
+                return Err(format!("Terminal node has actions at {line:?}"));
+            }
+            return Ok(());
+        }
+
+        if data.actions.is_empty() {
+            return Err(format!("Non-terminal node has no actions at {line:?}"));
+        }
+
+        if data.children.len() != data.actions.len() {
+            return Err(format!(
+                "Number of children does not match actions at {line:?}: {} != {}",
+                data.children.len(),
+                data.actions.len()
+            ));
+        }
+
+        if node.is_chance() {
+            if data.actions != ["Chance"] {
+                return Err(format!(
+                    "Chance node must have a single `Chance` action at {line:?}"
+                ));
+            }
+
+            let next_state = match node.board_state {
+                BoardState::Flop => BoardState::Turn,
+                BoardState::Turn => BoardState::River,
+                BoardState::River => unreachable!(),
+            };
+
+            let next_player = match (info.allin_flag, node.board_state) {
+                (false, _) => PLAYER_OOP,
+                (true, BoardState::Flop) => PLAYER_CHANCE_FLAG | PLAYER_CHANCE,
+                (true, _) => PLAYER_TERMINAL_FLAG,
+            };
+
+            node.actions.push(Action::Chance(0));
+            node.children.push(MutexLike::new(ActionTreeNode {
+                player: next_player,
+                board_state: next_state,
+                amount: node.amount,
+                ..Default::default()
+            }));
+
+            return self.import_recursive(
+                &mut node.children[0].lock(),
+                &data.children[0],
+                info.create_next(0, Action::Chance(0)),
+                line,
+            );
+        }
+
+        let player = node.player;
+        let opponent = node.player ^ 1;
+        let to_call = info.stack[player as usize] - info.stack[opponent as usize];
+
+        let mut entries = Vec::with_capacity(data.actions.len());
+        for (action, child) in data.actions.iter().zip(&data.children) {
+            let action = action_from_str(action)?;
+            let action = match action {
+                Action::Fold | Action::Call if to_call > 0 => action,
+                Action::Check if to_call == 0 => action,
+                Action::Bet(_) | Action::Raise(_) | Action::AllIn(_) => self
+                    .check_bet_action(node, action, &info, false)
+                    .map_err(|e| format!("{e} at {line:?}"))?,
+                _ => return Err(format!("Invalid action at {line:?}: {action:?}")),
+            };
+            entries.push((action, child));
+        }
+
+        entries.sort_unstable_by_key(|&(action, _)| action);
+        for pair in entries.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(format!("Duplicated action at {line:?}: {:?}", pair[0].0));
+            }
+        }
+
+        let player_after_call = match node.board_state {
+            BoardState::River => PLAYER_TERMINAL_FLAG,
+            _ => PLAYER_CHANCE_FLAG | player,
+        };
+
+        let player_after_check = match player {
+            PLAYER_OOP => opponent,
+            _ => player_after_call,
+        };
+
+        for &(action, _) in &entries {
+            let mut amount = node.amount;
+            let next_player = match action {
+                Action::Fold => PLAYER_FOLD_FLAG | player,
+                Action::Check => player_after_check,
+                Action::Call => {
+                    amount += to_call;
+                    player_after_call
+                }
+                _ => {
+                    amount += to_call;
+                    opponent
+                }
+            };
+
+            node.actions.push(action);
+            node.children.push(MutexLike::new(ActionTreeNode {
+                player: next_player,
+                board_state: node.board_state,
+                amount,
+                ..Default::default()
+            }));
+        }
+
+        for (index, &(action, child_data)) in entries.iter().enumerate() {
+            line.push(action);
+            self.import_recursive(
+                &mut node.children[index].lock(),
+                child_data,
+                info.create_next(player, action),
+                line,
+            )?;
+            line.pop();
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the given action can be added to the node. A bet or raise action of the
+    /// whole stack is converted into an all-in action. If `was_removed` is true, the bet amount is
+    /// not checked.
+    fn check_bet_action(
+        &self,
+        node: &ActionTreeNode,
+        action: Action,
+        info: &BuildTreeInfo,
+        was_removed: bool,
+    ) -> Result<Action, String> {
+        let is_bet_action = matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn(_));
+        if info.allin_flag && is_bet_action {
+            return Err(format!("Bet action after all-in: {action:?}"));
+        }
+
+        let player = node.player;
+        let opponent = node.player ^ 1;
+        let player_stack = info.stack[player as usize];
+        let opponent_stack = info.stack[opponent as usize];
+        let prev_amount = info.prev_amount;
+        let to_call = player_stack - opponent_stack;
+
+        let pot = self.config.starting_pot + 2 * (node.amount + to_call);
+        let max_amount = opponent_stack + prev_amount;
+        let limit_amount = self.limit_amount(node.board_state, max_amount, prev_amount, pot);
+        let min_amount = match self.config.fixed_limit {
+            Some(_) => limit_amount,
+            None => (prev_amount + to_call).clamp(1, limit_amount),
+        };
+
+        if let Some(fixed_limit) = &self.config.fixed_limit {
+            if is_bet_action && info.num_bets > fixed_limit.raise_cap {
+                return Err(format!("Raise cap exceeded: {action:?}"));
+            }
+        }
+
+        let action = match action {
+            Action::Bet(amount) | Action::Raise(amount) if amount == max_amount => {
+                Action::AllIn(amount)
+            }
+            _ => action,
+        };
+
+        let is_valid_amount =
+            |amount: i32| amount >= min_amount && amount < max_amount && amount <= limit_amount;
+
+        let is_valid_bet = match action {
+            Action::Bet(amount) if is_valid_amount(amount) => {
+                matches!(
+                    info.prev_action,
+                    Action::None | Action::Check | Action::Chance(_)
+                )
+            }
+            Action::Raise(amount) if is_valid_amount(amount) => {
+                matches!(info.prev_action, Action::Bet(_) | Action::Raise(_))
+            }
+            Action::AllIn(amount) => amount == max_amount && amount <= limit_amount,
+            _ => false,
+        };
+
+        if !was_removed && !is_valid_bet {
+            match action {
+                Action::Bet(amount) | Action::Raise(amount) => {
+                    return Err(format!(
+                        "Invalid bet amount: {amount} (min: {min_amount}, max: {limit_amount})"
+                    ));
+                }
+                Action::AllIn(amount) if amount > limit_amount => {
+                    return Err(format!(
+                        "Invalid all-in amount: {amount} (exceeds pot limit: {limit_amount})"
+                    ));
+                }
+                Action::AllIn(amount) => {
+                    return Err(format!(
+                        "Invalid all-in amount: {amount} (expected: {max_amount})"
+                    ));
+                }
+                _ => {
+                    return Err(format!("Invalid action: {action:?}"));
+                }
+            };
+        }
+
+        Ok(action)
+    }
+
     /// Recursive function to remove a given line from the tree.
     fn remove_line_recursive(node: &mut ActionTreeNode, line: &[Action]) -> Result<(), String> {
         if line.is_empty() {
@@ -1003,12 +1684,12 @@ impl ActionTree {
 
 impl ActionTreeNode {
     #[inline]
-    fn is_terminal(&self) -> bool {
+    pub(crate) fn is_terminal(&self) -> bool {
         self.player & PLAYER_TERMINAL_FLAG != 0
     }
 
     #[inline]
-    fn is_chance(&self) -> bool {
+    pub(crate) fn is_chance(&self) -> bool {
         self.player & PLAYER_CHANCE_FLAG != 0
     }
 }
@@ -1090,7 +1771,15 @@ fn count_num_action_nodes_recursive(node: &ActionTreeNode, street: usize, count:
     }
 }
 
-fn merge_bet_actions(actions: Vec<Action>, pot: i32, offset: i32, param: f64) -> Vec<Action> {
+#[inline]
+fn bet_amount(action: &Action) -> Option<i32> {
+    match *action {
+        Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => Some(amount),
+        _ => None,
+    }
+}
+
+pub(crate) fn merge_bet_actions(actions: Vec<Action>, pot: i32, offset: i32, param: f64) -> Vec<Action> {
     const EPS: f64 = 1e-12;
 
     let get_amount = |action: Action| match action {
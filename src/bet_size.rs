@@ -1,14 +1,20 @@
+use crate::error::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
 #[cfg(feature = "bincode")]
 use bincode::{Decode, Encode};
 
 /// Bet size options for the first bets and raises.
 ///
 /// In the `try_from()` method, multiple bet sizes can be specified using a comma-separated string.
-/// Each element must be a string ending in one of the following characters: %, x, c, r, e, a.
+/// Each element must be a string ending in one of the following characters: %, x, c, r, bb, e, a.
 ///
 /// - %: Percentage of the pot. (e.g., "70%")
 /// - x: Multiple of the previous bet. Valid for only raises. (e.g., "2.5x")
 /// - c: Constant value. Must be an integer. (e.g., "100c")
+/// - bb: Constant value in big blinds (see [`TreeConfig::big_blind`]). The amount in chips is
+///   rounded to the nearest integer. (e.g., "2.5bb")
 /// - c + r: Constant value with raise cap (for FLHE). Both values must be integers.
 ///          Valid only for raises. (e.g., "20c3r")
 /// - e: Geometric size.
@@ -23,7 +29,7 @@ use bincode::{Decode, Encode};
 /// use postflop_solver::BetSize::*;
 /// use postflop_solver::BetSizeOptions;
 ///
-/// let bet_size = BetSizeOptions::try_from(("50%, 100c, 2e, a", "2.5x")).unwrap();
+/// let bet_size = BetSizeOptions::try_from(("50%, 100c, 2e, a", "2.5x, 10bb")).unwrap();
 ///
 /// assert_eq!(
 ///     bet_size.bet,
@@ -35,9 +41,58 @@ use bincode::{Decode, Encode};
 ///    ]
 /// );
 ///
-/// assert_eq!(bet_size.raise, vec![PrevBetRelative(2.5)]);
+/// assert_eq!(bet_size.raise, vec![PrevBetRelative(2.5), BigBlindRelative(10.0)]);
+/// ```
+///
+/// [`TreeConfig::big_blind`]: crate::TreeConfig::big_blind
+///
+/// # Rules
+/// The `bet` and `raise` sizes can be overridden by rules that depend on the raise depth and the
+/// SPR (see [`BetSizeRule`]). The first matching rule is used.
+///
+/// ```
+/// use postflop_solver::BetSize::*;
+/// use postflop_solver::BetSizeOptions;
+///
+/// let bet_size = BetSizeOptions::try_from(("50%, 100%", "3x"))
+///     .unwrap()
+///     .with_rule("spr<2", "33%, a")
+///     .unwrap()
+///     .with_rule("depth=2", "2.5x")
+///     .unwrap()
+///     .with_rule("depth>=3", "a")
+///     .unwrap();
+///
+/// assert_eq!(bet_size.sizes(0, 5.0), [PotRelative(0.5), PotRelative(1.0)]);
+/// assert_eq!(bet_size.sizes(0, 1.5), [PotRelative(0.33), AllIn]);
+/// assert_eq!(bet_size.sizes(1, 5.0), [PrevBetRelative(3.0)]);
+/// assert_eq!(bet_size.sizes(2, 5.0), [PrevBetRelative(2.5)]);
+/// assert_eq!(bet_size.sizes(3, 5.0), [AllIn]);
+/// ```
+///
+/// # Serialization
+/// With serde, the bet sizes are represented by the same comma-separated strings, and the rules
+/// are omitted if empty.
+///
+/// ```
+/// use postflop_solver::BetSizeOptions;
+///
+/// let bet_size = BetSizeOptions::try_from(("50%, a", "2.5x"))
+///     .unwrap()
+///     .with_rule("spr<2", "33%")
+///     .unwrap();
+///
+/// let json = serde_json::to_string(&bet_size).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"bet":"50%, a","raise":"2.5x","rules":[{"condition":"spr<2","sizes":"33%"}]}"#
+/// );
+///
+/// let deserialized: BetSizeOptions = serde_json::from_str(&json).unwrap();
+/// assert_eq!(deserialized, bet_size);
 /// ```
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "BetSizeOptionsRepr", into = "BetSizeOptionsRepr")]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub struct BetSizeOptions {
     /// Bet size options for first bet.
@@ -45,18 +100,56 @@ pub struct BetSizeOptions {
 
     /// Bet size options for raise.
     pub raise: Vec<BetSize>,
+
+    /// Rules overriding `bet` and `raise` depending on the raise depth and the SPR.
+    pub rules: Vec<BetSizeRule>,
+}
+
+/// A rule that overrides the bet sizes depending on the raise depth and the SPR.
+///
+/// The raise depth is the number of bets and raises already made on the current street: `0` for the
+/// first bet, `1` for a raise, `2` for a 3-bet, and so on. The SPR is the ratio of the effective
+/// stack to the pot after calling the previous bet.
+///
+/// In the `new()` method, the condition is a comma-separated list of the following terms (an empty
+/// string matches any node):
+///
+/// - `depth=N`, `depth>=N`, `depth<=N`: The raise depth is equal to, at least, or at most `N`.
+/// - `spr<X`, `spr>=X`: The SPR is less than or at least `X`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "BetSizeRuleRepr", into = "BetSizeRuleRepr")]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub struct BetSizeRule {
+    /// Minimum raise depth (inclusive).
+    pub min_depth: i32,
+
+    /// Maximum raise depth (inclusive).
+    pub max_depth: i32,
+
+    /// Minimum SPR (inclusive).
+    pub min_spr: f64,
+
+    /// Maximum SPR (exclusive).
+    pub max_spr: f64,
+
+    /// Bet sizes used when the rule matches.
+    pub sizes: Vec<BetSize>,
 }
 
 /// Bet size options for the donk bets.
 ///
 /// See the [`BetSizeOptions`] struct for the description and examples.
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "DonkSizeOptionsRepr", into = "DonkSizeOptionsRepr")]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub struct DonkSizeOptions {
     pub donk: Vec<BetSize>,
 }
 
 /// Bet size specification.
+///
+/// The [`Display`](fmt::Display) implementation gives the string form accepted by
+/// [`BetSizeOptions::try_from`] (e.g., `"70%"`, `"2.5x"`, `"3e200%"`).
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub enum BetSize {
@@ -71,6 +164,14 @@ pub enum BetSize {
     /// If the second element is `0`, there is no raise cap.
     Additive(i32, i32),
 
+    /// Constant bet size in big blinds (see [`TreeConfig::big_blind`]).
+    ///
+    /// Like [`Additive`], the amount is added to the previous bet for raise actions.
+    ///
+    /// [`TreeConfig::big_blind`]: crate::TreeConfig::big_blind
+    /// [`Additive`]: #variant.Additive
+    BigBlindRelative(f64),
+
     /// Geometric bet size for `i32` streets with maximum pot-relative size of `f64`.
     ///
     /// If `i32 == 0`, the number of streets is as follows: flop = 3, turn = 2, river = 1.
@@ -80,13 +181,118 @@ pub enum BetSize {
     AllIn,
 }
 
+impl BetSizeOptions {
+    /// Adds a rule parsed from the condition and the comma-separated bet sizes.
+    ///
+    /// See the [`BetSizeRule`] struct for the syntax of the condition.
+    #[inline]
+    pub fn with_rule(mut self, condition: &str, sizes: &str) -> Result<Self, Error> {
+        self.rules.push(BetSizeRule::new(condition, sizes)?);
+        Ok(self)
+    }
+
+    /// Returns the bet sizes for the given raise depth and SPR.
+    #[inline]
+    pub fn sizes(&self, depth: i32, spr: f64) -> &[BetSize] {
+        match self.rules.iter().find(|rule| rule.matches(depth, spr)) {
+            Some(rule) => &rule.sizes,
+            None if depth == 0 => &self.bet,
+            None => &self.raise,
+        }
+    }
+}
+
+impl BetSizeRule {
+    /// Creates a rule from the condition and the comma-separated bet sizes.
+    ///
+    /// Sizes only valid for raises (e.g., "2.5x") are allowed only if the condition excludes the
+    /// first bet (i.e., `depth>=1`).
+    #[inline]
+    pub fn new(condition: &str, sizes: &str) -> Result<Self, Error> {
+        Self::parse(condition, sizes).map_err(Error::InvalidBetSize)
+    }
+
+    fn parse(condition: &str, sizes: &str) -> Result<Self, String> {
+        let mut rule = Self {
+            min_depth: 0,
+            max_depth: i32::MAX,
+            min_spr: 0.0,
+            max_spr: f64::INFINITY,
+            sizes: Vec::new(),
+        };
+
+        for term in condition
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            let term_lower = term.to_lowercase().replace(' ', "");
+            let err_msg = format!("Invalid condition: {term}");
+
+            if let Some(value) = term_lower.strip_prefix("depth") {
+                let (op, value) = split_operator(value).ok_or(&err_msg)?;
+                let depth = value.parse::<i32>().map_err(|_| &err_msg)?;
+                if depth < 0 {
+                    return Err(format!("Raise depth must be non-negative: {term}"));
+                }
+                match op {
+                    "=" => (rule.min_depth, rule.max_depth) = (depth, depth),
+                    ">=" => rule.min_depth = depth,
+                    "<=" => rule.max_depth = depth,
+                    _ => return Err(err_msg),
+                }
+            } else if let Some(value) = term_lower.strip_prefix("spr") {
+                let (op, value) = split_operator(value).ok_or(&err_msg)?;
+                let spr = parse_float(value).ok_or(&err_msg)?;
+                match op {
+                    "<" => rule.max_spr = spr,
+                    ">=" => rule.min_spr = spr,
+                    _ => return Err(err_msg),
+                }
+            } else {
+                return Err(err_msg);
+            }
+        }
+
+        if rule.min_depth > rule.max_depth || rule.min_spr >= rule.max_spr {
+            return Err(format!("Condition never matches: {condition}"));
+        }
+
+        let is_raise = rule.min_depth >= 1;
+        for size in sizes.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            rule.sizes.push(bet_size_from_str(size, is_raise)?);
+        }
+
+        rule.sizes
+            .sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
+
+        Ok(rule)
+    }
+
+    /// Returns whether the rule matches the given raise depth and SPR.
+    #[inline]
+    pub fn matches(&self, depth: i32, spr: f64) -> bool {
+        self.min_depth <= depth
+            && depth <= self.max_depth
+            && self.min_spr <= spr
+            && spr < self.max_spr
+    }
+}
+
 impl TryFrom<(&str, &str)> for BetSizeOptions {
-    type Error = String;
+    type Error = Error;
 
     /// Attempts to convert comma-separated strings into bet sizes.
     ///
     /// See the [`BetSizeOptions`] struct for the description and examples.
+    #[inline]
     fn try_from((bet_str, raise_str): (&str, &str)) -> Result<Self, Self::Error> {
+        Self::parse(bet_str, raise_str).map_err(Error::InvalidBetSize)
+    }
+}
+
+impl BetSizeOptions {
+    fn parse(bet_str: &str, raise_str: &str) -> Result<Self, String> {
         let mut bet_sizes = bet_str.split(',').map(str::trim).collect::<Vec<_>>();
         let mut raise_sizes = raise_str.split(',').map(str::trim).collect::<Vec<_>>();
 
@@ -112,17 +318,28 @@ impl TryFrom<(&str, &str)> for BetSizeOptions {
         bet.sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
         raise.sort_unstable_by(|l, r| l.partial_cmp(r).unwrap());
 
-        Ok(BetSizeOptions { bet, raise })
+        Ok(BetSizeOptions {
+            bet,
+            raise,
+            rules: Vec::new(),
+        })
     }
 }
 
 impl TryFrom<&str> for DonkSizeOptions {
-    type Error = String;
+    type Error = Error;
 
     /// Attempts to convert comma-separated strings into bet sizes.
     ///
     /// See the [`BetSizeOptions`] struct for the description and examples.
+    #[inline]
     fn try_from(donk_str: &str) -> Result<Self, Self::Error> {
+        Self::parse(donk_str).map_err(Error::InvalidBetSize)
+    }
+}
+
+impl DonkSizeOptions {
+    fn parse(donk_str: &str) -> Result<Self, String> {
         let mut donk_sizes = donk_str.split(',').map(str::trim).collect::<Vec<_>>();
 
         if donk_sizes.last().unwrap().is_empty() {
@@ -141,6 +358,158 @@ impl TryFrom<&str> for DonkSizeOptions {
     }
 }
 
+impl fmt::Display for BetSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BetSize::PotRelative(ratio) => write!(f, "{}%", format_float(ratio * 100.0)),
+            BetSize::PrevBetRelative(ratio) => write!(f, "{}x", format_float(ratio)),
+            BetSize::Additive(add, 0) => write!(f, "{add}c"),
+            BetSize::Additive(add, cap) => write!(f, "{add}c{cap}r"),
+            BetSize::BigBlindRelative(big_blinds) => write!(f, "{}bb", format_float(big_blinds)),
+            BetSize::Geometric(num_streets, max_ratio) => {
+                if num_streets != 0 {
+                    write!(f, "{num_streets}")?;
+                }
+                write!(f, "e")?;
+                if max_ratio.is_finite() {
+                    write!(f, "{}%", format_float(max_ratio * 100.0))?;
+                }
+                Ok(())
+            }
+            BetSize::AllIn => write!(f, "a"),
+        }
+    }
+}
+
+impl BetSizeRule {
+    /// Returns the condition string of the rule, which is accepted by the [`new`] method.
+    ///
+    /// [`new`]: #method.new
+    pub fn condition(&self) -> String {
+        let mut terms = Vec::new();
+
+        if self.min_depth == self.max_depth {
+            terms.push(format!("depth={}", self.min_depth));
+        } else {
+            if self.min_depth > 0 {
+                terms.push(format!("depth>={}", self.min_depth));
+            }
+            if self.max_depth != i32::MAX {
+                terms.push(format!("depth<={}", self.max_depth));
+            }
+        }
+
+        if self.min_spr > 0.0 {
+            terms.push(format!("spr>={}", format_float(self.min_spr)));
+        }
+        if self.max_spr.is_finite() {
+            terms.push(format!("spr<{}", format_float(self.max_spr)));
+        }
+
+        terms.join(", ")
+    }
+}
+
+/// Serialized form of [`BetSizeOptions`].
+#[derive(Clone, Serialize, Deserialize)]
+struct BetSizeOptionsRepr {
+    bet: String,
+    raise: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<BetSizeRule>,
+}
+
+/// Serialized form of [`BetSizeRule`].
+#[derive(Clone, Serialize, Deserialize)]
+struct BetSizeRuleRepr {
+    condition: String,
+    sizes: String,
+}
+
+/// Serialized form of [`DonkSizeOptions`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+struct DonkSizeOptionsRepr(String);
+
+impl From<BetSizeOptions> for BetSizeOptionsRepr {
+    #[inline]
+    fn from(options: BetSizeOptions) -> Self {
+        Self {
+            bet: sizes_to_string(&options.bet),
+            raise: sizes_to_string(&options.raise),
+            rules: options.rules,
+        }
+    }
+}
+
+impl TryFrom<BetSizeOptionsRepr> for BetSizeOptions {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(repr: BetSizeOptionsRepr) -> Result<Self, Self::Error> {
+        let mut options = Self::try_from((repr.bet.as_str(), repr.raise.as_str()))?;
+        options.rules = repr.rules;
+        Ok(options)
+    }
+}
+
+impl From<BetSizeRule> for BetSizeRuleRepr {
+    #[inline]
+    fn from(rule: BetSizeRule) -> Self {
+        Self {
+            condition: rule.condition(),
+            sizes: sizes_to_string(&rule.sizes),
+        }
+    }
+}
+
+impl TryFrom<BetSizeRuleRepr> for BetSizeRule {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(repr: BetSizeRuleRepr) -> Result<Self, Self::Error> {
+        Self::new(&repr.condition, &repr.sizes)
+    }
+}
+
+impl From<DonkSizeOptions> for DonkSizeOptionsRepr {
+    #[inline]
+    fn from(options: DonkSizeOptions) -> Self {
+        Self(sizes_to_string(&options.donk))
+    }
+}
+
+impl TryFrom<DonkSizeOptionsRepr> for DonkSizeOptions {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(repr: DonkSizeOptionsRepr) -> Result<Self, Self::Error> {
+        Self::try_from(repr.0.as_str())
+    }
+}
+
+#[inline]
+fn sizes_to_string(sizes: &[BetSize]) -> String {
+    sizes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats a float rounded to six decimal places without trailing zeros.
+#[inline]
+fn format_float(value: f64) -> String {
+    let rounded = (value * 1e6).round() / 1e6;
+    format!("{rounded}")
+}
+
+fn split_operator(s: &str) -> Option<(&str, &str)> {
+    [">=", "<=", "=", "<"]
+        .iter()
+        .find_map(|&op| s.strip_prefix(op).map(|value| (op, value)))
+}
+
 fn parse_float(s: &str) -> Option<f64> {
     if s.contains('+') || s.contains('-') || s.contains(|c: char| c.is_ascii_alphabetic()) {
         None
@@ -167,6 +536,15 @@ fn bet_size_from_str(s: &str, is_raise: bool) -> Result<BetSize, String> {
                 Ok(BetSize::PrevBetRelative(float))
             }
         }
+    } else if let Some(big_blinds) = s_lower.strip_suffix("bb") {
+        // Big blind relative
+        let float = parse_float(big_blinds).ok_or(&err_msg)?;
+        if float <= 0.0 {
+            let err_msg = format!("Big blind size must be positive: {s}");
+            Err(err_msg)
+        } else {
+            Ok(BetSize::BigBlindRelative(float))
+        }
     } else if s_lower.contains('c') {
         // Additive
         let mut split = s_lower.split('c');
@@ -254,6 +632,7 @@ fn bet_size_from_str(s: &str, is_raise: bool) -> Result<BetSize, String> {
 mod tests {
     use super::BetSize::*;
     use super::*;
+    use crate::action_tree::*;
 
     #[test]
     fn test_bet_size_from_str() {
@@ -267,6 +646,8 @@ mod tests {
             ("123C", Additive(123, 0)),
             ("0c1r", Additive(0, 1)),
             ("100C100R", Additive(100, 100)),
+            ("2.5bb", BigBlindRelative(2.5)),
+            ("10BB", BigBlindRelative(10.0)),
             ("e", Geometric(0, f64::INFINITY)),
             ("E", Geometric(0, f64::INFINITY)),
             ("2e", Geometric(2, f64::INFINITY)),
@@ -283,7 +664,7 @@ mod tests {
         let error_tests = [
             "", "0", "1.23", "%", "+42%", "-30%", "x", "0x", "1x", "c", "12.3c", "10c10", "42cr",
             "c3r", "0c0r", "123c101r", "1c2r3", "12c3.4r", "0e", "2.7e", "101e", "3e7", "E%",
-            "1e2e3", "bet", "1a", "a1",
+            "1e2e3", "bet", "1a", "a1", "bb", "0bb", "1b", "2.5bbb",
         ];
 
         for s in error_tests {
@@ -300,6 +681,7 @@ mod tests {
                 BetSizeOptions {
                     bet: vec![PotRelative(0.4), PotRelative(0.7)],
                     raise: Vec::new(),
+                    rules: Vec::new(),
                 },
             ),
             (
@@ -308,6 +690,7 @@ mod tests {
                 BetSizeOptions {
                     bet: vec![Additive(50, 0), Geometric(0, f64::INFINITY), AllIn],
                     raise: vec![PotRelative(0.25), PrevBetRelative(2.5), Geometric(0, 2.0)],
+                    rules: Vec::new(),
                 },
             ),
         ];
@@ -323,6 +706,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bet_size_to_string() {
+        let tests = [
+            "0%", "75%", "112.5%", "1.001x", "3.5x", "0c", "123c", "0c1r", "100c100r", "2.5bb",
+            "e", "2e", "e37.5%", "100e0.5%", "a",
+        ];
+
+        for s in tests {
+            let bet_size = bet_size_from_str(s, true).unwrap();
+            assert_eq!(bet_size.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_bet_sizes_serde() {
+        let options = BetSizeOptions::try_from(("33%, 75%, e", "2.5x, 10bb"))
+            .unwrap()
+            .with_rule("depth>=2, spr<1.5", "a")
+            .unwrap()
+            .with_rule("depth=1", "3x")
+            .unwrap();
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BetSizeOptions>(&json).unwrap(),
+            options
+        );
+
+        let rule = &options.rules[0];
+        assert_eq!(rule.condition(), "depth>=2, spr<1.5");
+
+        let donk = DonkSizeOptions::try_from("50%, a").unwrap();
+        let json = serde_json::to_string(&donk).unwrap();
+        assert_eq!(json, r#""50%, a""#);
+        assert_eq!(
+            serde_json::from_str::<DonkSizeOptions>(&json).unwrap(),
+            donk
+        );
+
+        let json = r#"{"bet":"50%","raise":"2.5x"}"#;
+        let options = serde_json::from_str::<BetSizeOptions>(json).unwrap();
+        assert!(options.rules.is_empty());
+        assert!(serde_json::from_str::<BetSizeOptions>(r#"{"bet":"2x","raise":""}"#).is_err());
+    }
+
+    #[test]
+    fn test_tree_config_serde() {
+        let bet_sizes = BetSizeOptions::try_from(("33%, 75%, e", "2.5x, a"))
+            .unwrap()
+            .with_rule("depth>=2", "a")
+            .unwrap();
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 400,
+            stacks: [400, 1000],
+            big_blind: 2,
+            rake_rate: 0.05,
+            rake_cap: 5.0,
+            turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            river_donk_sizes: Some(DonkSizeOptions::try_from("50%").unwrap()),
+            add_allin_threshold: 1.5,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&tree_config).unwrap();
+        assert!(json.contains(r#""initial_state":"turn""#));
+        let deserialized: TreeConfig = serde_json::from_str(&json).unwrap();
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let deserialized = ActionTree::new(deserialized).unwrap();
+        assert_eq!(action_tree.export(None), deserialized.export(None));
+
+        let action = Action::Bet(50);
+        let json = serde_json::to_string(&action).unwrap();
+        assert_eq!(json, r#"{"Bet":50}"#);
+        assert_eq!(serde_json::from_str::<Action>(&json).unwrap(), action);
+    }
+
+    #[test]
+    fn test_bet_size_rules() {
+        let rule = BetSizeRule::new("depth>=1, spr<2.5", "a, 2.5x").unwrap();
+        assert_eq!(
+            rule,
+            BetSizeRule {
+                min_depth: 1,
+                max_depth: i32::MAX,
+                min_spr: 0.0,
+                max_spr: 2.5,
+                sizes: vec![PrevBetRelative(2.5), AllIn],
+            }
+        );
+        assert!(rule.matches(3, 1.0));
+        assert!(!rule.matches(0, 1.0));
+        assert!(!rule.matches(1, 2.5));
+
+        let rule = BetSizeRule::new("", "50%").unwrap();
+        assert!(rule.matches(0, 100.0) && rule.matches(5, 0.0));
+
+        let rule = BetSizeRule::new("Depth = 2, SPR >= 1", "3x").unwrap();
+        assert_eq!((rule.min_depth, rule.max_depth), (2, 2));
+        assert_eq!((rule.min_spr, rule.max_spr), (1.0, f64::INFINITY));
+
+        let error_tests = [
+            ("depth=0", "2.5x"),
+            ("spr<2", "2.5x"),
+            ("depth>1", "a"),
+            ("depth=-1", "a"),
+            ("depth=1.5", "a"),
+            ("spr=2", "a"),
+            ("spr<x", "a"),
+            ("stack<100", "a"),
+            ("depth>=3, depth<=2", "a"),
+            ("spr>=2, spr<1", "a"),
+            ("depth=1", "3"),
+        ];
+
+        for (condition, sizes) in error_tests {
+            assert!(BetSizeRule::new(condition, sizes).is_err());
+        }
+    }
+
     #[test]
     fn test_donk_sizes_from_str() {
         let tests = [
@@ -1,5 +1,6 @@
 use crate::atomic_float::*;
 use crate::card::*;
+use crate::error::*;
 use crate::range::*;
 use crate::utility::*;
 use std::io::{self, Write};
@@ -187,30 +188,34 @@ impl BunchingData {
     ///
     /// `fold_ranges` can contain at most 4 ranges (6-max).
     #[inline]
-    pub fn new(fold_ranges: &[Range], mut flop: [Card; 3]) -> Result<Self, String> {
+    pub fn new(fold_ranges: &[Range], mut flop: [Card; 3]) -> Result<Self, Error> {
         let mut fold_ranges_vec = Vec::new();
 
         for range in fold_ranges {
             if !range.is_empty() {
                 if !range.is_suit_symmetric() {
-                    return Err("Fold ranges must be suit-symmetric".to_string());
+                    return Err(Error::InvalidRange(
+                        "Fold ranges must be suit-symmetric".to_string(),
+                    ));
                 }
                 fold_ranges_vec.push(*range);
             }
         }
 
         if fold_ranges_vec.is_empty() {
-            return Err("Fold ranges is empty".to_string());
+            return Err(Error::InvalidRange("Fold ranges is empty".to_string()));
         }
 
         if fold_ranges_vec.len() > 4 {
-            return Err("The number of folded players must be at most 4".to_string());
+            return Err(Error::InvalidRange(
+                "The number of folded players must be at most 4".to_string(),
+            ));
         }
 
         flop.sort_unstable();
 
         if flop[0] == flop[1] || flop[1] == flop[2] || flop[2] >= 52 {
-            return Err("Invalid flop".to_string());
+            return Err(Error::InvalidCard("Invalid flop".to_string()));
         }
 
         Ok(Self {
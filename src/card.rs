@@ -1,5 +1,6 @@
 use crate::hand::*;
 use crate::range::*;
+use serde::{Deserialize, Serialize};
 use std::mem;
 
 #[cfg(feature = "bincode")]
@@ -30,22 +31,111 @@ pub const NOT_DEALT: Card = Card::MAX;
 ///     flop: flop_from_str("Td9d6h").unwrap(),
 ///     turn: card_from_str("Qc").unwrap(),
 ///     river: NOT_DEALT,
+///     deck: DeckType::Standard,
 /// };
 /// ```
-#[derive(Debug, Clone)]
+///
+/// With serde, the ranges and the cards are represented by strings, and the missing fields are
+/// filled with the default values:
+///
+/// ```
+/// use postflop_solver::*;
+///
+/// let json = r#"{"range": ["QQ+,AKs", "TT+,AQs+"], "flop": "Td9d6h", "turn": "Qc"}"#;
+/// let card_config: CardConfig = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(card_config.flop, flop_from_str("Td9d6h").unwrap());
+/// assert_eq!(card_config.turn, card_from_str("Qc").unwrap());
+/// assert_eq!(card_config.river, NOT_DEALT);
+/// assert_eq!(card_config.deck, DeckType::Standard);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub struct CardConfig {
     /// Initial range of each player.
     pub range: [Range; 2],
 
     /// Flop cards: each card must be unique.
+    #[serde(with = "serde_flop")]
     pub flop: [Card; 3],
 
     /// Turn card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    #[serde(with = "serde_card", skip_serializing_if = "is_not_dealt")]
     pub turn: Card,
 
     /// River card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    #[serde(with = "serde_card", skip_serializing_if = "is_not_dealt")]
     pub river: Card,
+
+    /// Deck used in the game. The board cards must be contained in the deck, and the hands
+    /// containing removed cards are excluded from the ranges.
+    pub deck: DeckType,
+}
+
+/// An enum representing the deck used in the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+#[cfg_attr(feature = "bincode", derive(Decode, Encode))]
+pub enum DeckType {
+    /// Standard 52-card deck.
+    #[default]
+    Standard,
+
+    /// 36-card deck of short-deck (6+) hold'em, where the deuces through fives are removed.
+    ///
+    /// A-6-7-8-9 is the lowest straight, and a flush beats a full house.
+    ShortDeck,
+}
+
+impl DeckType {
+    /// Returns the number of cards in the deck.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::DeckType;
+    ///
+    /// assert_eq!(DeckType::Standard.num_cards(), 52);
+    /// assert_eq!(DeckType::ShortDeck.num_cards(), 36);
+    /// ```
+    #[inline]
+    pub fn num_cards(self) -> usize {
+        52 - self.removed_cards_mask().count_ones() as usize
+    }
+
+    /// Returns whether the deck contains the given card.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::*;
+    ///
+    /// let card = card_from_str("5s").unwrap();
+    /// assert!(DeckType::Standard.contains(card));
+    /// assert!(!DeckType::ShortDeck.contains(card));
+    /// ```
+    #[inline]
+    pub fn contains(self, card: Card) -> bool {
+        card < 52 && (1 << card) & self.removed_cards_mask() == 0
+    }
+
+    /// Returns the bit mask of the cards removed from the standard deck.
+    #[inline]
+    pub(crate) fn removed_cards_mask(self) -> u64 {
+        match self {
+            DeckType::Standard => 0,
+            DeckType::ShortDeck => (1 << 16) - 1, // 2c-5s
+        }
+    }
+
+    /// Evaluates the given seven-card hand under the hand rankings of the deck.
+    #[inline]
+    pub(crate) fn evaluate(self, hand: &Hand) -> u16 {
+        match self {
+            DeckType::Standard => hand.evaluate(),
+            DeckType::ShortDeck => hand.evaluate_short_deck(),
+        }
+    }
 }
 
 impl Default for CardConfig {
@@ -56,6 +146,63 @@ impl Default for CardConfig {
             flop: [NOT_DEALT; 3],
             turn: NOT_DEALT,
             river: NOT_DEALT,
+            deck: DeckType::Standard,
+        }
+    }
+}
+
+#[inline]
+fn is_not_dealt(card: &Card) -> bool {
+    *card == NOT_DEALT
+}
+
+/// Serializes a card as a string (e.g., `"Qc"`), where `NOT_DEALT` is an empty string.
+mod serde_card {
+    use super::*;
+    use serde::{de, ser, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(card: &Card, serializer: S) -> Result<S::Ok, S::Error> {
+        if *card == NOT_DEALT {
+            serializer.serialize_str("")
+        } else {
+            let s = card_to_string(*card).map_err(ser::Error::custom)?;
+            serializer.serialize_str(&s)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Card, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            Ok(NOT_DEALT)
+        } else {
+            card_from_str(&s).map_err(de::Error::custom)
+        }
+    }
+}
+
+/// Serializes flop cards as a string (e.g., `"Td9d6h"`), where undealt flop is an empty string.
+mod serde_flop {
+    use super::*;
+    use serde::{de, ser, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(flop: &[Card; 3], serializer: S) -> Result<S::Ok, S::Error> {
+        if flop.contains(&NOT_DEALT) {
+            serializer.serialize_str("")
+        } else {
+            let mut s = String::new();
+            for &card in flop {
+                s += &card_to_string(card).map_err(ser::Error::custom)?;
+            }
+            serializer.serialize_str(&s)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[Card; 3], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            Ok([NOT_DEALT; 3])
+        } else {
+            flop_from_str(&s).map_err(de::Error::custom)
         }
     }
 }
@@ -81,6 +228,9 @@ type IsomorphismData = (
     [[SwapList; 4]; 4],
 );
 
+/// The number of card pairs, i.e., the number of indices returned by [`card_pair_to_index`].
+pub(crate) const NUM_CARD_PAIRS: usize = 52 * 51 / 2;
+
 /// Returns an index of the given card pair.
 ///
 /// Examples: 2d2c => `0`, 2h2c => `1`, 2s2c => `2`, ..., AsAh => `1325`
@@ -118,7 +268,8 @@ impl CardConfig {
 
         let mut ret_turn = vec![Indices::default(); 52];
         for board in 0..52 {
-            if !self.flop.contains(&board)
+            if self.deck.contains(board)
+                && !self.flop.contains(&board)
                 && (self.turn == NOT_DEALT || self.turn == board)
                 && self.river == NOT_DEALT
             {
@@ -130,7 +281,9 @@ impl CardConfig {
         let mut ret_river = vec![Indices::default(); 52 * 51 / 2];
         for board1 in 0..52 {
             for board2 in board1 + 1..52 {
-                if !self.flop.contains(&board1)
+                if self.deck.contains(board1)
+                    && self.deck.contains(board2)
+                    && !self.flop.contains(&board1)
                     && !self.flop.contains(&board2)
                     && (self.turn == NOT_DEALT || board1 == self.turn || board2 == self.turn)
                     && (self.river == NOT_DEALT || board1 == self.river || board2 == self.river)
@@ -193,7 +346,9 @@ impl CardConfig {
 
         for board1 in 0..52 {
             for board2 in board1 + 1..52 {
-                if !board.contains(board1 as usize)
+                if self.deck.contains(board1)
+                    && self.deck.contains(board2)
+                    && !board.contains(board1 as usize)
                     && !board.contains(board2 as usize)
                     && (self.turn == NOT_DEALT || board1 == self.turn || board2 == self.turn)
                     && (self.river == NOT_DEALT || board1 == self.river || board2 == self.river)
@@ -224,7 +379,7 @@ impl CardConfig {
                                     } else {
                                         let hand = board.add_card(c1).add_card(c2);
                                         Some(StrengthItem {
-                                            strength: hand.evaluate() + 1, // +1 to avoid 0
+                                            strength: self.deck.evaluate(&hand) + 1, // +1 to avoid 0
                                             index: index as u16,
                                         })
                                     }
@@ -260,6 +415,7 @@ impl CardConfig {
             next_index += 1;
         }
 
+        let removed_mask = self.deck.removed_cards_mask();
         let flop_mask: u64 = (1 << self.flop[0]) | (1 << self.flop[1]) | (1 << self.flop[2]);
         let mut flop_rankset = [0; 4];
 
@@ -299,7 +455,7 @@ impl CardConfig {
             Self::isomorphism_internal(
                 &mut isomorphism_ref_turn,
                 &mut isomorphism_card_turn,
-                flop_mask,
+                flop_mask | removed_mask,
                 &isomorphic_suit,
             );
         }
@@ -311,7 +467,9 @@ impl CardConfig {
         // river isomorphism
         if self.river == NOT_DEALT {
             for turn in 0..52 {
-                if (1 << turn) & flop_mask != 0 || (self.turn != NOT_DEALT && self.turn != turn) {
+                if (1 << turn) & (flop_mask | removed_mask) != 0
+                    || (self.turn != NOT_DEALT && self.turn != turn)
+                {
                     continue;
                 }
 
@@ -344,7 +502,7 @@ impl CardConfig {
                 Self::isomorphism_internal(
                     &mut isomorphism_ref_river[turn as usize],
                     &mut isomorphism_card_river[turn as usize & 3],
-                    turn_mask,
+                    turn_mask | removed_mask,
                     &isomorphic_suit,
                 );
             }
@@ -0,0 +1,522 @@
+use crate::card::*;
+use crate::error::*;
+use crate::hand::*;
+use crate::range::*;
+use crate::utility::*;
+use std::cmp::Ordering;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Result of [`compute_equity`].
+#[derive(Debug, Clone, Default)]
+pub struct EquityResult {
+    /// The equity of each range.
+    pub equity: Vec<f64>,
+
+    /// The equity of each combo of each range, in the same order as [`Range::raw_data`].
+    ///
+    /// The combos that are not in the range, conflict with the board or the dead cards, or have no
+    /// possible opponent hands are `NaN`.
+    pub combo_equity: Vec<Vec<f64>>,
+}
+
+/// A combo in a range: `(index, mask, weight)`.
+type Combo = (usize, u64, f64);
+
+/// A combo on a runout: `(index, card1, card2, mask, strength, weight)`.
+type Showdown = (usize, Card, Card, u64, u16, f64);
+
+/// Computes the exact equities of two or three ranges by enumerating all the runouts.
+///
+/// `board` may contain zero to five cards, and `dead_cards` are removed from the deck and the
+/// ranges. The pot is split evenly on ties. The combos of the ranges are weighted by their weights
+/// and the card removal between the ranges is taken into account.
+///
+/// The computation time is proportional to the number of runouts; with three ranges, it is also
+/// proportional to the product of the sizes of two ranges per runout, so preflop equities of wide
+/// ranges take a long time.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let ranges = ["AA".parse().unwrap(), "KK".parse().unwrap()];
+/// let board = flop_from_str("Td9d6h").unwrap();
+/// let result = compute_equity(&ranges, &board, &[]).unwrap();
+/// assert!(result.equity[0] > 0.8);
+/// assert!((result.equity[0] + result.equity[1] - 1.0).abs() < 1e-9);
+///
+/// // per-combo equity
+/// let aces = result.combo_equity[0].iter().filter(|e| !e.is_nan());
+/// assert_eq!(aces.count(), 6);
+///
+/// // the kings are dead
+/// let dead_cards = [card_from_str("Ks").unwrap(), card_from_str("Kh").unwrap()];
+/// let result = compute_equity(&ranges, &board, &dead_cards).unwrap();
+/// assert!(result.combo_equity[1].iter().filter(|e| !e.is_nan()).count() == 1);
+/// ```
+pub fn compute_equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead_cards: &[Card],
+) -> Result<EquityResult, Error> {
+    if !(2..=3).contains(&ranges.len()) {
+        return Err(Error::InvalidRange(format!(
+            "Number of ranges must be 2 or 3: {}",
+            ranges.len()
+        )));
+    }
+
+    if board.len() > 5 {
+        return Err(Error::InvalidCard(format!(
+            "Board must have at most 5 cards: {}",
+            board.len()
+        )));
+    }
+
+    let mut board_mask: u64 = 0;
+    let mut dead_mask: u64 = 0;
+    for (i, &card) in board.iter().chain(dead_cards).enumerate() {
+        if card >= 52 {
+            return Err(Error::InvalidCard(format!(
+                "Card must be in [0, 52): {card}"
+            )));
+        }
+        if (board_mask | dead_mask) & (1 << card) != 0 {
+            return Err(Error::InvalidCard(format!("Duplicate cards: {card}")));
+        }
+        if i < board.len() {
+            board_mask |= 1 << card;
+        } else {
+            dead_mask |= 1 << card;
+        }
+    }
+
+    let used_mask = board_mask | dead_mask;
+    let num_players = ranges.len();
+
+    let combos = ranges
+        .iter()
+        .enumerate()
+        .map(|(player, range)| {
+            let combos = (0..52 * 51 / 2)
+                .filter_map(|index| {
+                    let (c1, c2) = index_to_card_pair(index);
+                    let mask: u64 = (1 << c1) | (1 << c2);
+                    let weight = range.raw_data()[index];
+                    (weight > 0.0 && mask & used_mask == 0).then_some((index, mask, weight as f64))
+                })
+                .collect::<Vec<Combo>>();
+            if combos.is_empty() {
+                Err(Error::InvalidRange(format!(
+                    "Range of player {player} is empty after removing the cards"
+                )))
+            } else {
+                Ok(combos)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the runouts are grouped by the first dealt card for parallelization
+    let deck = (0..52)
+        .filter(|&c| used_mask & (1 << c) == 0)
+        .collect::<Vec<Card>>();
+    let num_deal = 5 - board.len();
+    let num_groups = if num_deal == 0 { 1 } else { deck.len() };
+
+    let sums = into_par_iter(0..num_groups)
+        .map(|group| {
+            let mut evaluator = RunoutEvaluator::new(&combos);
+            if num_deal == 0 {
+                evaluator.evaluate(board_mask);
+            } else {
+                let mask = board_mask | (1 << deck[group]);
+                for_each_runout(&deck[group + 1..], num_deal - 1, mask, &mut |mask| {
+                    evaluator.evaluate(mask)
+                });
+            }
+            evaluator.sums
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = EquityResult {
+        equity: vec![0.0; num_players],
+        combo_equity: vec![vec![f64::NAN; 52 * 51 / 2]; num_players],
+    };
+
+    for player in 0..num_players {
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+
+        for &(index, _, weight) in &combos[player] {
+            let (win, total) = sums.iter().fold((0.0, 0.0), |acc, s| {
+                let (win, total) = s[player][index];
+                (acc.0 + win, acc.1 + total)
+            });
+            if total > 0.0 {
+                result.combo_equity[player][index] = win / total;
+                numerator += weight * win;
+                denominator += weight * total;
+            }
+        }
+
+        if denominator == 0.0 {
+            return Err(Error::InvalidRange(
+                "No valid combination of hands".to_string(),
+            ));
+        }
+
+        result.equity[player] = numerator / denominator;
+    }
+
+    Ok(result)
+}
+
+/// Calls `f` with the mask of each runout that deals `n` more cards from `deck`.
+fn for_each_runout(deck: &[Card], n: usize, mask: u64, f: &mut impl FnMut(u64)) {
+    if n == 0 {
+        f(mask);
+        return;
+    }
+    for i in 0..deck.len() {
+        for_each_runout(&deck[i + 1..], n - 1, mask | (1 << deck[i]), f);
+    }
+}
+
+/// Accumulates the showdown results of the runouts.
+struct RunoutEvaluator<'a> {
+    combos: &'a [Vec<Combo>],
+
+    /// `(win, total)` of each combo of each player, where `win` is the sum of the weighted pot
+    /// shares and `total` is the sum of the weights of the opponent hands.
+    sums: Vec<Vec<(f64, f64)>>,
+
+    /// The combos that do not conflict with the runout, sorted by strength.
+    hands: Vec<Vec<Showdown>>,
+
+    counters: Vec<Counter>,
+}
+
+impl<'a> RunoutEvaluator<'a> {
+    fn new(combos: &'a [Vec<Combo>]) -> Self {
+        Self {
+            combos,
+            sums: vec![vec![(0.0, 0.0); 52 * 51 / 2]; combos.len()],
+            hands: vec![Vec::new(); combos.len()],
+            counters: (0..combos.len()).map(|_| Counter::default()).collect(),
+        }
+    }
+
+    fn evaluate(&mut self, board_mask: u64) {
+        let board_hand = (0..52)
+            .filter(|&c| board_mask & (1 << c) != 0)
+            .fold(Hand::new(), |hand, c| hand.add_card(c));
+
+        for (player, combos) in self.combos.iter().enumerate() {
+            let hands = &mut self.hands[player];
+            hands.clear();
+            for &(index, mask, weight) in combos {
+                if mask & board_mask == 0 {
+                    let (c1, c2) = index_to_card_pair(index);
+                    let hand = board_hand.add_card(c1 as usize).add_card(c2 as usize);
+                    hands.push((index, c1, c2, mask, hand.evaluate(), weight));
+                }
+            }
+            hands.sort_unstable_by_key(|h| h.4);
+            self.counters[player].update(hands);
+        }
+
+        if self.combos.len() == 2 {
+            self.evaluate_two_way();
+        } else {
+            self.evaluate_three_way();
+        }
+    }
+
+    fn evaluate_two_way(&mut self) {
+        for player in 0..2 {
+            let counter = &self.counters[player ^ 1];
+            for &(index, c1, c2, _, strength, _) in &self.hands[player] {
+                let (lt, eq, total) = counter.count(strength, &[c1, c2]);
+                let sum = &mut self.sums[player][index];
+                sum.0 += lt + 0.5 * eq;
+                sum.1 += total;
+            }
+        }
+    }
+
+    fn evaluate_three_way(&mut self) {
+        for player in 0..3 {
+            let other = (player + 1) % 3;
+            let counter = &self.counters[(player + 2) % 3];
+            for &(index, c1, c2, mask, strength, _) in &self.hands[player] {
+                let (mut win, mut total) = (0.0, 0.0);
+                for &(_, d1, d2, other_mask, other_strength, weight) in &self.hands[other] {
+                    if mask & other_mask != 0 {
+                        continue;
+                    }
+                    let max_strength = strength.max(other_strength);
+                    let (lt, eq, t) = counter.count(max_strength, &[c1, c2, d1, d2]);
+                    let share = match strength.cmp(&other_strength) {
+                        Ordering::Greater => lt + eq / 2.0,
+                        Ordering::Equal => lt / 2.0 + eq / 3.0,
+                        Ordering::Less => 0.0,
+                    };
+                    win += weight * share;
+                    total += weight * t;
+                }
+                let sum = &mut self.sums[player][index];
+                sum.0 += win;
+                sum.1 += total;
+            }
+        }
+    }
+}
+
+/// Counts the weights of the hands weaker than, equal to, and stronger than a given strength,
+/// excluding the hands that contain the blocked cards.
+#[derive(Default)]
+struct Counter {
+    strengths: Vec<u16>,
+    cum_weights: Vec<f64>,
+    card_strengths: Vec<Vec<u16>>,
+    card_cum_weights: Vec<Vec<f64>>,
+    pairs: Vec<(u16, f64)>,
+    indices: Vec<usize>,
+}
+
+impl Counter {
+    /// Rebuilds the counter from the hands sorted by strength.
+    fn update(&mut self, hands: &[Showdown]) {
+        if self.pairs.is_empty() {
+            self.card_strengths = vec![Vec::new(); 52];
+            self.card_cum_weights = vec![Vec::new(); 52];
+            self.pairs = vec![(0, 0.0); 52 * 51 / 2];
+        }
+
+        for &index in &self.indices {
+            self.pairs[index] = (0, 0.0);
+        }
+
+        self.strengths.clear();
+        self.cum_weights.clear();
+        self.cum_weights.push(0.0);
+        self.indices.clear();
+        for card in 0..52 {
+            self.card_strengths[card].clear();
+            self.card_cum_weights[card].clear();
+            self.card_cum_weights[card].push(0.0);
+        }
+
+        for &(index, c1, c2, _, strength, weight) in hands {
+            self.strengths.push(strength);
+            self.cum_weights
+                .push(self.cum_weights.last().unwrap() + weight);
+            for card in [c1 as usize, c2 as usize] {
+                let cum = self.card_cum_weights[card].last().unwrap() + weight;
+                self.card_strengths[card].push(strength);
+                self.card_cum_weights[card].push(cum);
+            }
+            self.pairs[index] = (strength, weight);
+            self.indices.push(index);
+        }
+    }
+
+    /// Returns the weights of the hands weaker than and equal to `strength`, and the total weight,
+    /// excluding the hands that contain `blocked` cards (distinct).
+    fn count(&self, strength: u16, blocked: &[Card]) -> (f64, f64, f64) {
+        let (mut lt, mut le, mut total) = prefix_sum(&self.strengths, &self.cum_weights, strength);
+
+        for (i, &card) in blocked.iter().enumerate() {
+            let card_strengths = &self.card_strengths[card as usize];
+            let card_cum_weights = &self.card_cum_weights[card as usize];
+            let (a, b, c) = prefix_sum(card_strengths, card_cum_weights, strength);
+            lt -= a;
+            le -= b;
+            total -= c;
+
+            // the hands that contain two blocked cards are subtracted twice
+            for &other in &blocked[i + 1..] {
+                let (s, weight) = self.pairs[card_pair_to_index(card, other)];
+                if weight > 0.0 {
+                    total += weight;
+                    if s < strength {
+                        lt += weight;
+                    }
+                    if s <= strength {
+                        le += weight;
+                    }
+                }
+            }
+        }
+
+        (lt, le - lt, total)
+    }
+}
+
+#[inline]
+fn prefix_sum(strengths: &[u16], cum_weights: &[f64], strength: u16) -> (f64, f64, f64) {
+    let lt = strengths.partition_point(|&s| s < strength);
+    let le = lt + strengths[lt..].partition_point(|&s| s <= strength);
+    (
+        cum_weights[lt],
+        cum_weights[le],
+        cum_weights[strengths.len()],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Computes the equities by enumerating all the combinations of hands and runouts.
+    fn naive_equity(ranges: &[Range], board: &[Card]) -> Vec<f64> {
+        let board_mask: u64 = board.iter().map(|&c| 1 << c).sum();
+        let deck = (0..52)
+            .filter(|&c| board_mask & (1 << c) == 0)
+            .collect::<Vec<Card>>();
+        let mut runouts = Vec::new();
+        for_each_runout(&deck, 5 - board.len(), board_mask, &mut |m| runouts.push(m));
+
+        let combos = ranges
+            .iter()
+            .map(|range| {
+                (0..52 * 51 / 2)
+                    .filter(|&i| range.raw_data()[i] > 0.0)
+                    .map(|i| (index_to_card_pair(i), range.raw_data()[i] as f64))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut deals = vec![vec![]];
+        for player_combos in &combos {
+            let mut next = Vec::new();
+            for deal in &deals {
+                for &combo in player_combos {
+                    let mut deal = deal.clone();
+                    deal.push(combo);
+                    next.push(deal);
+                }
+            }
+            deals = next;
+        }
+
+        let mut win = vec![0.0; ranges.len()];
+        let mut total = 0.0;
+        for deal in deals {
+            let mask = deal.iter().fold(0u64, |m, &((c1, c2), _)| {
+                if m == u64::MAX || m & ((1 << c1) | (1 << c2)) != 0 {
+                    u64::MAX
+                } else {
+                    m | (1 << c1) | (1 << c2)
+                }
+            });
+            if mask == u64::MAX || mask & board_mask != 0 {
+                continue;
+            }
+            let weight = deal.iter().map(|&(_, w)| w).product::<f64>();
+            for &runout in &runouts {
+                if runout & mask != 0 {
+                    continue;
+                }
+                let board_hand = (0..52)
+                    .filter(|&c| runout & (1 << c) != 0)
+                    .fold(Hand::new(), |hand, c| hand.add_card(c));
+                let strengths = deal
+                    .iter()
+                    .map(|&((c1, c2), _)| {
+                        let hand = board_hand.add_card(c1 as usize).add_card(c2 as usize);
+                        hand.evaluate()
+                    })
+                    .collect::<Vec<_>>();
+                let max = *strengths.iter().max().unwrap();
+                let num_winners = strengths.iter().filter(|&&s| s == max).count();
+                for (w, &s) in win.iter_mut().zip(&strengths) {
+                    if s == max {
+                        *w += weight / num_winners as f64;
+                    }
+                }
+                total += weight;
+            }
+        }
+
+        win.iter().map(|w| w / total).collect()
+    }
+
+    #[test]
+    fn equity_two_way() {
+        let ranges = [
+            "QQ+,AKs,T9s:0.5".parse().unwrap(),
+            "JJ,AQ,KQs:0.25,65s".parse().unwrap(),
+        ];
+        let board = [
+            card_from_str("Qd").unwrap(),
+            card_from_str("7s").unwrap(),
+            card_from_str("4d").unwrap(),
+            card_from_str("Ac").unwrap(),
+        ];
+
+        let result = compute_equity(&ranges, &board, &[]).unwrap();
+        let expected = naive_equity(&ranges, &board);
+        for (x, y) in result.equity.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9);
+        }
+
+        let result = compute_equity(&ranges, &board[..3], &[]).unwrap();
+        let expected = naive_equity(&ranges, &board[..3]);
+        for (x, y) in result.equity.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9);
+        }
+
+        // per-combo equity
+        let index = card_pair_to_index(card_from_str("Qd").unwrap(), card_from_str("Qs").unwrap());
+        assert!(result.combo_equity[0][index].is_nan());
+        let aces = "AsAh".parse::<Range>().unwrap();
+        let single = compute_equity(&[aces, ranges[1]], &board[..3], &[]).unwrap();
+        let index = card_pair_to_index(card_from_str("Ah").unwrap(), card_from_str("As").unwrap());
+        assert!((result.combo_equity[0][index] - single.equity[0]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn equity_three_way() {
+        let ranges = [
+            "KK+,AQs".parse().unwrap(),
+            "QQ,AK:0.5,KQs".parse().unwrap(),
+            "JJ-TT,98s,AJs:0.75".parse().unwrap(),
+        ];
+        let board = [
+            card_from_str("Kd").unwrap(),
+            card_from_str("Ts").unwrap(),
+            card_from_str("9s").unwrap(),
+            card_from_str("2c").unwrap(),
+        ];
+
+        let result = compute_equity(&ranges, &board, &[]).unwrap();
+        let expected = naive_equity(&ranges, &board);
+        for (x, y) in result.equity.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn equity_river_and_errors() {
+        let ranges = ["AA".parse().unwrap(), "KK".parse().unwrap()];
+        let board = (0..5)
+            .map(|i| card_from_str(["2c", "3d", "7h", "8s", "Jc"][i]).unwrap())
+            .collect::<Vec<_>>();
+        let result = compute_equity(&ranges, &board, &[]).unwrap();
+        assert_eq!(result.equity, vec![1.0, 0.0]);
+
+        let aces = ranges[0];
+        assert!(compute_equity(&[aces], &board, &[]).is_err());
+        assert!(compute_equity(&[aces, aces, aces, aces], &board, &[]).is_err());
+        assert!(compute_equity(&ranges, &[0, 0, 1], &[]).is_err());
+        assert!(compute_equity(&ranges, &[0, 1, 2], &[2]).is_err());
+        assert!(compute_equity(&ranges, &[0, 1, 52], &[]).is_err());
+
+        // no valid combination
+        assert!(compute_equity(&[aces, aces], &[], &[48, 49]).is_err());
+        assert!(compute_equity(&[aces, ranges[1]], &[], &[48, 49, 50]).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+use std::io;
+use std::mem;
+
+#[cfg(feature = "bincode")]
+use bincode::error::{DecodeError, EncodeError};
+
+/// The error type of this crate.
+///
+/// Each variant except the wrapped source errors carries a human-readable message, so
+/// `to_string()` gives the same message as before. Match on the variant to distinguish the kinds of
+/// failures without parsing the message.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let err = "AKQ".parse::<Range>().unwrap_err();
+/// assert!(matches!(err, Error::InvalidRange(_)));
+///
+/// let err = card_from_str("1s").unwrap_err();
+/// assert!(matches!(err, Error::InvalidCard(_)));
+///
+/// let err = BetSizeOptions::try_from(("50", "")).unwrap_err();
+/// assert!(matches!(err, Error::InvalidBetSize(_)));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A card string or a card ID is invalid, or the board cards conflict with each other.
+    InvalidCard(String),
+
+    /// A range string or range data is invalid, or the ranges are empty or conflict with the board.
+    InvalidRange(String),
+
+    /// A bet size string or a bet size rule is invalid.
+    InvalidBetSize(String),
+
+    /// A field of the tree configuration or another game setting is invalid.
+    InvalidConfig(String),
+
+    /// An action or an action line does not exist or is not allowed at the node.
+    InvalidAction(String),
+
+    /// An imported tree description does not match the tree built from the configuration.
+    InvalidTreeData(String),
+
+    /// The method is called in a state that does not allow it (e.g., before allocating memory).
+    InvalidState(String),
+
+    /// The game tree or the estimated memory usage exceeds the limit.
+    TreeTooLarge(String),
+
+    /// A node strategy cannot be locked.
+    LockedNode(String),
+
+    /// The version of the file or the game data does not match the current format.
+    VersionMismatch { found: String, expected: String },
+
+    /// The file is not a valid data file (e.g., the magic number or the data type is wrong).
+    InvalidFile(String),
+
+    /// An I/O error.
+    Io(io::Error),
+
+    /// An error while decoding the data.
+    #[cfg(feature = "bincode")]
+    Decode(DecodeError),
+
+    /// An error while encoding the data.
+    #[cfg(feature = "bincode")]
+    Encode(EncodeError),
+
+    /// An error while serializing or deserializing JSON.
+    Json(serde_json::Error),
+
+    /// Any other error.
+    Other(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidCard(msg)
+            | Error::InvalidRange(msg)
+            | Error::InvalidBetSize(msg)
+            | Error::InvalidConfig(msg)
+            | Error::InvalidAction(msg)
+            | Error::InvalidTreeData(msg)
+            | Error::InvalidState(msg)
+            | Error::TreeTooLarge(msg)
+            | Error::LockedNode(msg)
+            | Error::InvalidFile(msg)
+            | Error::Other(msg) => f.write_str(msg),
+            Error::VersionMismatch { found, expected } => {
+                write!(f, "Version is invalid: found {found}, expected {expected}")
+            }
+            Error::Io(e) => write!(f, "I/O error: {e}"),
+            #[cfg(feature = "bincode")]
+            Error::Decode(e) => write!(f, "Failed to decode data: {e}"),
+            #[cfg(feature = "bincode")]
+            Error::Encode(e) => write!(f, "Failed to encode data: {e}"),
+            Error::Json(e) => write!(f, "JSON error: {e}"),
+        }
+    }
+}
+
+/// Two errors are equal if they are of the same kind and have the same message (the wrapped source
+/// errors are compared by their messages).
+impl PartialEq for Error {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        mem::discriminant(self) == mem::discriminant(other) && self.to_string() == other.to_string()
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            #[cfg(feature = "bincode")]
+            Error::Decode(e) => Some(e),
+            #[cfg(feature = "bincode")]
+            Error::Encode(e) => Some(e),
+            Error::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    #[inline]
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl From<DecodeError> for Error {
+    #[inline]
+    fn from(e: DecodeError) -> Self {
+        Error::Decode(e)
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl From<EncodeError> for Error {
+    #[inline]
+    fn from(e: EncodeError) -> Self {
+        Error::Encode(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    #[inline]
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+/// Allows the functions returning `Result<_, String>` to propagate [`Error`] with `?`.
+impl From<Error> for String {
+    #[inline]
+    fn from(e: Error) -> Self {
+        e.to_string()
+    }
+}
@@ -1,15 +1,16 @@
 // [File format]
 // The file consists of a header and a body. The header is as follows:
 //  - Magic number (4 bytes): 90 57 f1 09
-//  - Version number (1 byte): 1
+//  - Version number (1 byte): 2 (game), 1 (bunching), 1 (checkpoint)
 //  - Compression type (1 byte): 0 (none), 1 (zstd)
-//  - Data type (1 byte): 0 (game), 1 (bunching)
+//  - Data type (1 byte): 0 (game), 1 (bunching), 2 (checkpoint)
 //  - Estimated memory usage (`VarIntEncoding`)
 //  - Memo string
 //
 // `VarIntEncoding`: https://github.com/bincode-org/bincode/blob/trunk/docs/spec.md#varintencoding
 
 use crate::bunching::*;
+use crate::error::*;
 use crate::game::*;
 use crate::interface::*;
 use bincode::{Decode, Encode};
@@ -18,12 +19,25 @@ use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
 const MAGIC: u32 = 0x09f15790;
-const VERSION: u8 = 1;
 
 #[doc(hidden)]
+#[derive(Clone, Copy)]
 pub enum DataType {
     Game = 0,
     Bunching = 1,
+    Checkpoint = 2,
+}
+
+impl DataType {
+    /// Returns the version number of the file format of the data type.
+    #[inline]
+    fn version(self) -> u8 {
+        match self {
+            DataType::Game => 2,
+            DataType::Bunching => 1,
+            DataType::Checkpoint => 1,
+        }
+    }
 }
 
 /// A trait for data that can be saved into a file.
@@ -31,18 +45,17 @@ pub trait FileData: Decode<()> + Encode<> {
     #[doc(hidden)]
     fn data_type() -> DataType;
     #[doc(hidden)]
-    fn is_ready_to_save(&self) -> bool;
+    fn check_ready_to_save(&self) -> Result<(), Error>;
     #[doc(hidden)]
     fn estimated_memory_usage(&self) -> u64;
 }
 
-fn encode_into_std_write<E: Encode, W: Write>(
-    val: E,
-    writer: &mut W,
-    err_msg: &str,
-) -> Result<usize, String> {
-    bincode::encode_into_std_write(val, writer, bincode::config::standard())
-        .map_err(|e| format!("{}: {}", err_msg, e))
+fn encode_into_std_write<E: Encode, W: Write>(val: E, writer: &mut W) -> Result<usize, Error> {
+    Ok(bincode::encode_into_std_write(
+        val,
+        writer,
+        bincode::config::standard(),
+    )?)
 }
 
 /// Saves data into a standard writer.
@@ -63,54 +76,59 @@ pub fn save_data_into_std_write<T: FileData, W: Write>(
     memo: &str,
     writer: &mut W,
     compression_level: Option<i32>,
-) -> Result<(), String> {
-    if !data.is_ready_to_save() {
-        return Err("Data is not ready to save".to_string());
-    }
+) -> Result<(), Error> {
+    data.check_ready_to_save()?;
+
+    save_into_std_write_internal(
+        data,
+        T::data_type(),
+        data.estimated_memory_usage(),
+        memo,
+        writer,
+        compression_level,
+    )
+}
 
+fn save_into_std_write_internal<E: Encode, W: Write>(
+    data: &E,
+    data_type: DataType,
+    estimated_memory_usage: u64,
+    memo: &str,
+    writer: &mut W,
+    compression_level: Option<i32>,
+) -> Result<(), Error> {
     #[cfg(not(feature = "zstd"))]
     if compression_level.is_some() {
-        return Err("Compression is not supported".to_string());
+        return Err(Error::InvalidConfig(
+            "Compression is not supported".to_string(),
+        ));
     }
 
-    encode_into_std_write(MAGIC, writer, "Failed to write magic number")?;
-    encode_into_std_write(VERSION, writer, "Failed to write version number")?;
+    encode_into_std_write(MAGIC, writer)?;
+    encode_into_std_write(data_type.version(), writer)?;
 
     let compression_type = compression_level.is_some() as u8;
-    encode_into_std_write(compression_type, writer, "Failed to write compression type")?;
+    encode_into_std_write(compression_type, writer)?;
 
-    encode_into_std_write(T::data_type() as u8, writer, "Failed to write data type")?;
-    encode_into_std_write(
-        data.estimated_memory_usage(),
-        writer,
-        "Failed to write memory usage",
-    )?;
+    encode_into_std_write(data_type as u8, writer)?;
+    encode_into_std_write(estimated_memory_usage, writer)?;
 
-    encode_into_std_write(memo, writer, "Failed to write memo")?;
+    encode_into_std_write(memo, writer)?;
 
     if compression_level.is_none() {
-        encode_into_std_write(data, writer, "Failed to write data")?;
-        writer
-            .flush()
-            .map_err(|e| format!("Failed to flush writer: {}", e))?;
+        encode_into_std_write(data, writer)?;
+        writer.flush()?;
     }
 
     #[cfg(feature = "zstd")]
     if let Some(compression_level) = compression_level {
-        let mut zstd_encoder = zstd::stream::Encoder::new(writer, compression_level)
-            .map_err(|e| format!("Failed to create zstd encoder: {}", e))?;
+        let mut zstd_encoder = zstd::stream::Encoder::new(writer, compression_level)?;
 
         #[cfg(feature = "rayon")]
-        zstd_encoder
-            .multithread(rayon::current_num_threads() as u32)
-            .map_err(|e| format!("Failed to enable multithreaded zstd encoder: {}", e))?;
-
-        encode_into_std_write(data, &mut zstd_encoder, "Failed to write data")?;
-        zstd_encoder
-            .finish()
-            .map_err(|e| format!("Failed to finish zstd encoder: {}", e))?
-            .flush()
-            .map_err(|e| format!("Failed to flush writer: {}", e))?;
+        zstd_encoder.multithread(rayon::current_num_threads() as u32)?;
+
+        encode_into_std_write(data, &mut zstd_encoder)?;
+        zstd_encoder.finish()?.flush()?;
     }
 
     Ok(())
@@ -133,15 +151,31 @@ pub fn save_data_to_file<T: FileData, P: AsRef<Path>>(
     memo: &str,
     path: P,
     compression_level: Option<i32>,
-) -> Result<(), String> {
-    let file = File::create(path).map_err(|e| format!("Failed to create file: {}", e))?;
+) -> Result<(), Error> {
+    let file = File::create(path)?;
     let mut writer = BufWriter::new(file);
     save_data_into_std_write(data, memo, &mut writer, compression_level)
 }
 
-fn decode_from_std_read<D: Decode<()>, R: Read>(reader: &mut R, err_msg: &str) -> Result<D, String> {
-    bincode::decode_from_std_read(reader, bincode::config::standard())
-        .map_err(|e| format!("{}: {}", err_msg, e))
+fn decode_from_std_read<D: Decode<()>, R: Read>(reader: &mut R) -> Result<D, Error> {
+    Ok(bincode::decode_from_std_read(
+        reader,
+        bincode::config::standard(),
+    )?)
+}
+
+fn decode_body<D: Decode<()>, R: Read>(reader: &mut R, data_type: DataType) -> Result<D, Error> {
+    if let DataType::Bunching = data_type {
+        return decode_from_std_read(reader);
+    }
+
+    // the game data starts with its version string; check it here to report a mismatch as
+    // `Error::VersionMismatch`, then decode the whole data with the string put back
+    let version: String = decode_from_std_read(reader)?;
+    check_version_str(&version)?;
+    let mut prefix = Vec::new();
+    encode_into_std_write(&version, &mut prefix)?;
+    decode_from_std_read(&mut prefix.as_slice().chain(reader))
 }
 
 /// Loads data from a standard reader.
@@ -163,50 +197,67 @@ fn decode_from_std_read<D: Decode<()>, R: Read>(reader: &mut R, err_msg: &str) -
 pub fn load_data_from_std_read<T: FileData, R: Read>(
     reader: &mut R,
     max_memory_usage: Option<u64>,
-) -> Result<(T, String), String> {
-    let magic: u32 = decode_from_std_read(reader, "Failed to read magic number")?;
+) -> Result<(T, String), Error> {
+    load_from_std_read_internal(reader, T::data_type(), max_memory_usage)
+}
+
+fn load_from_std_read_internal<D: Decode<()>, R: Read>(
+    reader: &mut R,
+    expected_data_type: DataType,
+    max_memory_usage: Option<u64>,
+) -> Result<(D, String), Error> {
+    let magic: u32 = decode_from_std_read(reader)?;
     if magic != MAGIC {
-        return Err("Magic number is invalid".to_string());
+        return Err(Error::InvalidFile("Magic number is invalid".to_string()));
     }
 
-    let version: u8 = decode_from_std_read(reader, "Failed to read version number")?;
-    if version != VERSION {
-        return Err("Version number is invalid".to_string());
-    }
+    let version: u8 = decode_from_std_read(reader)?;
 
-    let compression_type: u8 = decode_from_std_read(reader, "Failed to read compression type")?;
+    let compression_type: u8 = decode_from_std_read(reader)?;
     if compression_type > 1 {
-        return Err("Compression type is invalid".to_string());
+        return Err(Error::InvalidFile(
+            "Compression type is invalid".to_string(),
+        ));
     }
 
     #[cfg(not(feature = "zstd"))]
     if compression_type == 1 {
-        return Err("Compression is not supported".to_string());
+        return Err(Error::InvalidConfig(
+            "Compression is not supported".to_string(),
+        ));
+    }
+
+    let data_type: u8 = decode_from_std_read(reader)?;
+    if data_type != expected_data_type as u8 {
+        return Err(Error::InvalidFile("Data type is invalid".to_string()));
     }
 
-    let data_type: u8 = decode_from_std_read(reader, "Failed to read data type")?;
-    if data_type != T::data_type() as u8 {
-        return Err("Data type is invalid".to_string());
+    if version != expected_data_type.version() {
+        return Err(Error::VersionMismatch {
+            found: version.to_string(),
+            expected: expected_data_type.version().to_string(),
+        });
     }
 
-    let estimated_memory_usage: u64 = decode_from_std_read(reader, "Failed to read memory usage")?;
+    let estimated_memory_usage: u64 = decode_from_std_read(reader)?;
     if let Some(max_memory_usage) = max_memory_usage {
         if estimated_memory_usage > max_memory_usage {
-            return Err("Estimated memory usage is too large".to_string());
+            return Err(Error::TreeTooLarge(
+                "Estimated memory usage is too large".to_string(),
+            ));
         }
     }
 
-    let memo: String = decode_from_std_read(reader, "Failed to read memo")?;
+    let memo: String = decode_from_std_read(reader)?;
 
     #[cfg(not(feature = "zstd"))]
-    let data: T = decode_from_std_read(reader, "Failed to read data")?;
+    let data: D = decode_body(reader, expected_data_type)?;
     #[cfg(feature = "zstd")]
-    let data: T = if compression_type == 0 {
-        decode_from_std_read(reader, "Failed to read data")?
+    let data: D = if compression_type == 0 {
+        decode_body(reader, expected_data_type)?
     } else {
-        let mut zstd_decoder = zstd::stream::Decoder::new(reader)
-            .map_err(|e| format!("Failed to create zstd decoder: {}", e))?;
-        decode_from_std_read(&mut zstd_decoder, "Failed to read data")?
+        let mut zstd_decoder = zstd::stream::Decoder::new(reader)?;
+        decode_body(&mut zstd_decoder, expected_data_type)?
     };
 
     Ok((data, memo))
@@ -229,19 +280,124 @@ pub fn load_data_from_std_read<T: FileData, R: Read>(
 pub fn load_data_from_file<T: FileData, P: AsRef<Path>>(
     path: P,
     max_memory_usage: Option<u64>,
-) -> Result<(T, String), String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+) -> Result<(T, String), Error> {
+    let file = File::open(path)?;
     let mut reader = BufReader::new(file);
     load_data_from_std_read(&mut reader, max_memory_usage)
 }
 
+/// Saves a game in the middle of solving into a standard writer.
+///
+/// Unlike [`save_data_into_std_write`], this function also saves the cumulative regrets (and the
+/// predictions of predictive algorithms, if allocated) together with `current_iteration`, so that
+/// the solving process can be resumed later with the same discounting schedule. The re-solving
+/// gadget of a subgame created by [`PostFlopGame::create_subgame`] is saved as well.
+/// If you want to save the checkpoint into a file, use [`save_checkpoint_to_file`] instead.
+///
+/// # Arguments
+///
+/// - `game`: The game to be saved. The game must be allocated but not yet solved.
+/// - `current_iteration`: The number of completed iterations.
+/// - `memo`: A memo string to be saved with the checkpoint.
+/// - `writer`: The writer to write the checkpoint into.
+/// - `compression_level`: The zstd compression level to use. If `None`, no compression is used.
+///   `Some(level)` can only be specified if the `zstd` feature is enabled.
+pub fn save_checkpoint_into_std_write<W: Write>(
+    game: &PostFlopGame,
+    current_iteration: u32,
+    memo: &str,
+    writer: &mut W,
+    compression_level: Option<i32>,
+) -> Result<(), Error> {
+    game.check_checkpoint()?;
+
+    let checkpoint = CheckpointRef {
+        game,
+        current_iteration,
+    };
+
+    save_into_std_write_internal(
+        &checkpoint,
+        DataType::Checkpoint,
+        game.allocated_memory(),
+        memo,
+        writer,
+        compression_level,
+    )
+}
+
+/// Saves a game in the middle of solving into a file.
+///
+/// See [`save_checkpoint_into_std_write`] for details.
+/// If the file already exists, it will be overwritten.
+pub fn save_checkpoint_to_file<P: AsRef<Path>>(
+    game: &PostFlopGame,
+    current_iteration: u32,
+    memo: &str,
+    path: P,
+    compression_level: Option<i32>,
+) -> Result<(), Error> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    save_checkpoint_into_std_write(
+        game,
+        current_iteration,
+        memo,
+        &mut writer,
+        compression_level,
+    )
+}
+
+/// Loads a game in the middle of solving from a standard reader.
+///
+/// If you want to load the checkpoint from a file, use [`load_checkpoint_from_file`] instead.
+///
+/// # Returns
+///
+/// A tuple of the restored game, the number of completed iterations, and the memo string.
+/// The solving process can be resumed by passing the number of completed iterations to
+/// [`resume_with_callback`] or by calling [`solve_step`] starting from that iteration.
+///
+/// [`resume_with_callback`]: crate::resume_with_callback
+/// [`solve_step`]: crate::solve_step
+pub fn load_checkpoint_from_std_read<R: Read>(
+    reader: &mut R,
+    max_memory_usage: Option<u64>,
+) -> Result<(PostFlopGame, u32, String), Error> {
+    let (checkpoint, memo): (Checkpoint, String) =
+        load_from_std_read_internal(reader, DataType::Checkpoint, max_memory_usage)?;
+    Ok((checkpoint.game, checkpoint.current_iteration, memo))
+}
+
+/// Loads a game in the middle of solving from a file.
+///
+/// See [`load_checkpoint_from_std_read`] for details.
+pub fn load_checkpoint_from_file<P: AsRef<Path>>(
+    path: P,
+    max_memory_usage: Option<u64>,
+) -> Result<(PostFlopGame, u32, String), Error> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    load_checkpoint_from_std_read(&mut reader, max_memory_usage)
+}
+
 impl FileData for PostFlopGame {
     fn data_type() -> DataType {
         DataType::Game
     }
 
-    fn is_ready_to_save(&self) -> bool {
-        self.is_solved()
+    fn check_ready_to_save(&self) -> Result<(), Error> {
+        if !self.is_solved() {
+            return Err(Error::InvalidState("Data is not ready to save".to_string()));
+        }
+
+        if self.terminal_payoff().is_some() {
+            return Err(Error::InvalidState(
+                "Game with a terminal payoff cannot be saved".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
     fn estimated_memory_usage(&self) -> u64 {
@@ -254,8 +410,12 @@ impl FileData for BunchingData {
         DataType::Bunching
     }
 
-    fn is_ready_to_save(&self) -> bool {
-        self.is_ready()
+    fn check_ready_to_save(&self) -> Result<(), Error> {
+        if !self.is_ready() {
+            return Err(Error::InvalidState("Data is not ready to save".to_string()));
+        }
+
+        Ok(())
     }
 
     fn estimated_memory_usage(&self) -> u64 {
@@ -268,7 +428,9 @@ mod tests {
     use super::*;
     use crate::action_tree::*;
     use crate::card::*;
+    use crate::icm::*;
     use crate::range::*;
+    use crate::solver::*;
     use crate::utility::*;
 
     #[test]
@@ -330,6 +492,227 @@ mod tests {
         assert!((root_ev_ip - 15.0).abs() < 1e-4);
     }
 
+    #[test]
+    fn save_and_load_checkpoint() {
+        checkpoint_round_trip(false, "tmpfile.ckpt");
+    }
+
+    #[test]
+    fn save_and_load_checkpoint_compressed() {
+        checkpoint_round_trip(true, "tmpfile-compressed.ckpt");
+    }
+
+    fn checkpoint_round_trip(enable_compression: bool, path: &str) {
+        let card_config = CardConfig {
+            range: [
+                "TT+,AKs,AQo,76s".parse().unwrap(),
+                "99+,AJs+,KQs".parse().unwrap(),
+            ],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 60,
+            effective_stack: 300,
+            turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.allocate_memory(enable_compression);
+
+        for t in 0..20 {
+            solve_step(&game, t);
+        }
+
+        // `save_data` rejects a game in the middle of solving, and `load_data` rejects a checkpoint
+        // file
+        assert!(save_data_to_file(&game, "", path, None).is_err());
+        save_checkpoint_to_file(&game, 20, "memo", path, None).unwrap();
+        assert!(load_data_from_file::<PostFlopGame, _>(path, None).is_err());
+
+        let (resumed, current_iteration, memo) = load_checkpoint_from_file(path, None).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(current_iteration, 20);
+        assert_eq!(memo, "memo");
+
+        for t in 20..40 {
+            solve_step(&game, t);
+            solve_step(&resumed, t);
+        }
+
+        let exploitability = compute_exploitability(&game);
+        let exploitability_resumed = compute_exploitability(&resumed);
+        assert!((exploitability - exploitability_resumed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn save_and_load_subgame_checkpoint() {
+        let card_config = CardConfig {
+            range: [
+                "TT+,AKs,AQo,76s".parse().unwrap(),
+                "99+,AJs+,KQs".parse().unwrap(),
+            ],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 60,
+            effective_stack: 300,
+            turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.allocate_memory(false);
+        solve(&mut game, 100, 0.5, false);
+
+        let tree_config = TreeConfig {
+            turn_bet_sizes: [("50%, 100%", "").try_into().unwrap(), Default::default()],
+            ..game.subgame_tree_config().unwrap()
+        };
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut subgame = game.create_subgame(action_tree, 0).unwrap();
+        subgame.allocate_memory(false);
+
+        for t in 0..20 {
+            solve_step(&subgame, t);
+        }
+
+        let path = "tmpfile-subgame.ckpt";
+        save_checkpoint_to_file(&subgame, 20, "", path, None).unwrap();
+        let (resumed, _, _) = load_checkpoint_from_file(path, None).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        // the re-solving gadget is restored
+        assert_eq!(resumed.gadget_player(), Some(1));
+        assert_eq!(resumed.gadget_values(), subgame.gadget_values());
+        assert_eq!(*resumed.gadget_regrets(), *subgame.gadget_regrets());
+
+        for t in 20..40 {
+            solve_step(&subgame, t);
+            solve_step(&resumed, t);
+        }
+
+        assert_eq!(*resumed.gadget_regrets(), *subgame.gadget_regrets());
+        let exploitability = compute_exploitability(&subgame);
+        let exploitability_resumed = compute_exploitability(&resumed);
+        assert!((exploitability - exploitability_resumed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn save_terminal_payoff() {
+        let card_config = CardConfig {
+            range: ["AA,KK,QQ".parse().unwrap(), "JJ,AKs".parse().unwrap()],
+            flop: flop_from_str("Td6h2c").unwrap(),
+            turn: card_from_str("3s").unwrap(),
+            river: card_from_str("8d").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::River,
+            starting_pot: 40,
+            effective_stack: 200,
+            river_bet_sizes: [("a", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        let payoff = TerminalPayoff::icm(vec![200.0, 200.0, 100.0], vec![0.7, 0.3]).unwrap();
+        game.set_terminal_payoff(payoff).unwrap();
+        game.allocate_memory(false);
+
+        // the terminal payoff is not serialized, so saving is rejected
+        let mut buf = Vec::new();
+        solve_step(&game, 0);
+        assert!(save_checkpoint_into_std_write(&game, 1, "", &mut buf, None).is_err());
+        finalize(&mut game);
+        assert!(save_data_into_std_write(&game, "", &mut buf, None).is_err());
+
+        game.reset_terminal_payoff();
+        save_data_into_std_write(&game, "", &mut buf, None).unwrap();
+    }
+
+    #[test]
+    fn load_invalid_header() {
+        let mut buf = Vec::new();
+        encode_into_std_write(0u32, &mut buf).unwrap();
+        let err = load_data_from_std_read::<PostFlopGame, _>(&mut buf.as_slice(), None);
+        assert!(matches!(err, Err(Error::InvalidFile(_))));
+
+        let mut buf = Vec::new();
+        encode_into_std_write(MAGIC, &mut buf).unwrap();
+        encode_into_std_write(1u8, &mut buf).unwrap();
+        encode_into_std_write(0u8, &mut buf).unwrap();
+        encode_into_std_write(DataType::Game as u8, &mut buf).unwrap();
+        let err = load_data_from_std_read::<PostFlopGame, _>(&mut buf.as_slice(), None);
+        assert_eq!(
+            err.err(),
+            Some(Error::VersionMismatch {
+                found: "1".to_string(),
+                expected: "2".to_string()
+            })
+        );
+
+        let err = load_data_from_std_read::<PostFlopGame, _>(&mut [].as_slice(), None);
+        assert!(matches!(err, Err(Error::Decode(_))));
+    }
+
+    #[test]
+    fn load_game_version_mismatch() {
+        let card_config = CardConfig {
+            range: [Range::ones(); 2],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            river: card_from_str("7s").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::River,
+            starting_pot: 60,
+            effective_stack: 970,
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+        game.allocate_memory(false);
+        finalize(&mut game);
+
+        let mut buf = Vec::new();
+        save_data_into_std_write(&game, "", &mut buf, None).unwrap();
+
+        // overwrite the version string of the game data with an old one
+        let pos = buf
+            .windows(VERSION_STR.len())
+            .position(|w| w == VERSION_STR.as_bytes())
+            .unwrap();
+        buf[pos..pos + VERSION_STR.len()].copy_from_slice(b"2023-03-19");
+
+        let err = load_data_from_std_read::<PostFlopGame, _>(&mut buf.as_slice(), None);
+        assert_eq!(
+            err.err(),
+            Some(Error::VersionMismatch {
+                found: "2023-03-19".to_string(),
+                expected: VERSION_STR.to_string()
+            })
+        );
+    }
+
     #[test]
     #[cfg(feature = "zstd")]
     fn save_and_load_file_compressed() {
@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::{
-    card_to_string_simple, deal, play, select_spot, Card, GameState, PostFlopGame, Spot, SpotType,
+    card_to_string_simple, deal, play, select_spot, Card, Error, GameState, PostFlopGame, Spot,
+    SpotType,
 };
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -33,7 +34,7 @@ pub struct TreeNode {
     pub path: String,
 }
 
-pub fn save_exploration_results(game: &mut PostFlopGame, filename: &str) -> Result<(), String> {
+pub fn save_exploration_results(game: &mut PostFlopGame, filename: &str) -> Result<(), Error> {
     let tree = build_exploration_tree(game)?;
     // let file = File::create(filename).map_err(|e| format!("Erreur création fichier: {}", e))?;
     // let writer = BufWriter::new(file);
@@ -52,7 +53,7 @@ fn build_node_recursive(
     turn_actions: &mut Vec<String>,
     river_actions: &mut Vec<String>,
     current_street: &mut &str,
-) -> Result<TreeNode, String> {
+) -> Result<TreeNode, Error> {
     // println!("DEBUG - Current street: {}", current_street);
     // println!("DEBUG - Flop actions: {:?}", flop_actions);
     // println!("DEBUG - Turn actions: {:?}", turn_actions);
@@ -61,12 +62,18 @@ fn build_node_recursive(
     if state.selected_chance_index > -1 {
         let chance_index = state.selected_chance_index as usize;
         if chance_index >= state.spots.len() {
-            return Err(format!("Index de chance invalide: {}", chance_index));
+            return Err(Error::InvalidState(format!(
+                "Invalid chance index: {}",
+                chance_index
+            )));
         }
 
         let chance_spot = &state.spots[chance_index];
         if chance_spot.spot_type != SpotType::Chance {
-            return Err(format!("Nœud non-chance à l'index {}", chance_index));
+            return Err(Error::InvalidState(format!(
+                "Spot at index {} is not a chance node",
+                chance_index
+            )));
         }
 
         // Pour les nœuds chance, créer un nœud avec le type spécifique
@@ -89,7 +96,7 @@ fn build_node_recursive(
             .collect();
 
         if available_cards.is_empty() {
-            return Err("Aucune carte disponible pour la distribution".to_string());
+            return Err(Error::InvalidState("No card available to deal".to_string()));
         }
 
         // Explorer chaque carte disponible
@@ -166,7 +173,12 @@ fn build_node_recursive(
     let current_spot_index = state.selected_spot_index as usize;
     let current_spot = match state.spots.get(current_spot_index) {
         Some(spot) => spot,
-        None => return Err(format!("Spot à l'index {} non trouvé", current_spot_index)),
+        None => {
+            return Err(Error::InvalidState(format!(
+                "Spot not found at index {}",
+                current_spot_index
+            )))
+        }
     };
 
     match current_spot.spot_type {
@@ -302,7 +314,7 @@ fn build_node_recursive(
 }
 
 // Fonction d'initialisation pour l'exploration d'arbre
-fn build_exploration_tree(game: &mut PostFlopGame) -> Result<TreeNode, String> {
+fn build_exploration_tree(game: &mut PostFlopGame) -> Result<TreeNode, Error> {
     // Initialiser l'état de jeu
     let mut state = GameState::new();
     let starting_pot = game.tree_config().starting_pot as f64;
@@ -318,6 +330,7 @@ fn build_exploration_tree(game: &mut PostFlopGame) -> Result<TreeNode, String> {
         cards: Vec::new(),
         pot: starting_pot,
         stack: effective_stack,
+        stacks: game.tree_config().player_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: None,
     };
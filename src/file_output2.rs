@@ -2,8 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 use crate::{
-    card_to_string_simple, format_path_string, holes_to_strings, play, select_spot, GameState,
-    PostFlopGame, SpecificResultData, Spot, SpotType,
+    card_to_string_simple, format_action, format_path_string, holes_to_strings, play, select_spot,
+    AmountUnit, Error, GameState, PostFlopGame, SpecificResultData, Spot, SpotType,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -42,11 +42,15 @@ struct RangeData {
     ip_player: PlayerData,
 }
 
+/// Explores the game tree from the current street and saves the ranges of each node as JSON.
+///
+/// The action labels in the saved strategies are formatted in `unit` (e.g., "Bet 2.5bb").
 pub fn explore_and_save_ranges(
     game: &mut PostFlopGame,
     output_dir: &str,
     max_depth: usize,
-) -> Result<(), String> {
+    unit: AmountUnit,
+) -> Result<(), Error> {
     let board_size = game.current_board().len();
     let street_name = match board_size {
         3 => "flop",
@@ -72,8 +76,7 @@ pub fn explore_and_save_ranges(
 
     // Créer le répertoire de sortie s'il n'existe pas
     if !Path::new(&street_dir).exists() {
-        std::fs::create_dir_all(&street_dir)
-            .map_err(|e| format!("Échec de création du répertoire {}: {}", street_dir, e))?;
+        std::fs::create_dir_all(&street_dir)?;
     }
 
     let mut state = GameState::new();
@@ -86,6 +89,7 @@ pub fn explore_and_save_ranges(
         cards: Vec::new(),
         pot: game.tree_config().starting_pot as f64,
         stack: game.tree_config().effective_stack as f64,
+        stacks: game.tree_config().player_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: None,
     };
@@ -94,7 +98,7 @@ pub fn explore_and_save_ranges(
 
     let results = select_spot(game, &mut state, 1, true, false)?;
     let root_path_id = format!("{}_Root", current_street);
-    save_node_data(game, &root_path_id, &street_dir, &results)?;
+    save_node_data(game, &root_path_id, &street_dir, &results, unit)?;
 
     let mut actions = Vec::new();
     explore_actions_recursive(
@@ -105,10 +109,12 @@ pub fn explore_and_save_ranges(
         &street_dir,
         0,
         max_depth,
+        unit,
     )
     // Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn explore_actions_recursive(
     game: &mut PostFlopGame,
     state: &mut GameState,
@@ -117,7 +123,8 @@ fn explore_actions_recursive(
     output_dir: &str,
     depth: usize,
     max_depth: usize,
-) -> Result<(), String> {
+    unit: AmountUnit,
+) -> Result<(), Error> {
     // Si nous avons atteint un nœud terminal, un nœud chance ou la profondeur maximale, nous nous arrêtons
     if game.is_terminal_node() || game.is_chance_node() || depth >= max_depth {
         return Ok(());
@@ -126,7 +133,10 @@ fn explore_actions_recursive(
     // Obtenir le spot actuel et ses actions disponibles
     let spot_index = state.selected_spot_index as usize;
     if spot_index >= state.spots.len() {
-        return Err(format!("Index de spot invalide: {}", spot_index));
+        return Err(Error::InvalidState(format!(
+            "Invalid spot index: {}",
+            spot_index
+        )));
     }
 
     let action_count = state.spots[spot_index].actions.len();
@@ -157,7 +167,7 @@ fn explore_actions_recursive(
 
         if action_name != "Fold" {
             let path_id = format_path_string(actions, current_street);
-            save_node_data(game, &path_id, output_dir, &results)?;
+            save_node_data(game, &path_id, output_dir, &results, unit)?;
         }
 
         if !game.is_terminal_node() && !game.is_chance_node() && depth + 1 < max_depth {
@@ -169,6 +179,7 @@ fn explore_actions_recursive(
                 output_dir,
                 depth + 1,
                 max_depth,
+                unit,
             )?;
         }
 
@@ -252,9 +263,12 @@ pub fn save_node_data(
     path_id: &str,
     output_dir: &str,
     results: &SpecificResultData,
-) -> Result<bool, String> {
+    unit: AmountUnit,
+) -> Result<bool, Error> {
     if results.equity.is_empty() || results.ev.is_empty() || results.weights.is_empty() {
-        return Err("Résultats incomplets, impossible de sauvegarder le nœud".to_string());
+        return Err(Error::InvalidState(
+            "Results are incomplete; cannot save the node".to_string(),
+        ));
     }
 
     let mut filename = path_id
@@ -295,23 +309,20 @@ pub fn save_node_data(
         pot_oop,
         pot_ip,
         current_player: game.current_player(),
-        oop_player: build_player_data(game, 0, results)?,
-        ip_player: build_player_data(game, 1, results)?,
+        oop_player: build_player_data(game, 0, results, unit)?,
+        ip_player: build_player_data(game, 1, results, unit)?,
     };
 
     // Sérialiser en JSON
-    let json_data = serde_json::to_string_pretty(&range_data)
-        .map_err(|e| format!("Erreur de sérialisation JSON: {}", e))?;
+    let json_data = serde_json::to_string_pretty(&range_data)?;
 
     // Créer le répertoire s'il n'existe pas
     if !Path::new(output_dir).exists() {
-        std::fs::create_dir_all(output_dir)
-            .map_err(|e| format!("Échec de création du répertoire {}: {}", output_dir, e))?;
+        std::fs::create_dir_all(output_dir)?;
     }
 
     // Écrire le fichier JSON
-    std::fs::write(&full_path, json_data)
-        .map_err(|e| format!("Échec d'écriture du fichier {}: {}", full_path, e))?;
+    std::fs::write(&full_path, json_data)?;
 
     // println!(
     //     "Données sauvegardées en JSON pour '{}' dans {}",
@@ -325,26 +336,27 @@ fn build_player_data(
     game: &mut PostFlopGame,
     player: usize,
     results: &SpecificResultData,
-) -> Result<PlayerData, String> {
+    unit: AmountUnit,
+) -> Result<PlayerData, Error> {
     if results.equity.is_empty() {
-        return Err("Les données d'équité sont vides".to_string());
+        return Err(Error::InvalidState("Equity data is empty".to_string()));
     }
 
     if results.ev.is_empty() {
-        return Err("Les données d'EV sont vides".to_string());
+        return Err(Error::InvalidState("EV data is empty".to_string()));
     }
 
     if results.weights.is_empty() {
-        return Err("Les données de poids sont vides".to_string());
+        return Err(Error::InvalidState("Weight data is empty".to_string()));
     }
 
     // Vérifier si l'indice du joueur est valide
     if player >= results.equity.len() {
-        return Err(format!(
-            "Index de joueur invalide: {} (max: {})",
+        return Err(Error::InvalidState(format!(
+            "Invalid player index: {} (max: {})",
             player,
             results.equity.len() - 1
-        ));
+        )));
     }
 
     let equity = &results.equity[player];
@@ -376,16 +388,13 @@ fn build_player_data(
     // }
 
     // Convertir les mains en chaînes
-    let hand_strings = match holes_to_strings(
+    let hand_strings = holes_to_strings(
         hands
             .iter()
             .map(|&(c1, c2)| (c1 as u8, c2 as u8))
             .collect::<Vec<_>>()
             .as_slice(),
-    ) {
-        Ok(strings) => strings,
-        Err(_) => return Err("Erreur lors de la conversion des mains en chaînes".to_string()),
-    };
+    )?;
 
     let is_current_player = player == game.current_player();
 
@@ -398,7 +407,7 @@ fn build_player_data(
         let actions = game.available_actions();
         action_names = actions
             .iter()
-            .map(|a| format!("{:?}", a)) // Format standard pour les actions
+            .map(|&a| format_action(game, a, unit))
             .collect();
         strategy = game.strategy();
         action_evs = game.expected_values_detail(player);
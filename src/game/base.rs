@@ -52,10 +52,11 @@ impl Game for PostFlopGame {
 
     #[inline]
     fn chance_factor(&self, node: &Self::Node) -> usize {
+        let num_cards = self.card_config.deck.num_cards();
         if node.turn == NOT_DEALT {
-            45 - self.bunching_num_dead_cards
+            num_cards - 7 - self.bunching_num_dead_cards
         } else {
-            44 - self.bunching_num_dead_cards
+            num_cards - 8 - self.bunching_num_dead_cards
         }
     }
 
@@ -67,6 +68,7 @@ impl Game for PostFlopGame {
     #[inline]
     fn set_solved(&mut self) {
         self.state = State::Solved;
+        self.storage_prediction = Vec::new();
         let history = self.action_history.clone();
         self.apply_history(&history);
     }
@@ -78,7 +80,8 @@ impl Game for PostFlopGame {
 
     #[inline]
     fn is_raked(&self) -> bool {
-        self.tree_config.rake_rate > 0.0 && self.tree_config.rake_cap > 0.0
+        (self.tree_config.rake_rate > 0.0 && self.tree_config.rake_cap > 0.0)
+            || self.terminal_payoff.is_some()
     }
 
     #[inline]
@@ -114,6 +117,36 @@ impl Game for PostFlopGame {
     fn is_compression_enabled(&self) -> bool {
         self.is_compression_enabled
     }
+
+    #[inline]
+    fn allocated_memory(&self) -> u64 {
+        let storage_bytes = self.storage1.len()
+            + self.storage2.len()
+            + self.storage_ip.len()
+            + self.storage_chance.len()
+            + self.storage_prediction.len();
+        storage_bytes as u64 + self.misc_memory_usage
+    }
+
+    #[inline]
+    fn is_prediction_enabled(&self) -> bool {
+        !self.storage_prediction.is_empty()
+    }
+
+    #[inline]
+    fn gadget_player(&self) -> Option<usize> {
+        self.gadget_player
+    }
+
+    #[inline]
+    fn gadget_values(&self) -> &[f32] {
+        &self.gadget_values
+    }
+
+    #[inline]
+    fn gadget_regrets(&self) -> MutexGuardLike<'_, Vec<f32>> {
+        self.gadget_regrets.lock()
+    }
 }
 
 impl PostFlopGame {
@@ -132,7 +165,7 @@ impl PostFlopGame {
 
     /// Creates a new [`PostFlopGame`] with the specified configuration.
     #[inline]
-    pub fn with_config(card_config: CardConfig, action_tree: ActionTree) -> Result<Self, String> {
+    pub fn with_config(card_config: CardConfig, action_tree: ActionTree) -> Result<Self, Error> {
         let mut game = Self::new();
         game.update_config(card_config, action_tree)?;
         Ok(game)
@@ -144,13 +177,19 @@ impl PostFlopGame {
         &mut self,
         card_config: CardConfig,
         action_tree: ActionTree,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         self.state = State::ConfigError;
 
         if !action_tree.invalid_terminals().is_empty() {
-            return Err("Invalid terminal is found in action tree".to_string());
+            return Err(Error::InvalidConfig(
+                "Invalid terminal is found in action tree".to_string(),
+            ));
         }
 
+        self.gadget_player = None;
+        self.gadget_values = Vec::new();
+        self.gadget_regrets = MutexLike::default();
+
         self.card_config = card_config;
         (
             self.tree_config,
@@ -167,6 +206,7 @@ impl PostFlopGame {
 
         self.init_interpreter();
         self.reset_bunching_effect();
+        self.reset_terminal_payoff();
 
         Ok(())
     }
@@ -178,23 +218,34 @@ impl PostFlopGame {
     /// *O*(#(OOP private hands) + #(IP private hands)) to *O*(#(OOP private hands) * #(IP private
     /// hands)).
     #[inline]
-    pub fn set_bunching_effect(&mut self, bunching_data: &BunchingData) -> Result<(), String> {
+    pub fn set_bunching_effect(&mut self, bunching_data: &BunchingData) -> Result<(), Error> {
         if self.state <= State::Uninitialized {
-            return Err("Game is not successfully initialized".to_string());
+            return Err(Error::InvalidState(
+                "Game is not successfully initialized".to_string(),
+            ));
         }
 
         if !bunching_data.is_ready() {
-            return Err("Bunching configuration is not ready".to_string());
+            return Err(Error::InvalidState(
+                "Bunching configuration is not ready".to_string(),
+            ));
         }
 
         let mut flop_sorted = self.card_config.flop;
         flop_sorted.sort_unstable();
         if flop_sorted != bunching_data.flop() {
-            return Err("Flop cards do not match".to_string());
+            return Err(Error::InvalidConfig("Flop cards do not match".to_string()));
+        }
+
+        if self.card_config.deck != DeckType::Standard {
+            return Err(Error::InvalidConfig(
+                "Bunching effect is only supported with the standard deck".to_string(),
+            ));
         }
 
         self.reset_bunching_effect();
-        self.set_bunching_effect_internal(bunching_data)?;
+        self.set_bunching_effect_internal(bunching_data)
+            .map_err(Error::InvalidConfig)?;
 
         Ok(())
     }
@@ -214,6 +265,52 @@ impl PostFlopGame {
         self.back_to_root();
     }
 
+    /// Sets the terminal payoff, which converts the chip stacks after the hand into dollar equity.
+    ///
+    /// The stacks of OOP and IP in `payoff` must be at least [`TreeConfig::stacks`]. Once set, the
+    /// counterfactual values and [`expected_values`] are measured in dollars instead of chips, and
+    /// the game is no longer zero-sum. The terminal payoff is reset by [`update_config`]. It cannot
+    /// be serialized, so saving a game or a checkpoint with a terminal payoff returns an error.
+    ///
+    /// [`expected_values`]: #method.expected_values
+    /// [`update_config`]: #method.update_config
+    pub fn set_terminal_payoff(&mut self, payoff: TerminalPayoff) -> Result<(), Error> {
+        if self.state <= State::Uninitialized {
+            return Err(Error::InvalidState(
+                "Game is not successfully initialized".to_string(),
+            ));
+        }
+
+        if self.state == State::Solved {
+            return Err(Error::InvalidState("Game is already solved".to_string()));
+        }
+
+        let stacks = self.tree_config.player_stacks();
+        if payoff.stacks()[0] < stacks[0] as f64 || payoff.stacks()[1] < stacks[1] as f64 {
+            return Err(Error::InvalidConfig(format!(
+                "Stacks of OOP and IP must be at least the stacks of the tree configuration: {:?}",
+                &payoff.stacks()[..2]
+            )));
+        }
+
+        self.terminal_payoff = Some(payoff);
+        self.init_terminal_payoff_table();
+        Ok(())
+    }
+
+    /// Resets the terminal payoff so that the utility is linear in chips.
+    #[inline]
+    pub fn reset_terminal_payoff(&mut self) {
+        self.terminal_payoff = None;
+        self.terminal_payoff_table = Vec::new();
+    }
+
+    /// Obtains the terminal payoff, if set.
+    #[inline]
+    pub fn terminal_payoff(&self) -> Option<&TerminalPayoff> {
+        self.terminal_payoff.as_ref()
+    }
+
     /// Obtains the card configuration.
     #[inline]
     pub fn card_config(&self) -> &CardConfig {
@@ -226,6 +323,12 @@ impl PostFlopGame {
         &self.tree_config
     }
 
+    /// Obtains the root node of the action tree.
+    #[inline]
+    pub(crate) fn action_root(&self) -> MutexGuardLike<'_, ActionTreeNode> {
+        self.action_root.lock()
+    }
+
     /// Obtains the added lines.
     #[inline]
     pub fn added_lines(&self) -> &[Vec<Action>] {
@@ -282,16 +385,22 @@ impl PostFlopGame {
     ///
     /// This allows the removal of chance-specific lines (e.g., remove overbets on board-pairing
     /// turns) which we cannot do while building an action tree.
-    pub fn remove_lines(&mut self, lines: &[Vec<Action>]) -> Result<(), String> {
+    pub fn remove_lines(&mut self, lines: &[Vec<Action>]) -> Result<(), Error> {
         if self.state <= State::Uninitialized {
-            return Err("Game is not successfully initialized".to_string());
+            return Err(Error::InvalidState(
+                "Game is not successfully initialized".to_string(),
+            ));
         } else if self.state >= State::MemoryAllocated {
-            return Err("Game has already been allocated".to_string());
+            return Err(Error::InvalidState(
+                "Game has already been allocated".to_string(),
+            ));
         }
 
         for line in lines {
             let mut root = self.root();
-            let info = self.remove_line_recursive(&mut root, line)?;
+            let info = self
+                .remove_line_recursive(&mut root, line)
+                .map_err(Error::InvalidAction)?;
             self.num_storage -= info.num_storage;
             self.num_storage_ip -= info.num_storage_ip;
             self.num_storage_chance -= info.num_storage_chance;
@@ -353,57 +462,111 @@ impl PostFlopGame {
         self.target_storage_mode = BoardState::River;
     }
 
+    /// Allocates the additional memory required by predictive algorithms such as
+    /// [`PredictiveCfrPlus`].
+    ///
+    /// This method must be called after [`allocate_memory`] with compression disabled.
+    /// The additional memory usage is the same as the size of the cumulative regrets, i.e.,
+    /// `4 * num_storage` bytes. The memory is released when the game is solved.
+    ///
+    /// [`PredictiveCfrPlus`]: crate::PredictiveCfrPlus
+    /// [`allocate_memory`]: #method.allocate_memory
+    pub fn allocate_prediction_memory(&mut self) {
+        if self.state != State::MemoryAllocated || self.storage_mode != BoardState::River {
+            panic!("Memory is not allocated");
+        }
+
+        if self.is_compression_enabled {
+            panic!("Predictive algorithms do not support compression");
+        }
+
+        if self.is_prediction_enabled() {
+            return;
+        }
+
+        self.storage_prediction = vec![0; self.storage2.len()];
+
+        let base2 = self.storage2.as_ptr();
+        let base4 = self.storage_prediction.as_mut_ptr();
+        for node in &self.node_arena {
+            let mut node = node.lock();
+            if !node.is_terminal() && !node.is_chance() {
+                unsafe {
+                    let offset = node.storage2.offset_from(base2);
+                    node.storage4 = base4.offset(offset);
+                }
+            }
+        }
+    }
+
     /// Checks the card configuration.
-    pub(crate) fn check_card_config(&mut self) -> Result<(), String> {
+    pub(crate) fn check_card_config(&mut self) -> Result<(), Error> {
         let config = &self.card_config;
         let (flop, turn, river) = (config.flop, config.turn, config.river);
         let range = &config.range;
 
         if flop.contains(&NOT_DEALT) {
-            return Err("Flop cards not initialized".to_string());
+            return Err(Error::InvalidCard("Flop cards not initialized".to_string()));
         }
 
         if flop.iter().any(|&c| 52 <= c) {
-            return Err(format!("Flop cards must be in [0, 52): flop = {flop:?}"));
+            return Err(Error::InvalidCard(format!(
+                "Flop cards must be in [0, 52): flop = {flop:?}"
+            )));
         }
 
         if flop[0] == flop[1] || flop[0] == flop[2] || flop[1] == flop[2] {
-            return Err(format!("Flop cards must be unique: flop = {flop:?}"));
+            return Err(Error::InvalidCard(format!(
+                "Flop cards must be unique: flop = {flop:?}"
+            )));
+        }
+
+        let deck = config.deck;
+        for card in [flop[0], flop[1], flop[2], turn, river] {
+            if card != NOT_DEALT && 52 > card && !deck.contains(card) {
+                return Err(Error::InvalidCard(format!(
+                    "Board card is not contained in the deck: card = {card}, deck = {deck:?}"
+                )));
+            }
         }
 
         if turn != NOT_DEALT {
             if 52 <= turn {
-                return Err(format!("Turn card must be in [0, 52): turn = {turn}"));
+                return Err(Error::InvalidCard(format!(
+                    "Turn card must be in [0, 52): turn = {turn}"
+                )));
             }
 
             if flop.contains(&turn) {
-                return Err(format!(
+                return Err(Error::InvalidCard(format!(
                     "Turn card must be different from flop cards: turn = {turn}"
-                ));
+                )));
             }
         }
 
         if river != NOT_DEALT {
             if 52 <= river {
-                return Err(format!("River card must be in [0, 52): river = {river}"));
+                return Err(Error::InvalidCard(format!(
+                    "River card must be in [0, 52): river = {river}"
+                )));
             }
 
             if flop.contains(&river) {
-                return Err(format!(
+                return Err(Error::InvalidCard(format!(
                     "River card must be different from flop cards: river = {river}"
-                ));
+                )));
             }
 
             if turn == river {
-                return Err(format!(
+                return Err(Error::InvalidCard(format!(
                     "River card must be different from turn card: river = {river}"
-                ));
+                )));
             }
 
             if turn == NOT_DEALT {
-                return Err(format!(
+                return Err(Error::InvalidCard(format!(
                     "River card specified without turn card: river = {river}"
-                ));
+                )));
             }
         }
 
@@ -414,26 +577,30 @@ impl PostFlopGame {
         };
 
         if self.tree_config.initial_state != expected_state {
-            return Err(format!(
+            return Err(Error::InvalidConfig(format!(
                 "Invalid initial state of `tree_config`: expected = {:?}, actual = {:?}",
                 expected_state, self.tree_config.initial_state
-            ));
+            )));
         }
 
         if range[0].is_empty() {
-            return Err("OOP range is empty".to_string());
+            return Err(Error::InvalidRange("OOP range is empty".to_string()));
         }
 
         if range[1].is_empty() {
-            return Err("IP range is empty".to_string());
+            return Err(Error::InvalidRange("IP range is empty".to_string()));
         }
 
         if !range[0].is_valid() {
-            return Err("OOP range is invalid (loaded broken data?)".to_string());
+            return Err(Error::InvalidRange(
+                "OOP range is invalid (loaded broken data?)".to_string(),
+            ));
         }
 
         if !range[1].is_valid() {
-            return Err("IP range is invalid (loaded broken data?)".to_string());
+            return Err(Error::InvalidRange(
+                "IP range is invalid (loaded broken data?)".to_string(),
+            ));
         }
 
         self.init_hands();
@@ -456,7 +623,9 @@ impl PostFlopGame {
         }
 
         if self.num_combinations == 0.0 {
-            return Err("Valid card assignment does not exist".to_string());
+            return Err(Error::InvalidRange(
+                "Valid card assignment does not exist".to_string(),
+            ));
         }
 
         Ok(())
@@ -477,8 +646,11 @@ impl PostFlopGame {
             board_mask |= 1 << river;
         }
 
+        // exclude the hands containing the cards removed from the deck
+        let dead_mask = board_mask | config.deck.removed_cards_mask();
+
         for player in 0..2 {
-            let (hands, weights) = range[player].get_hands_weights(board_mask);
+            let (hands, weights) = range[player].get_hands_weights(dead_mask);
             self.initial_weights[player] = weights;
             self.private_cards[player] = hands;
         }
@@ -520,14 +692,14 @@ impl PostFlopGame {
     }
 
     /// Initializes the root node of game tree.
-    fn init_root(&mut self) -> Result<(), String> {
+    fn init_root(&mut self) -> Result<(), Error> {
         let num_nodes = self.count_num_nodes();
         let total_num_nodes = num_nodes[0] + num_nodes[1] + num_nodes[2];
 
         if total_num_nodes > u32::MAX as u64
             || mem::size_of::<PostFlopNode>() as u64 * total_num_nodes > isize::MAX as u64
         {
-            return Err("Too many nodes".to_string());
+            return Err(Error::TreeTooLarge("Too many nodes".to_string()));
         }
 
         self.num_nodes = num_nodes;
@@ -582,35 +754,50 @@ impl PostFlopGame {
         self.storage2 = Vec::new();
         self.storage_ip = Vec::new();
         self.storage_chance = Vec::new();
+        self.storage_prediction = Vec::new();
     }
 
-    /// Counts the number of nodes in the game tree.
+    /// Returns the numbers of game nodes corresponding to a single turn and river action node,
+    /// i.e., the numbers of dealt turn cards and (turn, river) pairs after isomorphism.
     #[inline]
-    fn count_num_nodes(&self) -> [u64; 3] {
-        let (turn_coef, river_coef) = match (self.card_config.turn, self.card_config.river) {
+    pub(super) fn num_deals(&self) -> [u64; 2] {
+        let num_cards = self.card_config.deck.num_cards();
+        match (self.card_config.turn, self.card_config.river) {
             (NOT_DEALT, _) => {
                 let mut river_coef = 0;
                 let flop = self.card_config.flop;
                 let skip_cards = &self.isomorphism_card_turn;
                 let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
                 let skip_mask: u64 = skip_cards.iter().map(|&card| 1 << card).sum();
+                let removed_mask = self.card_config.deck.removed_cards_mask();
                 for turn in 0..52 {
-                    if (1 << turn) & (flop_mask | skip_mask) == 0 {
-                        river_coef += 48 - self.isomorphism_card_river[turn & 3].len();
+                    if (1 << turn) & (flop_mask | skip_mask | removed_mask) == 0 {
+                        river_coef += num_cards - 4 - self.isomorphism_card_river[turn & 3].len();
                     }
                 }
-                (49 - self.isomorphism_card_turn.len(), river_coef)
+                [
+                    (num_cards - 3 - self.isomorphism_card_turn.len()) as u64,
+                    river_coef as u64,
+                ]
             }
-            (turn, NOT_DEALT) => (1, 48 - self.isomorphism_card_river[turn as usize & 3].len()),
-            _ => (0, 1),
-        };
+            (turn, NOT_DEALT) => [
+                1,
+                (num_cards - 4 - self.isomorphism_card_river[turn as usize & 3].len()) as u64,
+            ],
+            _ => [0, 1],
+        }
+    }
 
+    /// Counts the number of nodes in the game tree.
+    #[inline]
+    fn count_num_nodes(&self) -> [u64; 3] {
+        let [turn_coef, river_coef] = self.num_deals();
         let num_action_nodes = count_num_action_nodes(&self.action_root.lock());
 
         [
             num_action_nodes[0],
-            num_action_nodes[1] * turn_coef as u64,
-            num_action_nodes[2] * river_coef as u64,
+            num_action_nodes[1] * turn_coef,
+            num_action_nodes[2] * river_coef,
         ]
     }
 
@@ -713,6 +900,7 @@ impl PostFlopGame {
         let mut node = self.node_arena[node_index].lock();
         let flop = self.card_config.flop;
         let flop_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
+        let removed_mask = self.card_config.deck.removed_cards_mask();
 
         // deal turn
         if node.turn == NOT_DEALT {
@@ -721,7 +909,7 @@ impl PostFlopGame {
 
             node.children_offset = (info.turn_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (flop_mask | skip_mask) == 0 {
+                if (1 << card) & (flop_mask | removed_mask | skip_mask) == 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
@@ -739,7 +927,7 @@ impl PostFlopGame {
 
             node.children_offset = (info.river_index - node_index) as u32;
             for card in 0..52 {
-                if (1 << card) & (turn_mask | skip_mask) == 0 {
+                if (1 << card) & (turn_mask | removed_mask | skip_mask) == 0 {
                     node.num_children += 1;
                     let mut child = node.children().last().unwrap().lock();
                     child.prev_action = Action::Chance(card);
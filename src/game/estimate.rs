@@ -0,0 +1,145 @@
+use super::*;
+use crate::interface::*;
+use std::mem;
+
+/// The estimated size of a [`PostFlopGame`], returned by [`PostFlopGame::estimate_size`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameSizeEstimate {
+    /// Number of nodes on the flop, turn, and river, including chance and terminal nodes.
+    pub num_nodes: [u64; 3],
+
+    /// Number of chance nodes that deal the turn and the river, respectively.
+    pub num_chance_nodes: [u64; 2],
+
+    /// Number of turn cards and (turn, river) pairs dealt in the game tree after isomorphism.
+    /// The value is `0` if the corresponding card is already on the board.
+    pub num_deals: [u64; 2],
+
+    /// Estimated memory usage in bytes (uncompressed, compressed).
+    pub memory_usage: (u64, u64),
+}
+
+#[derive(Default)]
+struct EstimateInfo {
+    num_nodes: [u64; 3],
+    num_chance_nodes: [u64; 2],
+    num_storage: [u64; 3],
+    num_storage_ip: [u64; 3],
+    num_storage_chance: [u64; 3],
+}
+
+impl PostFlopGame {
+    /// Estimates the size of the game without building the game tree.
+    ///
+    /// The node counts are exact, i.e., they are the same as those of the game created by
+    /// [`with_config`]. The memory usage corresponds to [`memory_usage`], except that the small
+    /// tables that depend on the board cards (e.g., the hand strengths) are not included.
+    ///
+    /// This method only evaluates the ranges and the card isomorphism, so it is much faster than
+    /// [`with_config`] and does not allocate memory proportional to the tree size.
+    ///
+    /// [`with_config`]: #method.with_config
+    /// [`memory_usage`]: #method.memory_usage
+    pub fn estimate_size(
+        card_config: &CardConfig,
+        action_tree: &ActionTree,
+    ) -> Result<GameSizeEstimate, Error> {
+        if !action_tree.invalid_terminals().is_empty() {
+            return Err(Error::InvalidConfig(
+                "Invalid terminal is found in action tree".to_string(),
+            ));
+        }
+
+        let mut game = Self {
+            card_config: card_config.clone(),
+            tree_config: action_tree.config().clone(),
+            ..Default::default()
+        };
+
+        game.check_card_config()?;
+
+        (
+            _,
+            game.isomorphism_card_turn,
+            _,
+            _,
+            game.isomorphism_card_river,
+            _,
+        ) = game.card_config.isomorphism(&game.private_cards);
+
+        let mut info = EstimateInfo::default();
+        let street = game.tree_config.initial_state as usize;
+        game.estimate_recursive(&action_tree.root(), street, Action::None, &mut info);
+
+        let [turn_coef, river_coef] = game.num_deals();
+        let coef = [1, turn_coef, river_coef];
+        let apply_coef = |counts: [u64; 3]| -> u64 { (0..3).map(|i| counts[i] * coef[i]).sum() };
+
+        let num_nodes = [0, 1, 2].map(|i| info.num_nodes[i] * coef[i]);
+        let num_chance_nodes = [0, 1].map(|i| info.num_chance_nodes[i] * coef[i]);
+        let num_deals = match (card_config.turn, card_config.river) {
+            (NOT_DEALT, _) => [turn_coef, river_coef],
+            (_, NOT_DEALT) => [0, river_coef],
+            _ => [0, 0],
+        };
+
+        let num_elements = 2 * apply_coef(info.num_storage)
+            + apply_coef(info.num_storage_ip)
+            + apply_coef(info.num_storage_chance);
+
+        let total_num_nodes = num_nodes.iter().sum::<u64>();
+        let misc_memory_usage = mem::size_of::<Self>() as u64
+            + mem::size_of::<MutexLike<PostFlopNode>>() as u64 * total_num_nodes;
+
+        Ok(GameSizeEstimate {
+            num_nodes,
+            num_chance_nodes,
+            num_deals,
+            memory_usage: (
+                4 * num_elements + misc_memory_usage,
+                2 * num_elements + misc_memory_usage,
+            ),
+        })
+    }
+
+    /// Recursively counts the nodes and storage elements corresponding to the action tree.
+    fn estimate_recursive(
+        &self,
+        node: &ActionTreeNode,
+        street: usize,
+        prev_action: Action,
+        info: &mut EstimateInfo,
+    ) {
+        info.num_nodes[street] += 1;
+
+        if node.is_terminal() {
+            return;
+        }
+
+        if node.is_chance() {
+            info.num_chance_nodes[street] += 1;
+
+            // the chance node stores the counterfactual values of the player who acted last
+            let num_elements = match node.player & PLAYER_MASK {
+                PLAYER_OOP => self.num_private_hands(PLAYER_IP as usize),
+                PLAYER_IP => self.num_private_hands(PLAYER_OOP as usize),
+                _ => 0,
+            };
+            info.num_storage_chance[street] += num_elements as u64;
+
+            let child = &node.children[0].lock();
+            self.estimate_recursive(child, street + 1, Action::Chance(0), info);
+        } else {
+            let num_private_hands = self.num_private_hands(node.player as usize);
+            info.num_storage[street] += (node.actions.len() * num_private_hands) as u64;
+
+            if matches!(prev_action, Action::None | Action::Chance(_)) {
+                info.num_storage_ip[street] += self.num_private_hands(PLAYER_IP as usize) as u64;
+            }
+
+            for (&action, child) in node.actions.iter().zip(&node.children) {
+                self.estimate_recursive(&child.lock(), street, action, info);
+            }
+        }
+    }
+}
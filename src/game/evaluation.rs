@@ -1,4 +1,5 @@
 use super::*;
+use crate::interface::*;
 use crate::sliceop::*;
 use std::mem::MaybeUninit;
 
@@ -12,6 +13,63 @@ fn min(x: f64, y: f64) -> f64 {
 }
 
 impl PostFlopGame {
+    /// Returns the payoffs of `player` when the player wins, loses, and ties at `node`.
+    #[inline]
+    fn terminal_amounts(&self, node: &PostFlopNode, player: usize) -> [f64; 3] {
+        if self.terminal_payoff.is_some() {
+            let index = self
+                .terminal_payoff_table
+                .binary_search_by_key(&node.amount, |&(amount, _)| amount)
+                .unwrap();
+            return self.terminal_payoff_table[index].1[player];
+        }
+
+        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
+        let half_pot = 0.5 * pot;
+        let rake = self.rake(pot);
+        [half_pot - rake, -half_pot, -0.5 * rake]
+    }
+
+    /// Returns the rake taken from the given pot.
+    #[inline]
+    fn rake(&self, pot: f64) -> f64 {
+        min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap)
+    }
+
+    /// Precomputes the payoffs of the terminal payoff for every amount of the terminal nodes, so
+    /// that the payoff model is not evaluated during the terminal evaluation.
+    pub(super) fn init_terminal_payoff_table(&mut self) {
+        let Some(payoff) = &self.terminal_payoff else {
+            self.terminal_payoff_table = Vec::new();
+            return;
+        };
+
+        let mut amounts = self
+            .node_arena
+            .iter()
+            .filter_map(|node| {
+                let node = node.lock();
+                node.is_terminal().then_some(node.amount)
+            })
+            .collect::<Vec<_>>();
+        amounts.sort_unstable();
+        amounts.dedup();
+
+        let starting_pot = self.tree_config.starting_pot as f64;
+        let table = amounts
+            .into_iter()
+            .map(|amount| {
+                let rake = self.rake(starting_pot + 2.0 * amount as f64);
+                let payoffs = [0, 1].map(|player| {
+                    payoff.relative_payoffs(player, starting_pot, amount as f64, rake)
+                });
+                (amount, payoffs)
+            })
+            .collect();
+
+        self.terminal_payoff_table = table;
+    }
+
     pub(super) fn evaluate_internal(
         &self,
         result: &mut [MaybeUninit<f32>],
@@ -19,11 +77,9 @@ impl PostFlopGame {
         player: usize,
         cfreach: &[f32],
     ) {
-        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
-        let half_pot = 0.5 * pot;
-        let rake = min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap);
-        let amount_win = (half_pot - rake) / self.num_combinations;
-        let amount_lose = -half_pot / self.num_combinations;
+        let [win, lose, tie] = self.terminal_amounts(node, player);
+        let amount_win = win / self.num_combinations;
+        let amount_lose = lose / self.num_combinations;
 
         let player_cards = &self.private_cards[player];
         let opponent_cards = &self.private_cards[player ^ 1];
@@ -92,7 +148,7 @@ impl PostFlopGame {
             }
         }
         // showdown (optimized for no rake; 2-pass)
-        else if rake == 0.0 {
+        else if tie == 0.0 {
             let pair_index = card_pair_to_index(node.turn, node.river);
             let hand_strength = &self.hand_strength[pair_index];
             let player_strength = &hand_strength[player];
@@ -151,7 +207,7 @@ impl PostFlopGame {
         }
         // showdown (raked; 3-pass)
         else {
-            let amount_tie = -0.5 * rake / self.num_combinations;
+            let amount_tie = tie / self.num_combinations;
             let same_hand_index = &self.same_hand_index[player];
 
             let pair_index = card_pair_to_index(node.turn, node.river);
@@ -259,12 +315,10 @@ impl PostFlopGame {
         player: usize,
         cfreach: &[f32],
     ) {
-        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
-        let half_pot = 0.5 * pot;
-        let rake = min(pot * self.tree_config.rake_rate, self.tree_config.rake_cap);
-        let amount_win = ((half_pot - rake) / self.bunching_num_combinations) as f32;
-        let amount_lose = (-half_pot / self.bunching_num_combinations) as f32;
-        let amount_tie = (-0.5 * rake / self.bunching_num_combinations) as f32;
+        let [win, lose, tie] = self.terminal_amounts(node, player);
+        let amount_win = (win / self.bunching_num_combinations) as f32;
+        let amount_lose = (lose / self.bunching_num_combinations) as f32;
+        let amount_tie = (tie / self.bunching_num_combinations) as f32;
         let opponent_len = self.private_cards[player ^ 1].len();
 
         // someone folded
@@ -3,13 +3,6 @@ use crate::interface::*;
 use crate::sliceop::*;
 use crate::utility::*;
 
-/// Decodes the encoded `i16` slice to the `f32` slice.
-#[inline]
-fn decode_signed_slice(slice: &[i16], scale: f32) -> Vec<f32> {
-    let decoder = scale / i16::MAX as f32;
-    slice.iter().map(|&x| x as f32 * decoder).collect()
-}
-
 impl PostFlopGame {
     #[inline]
     pub fn cloned_history(&self) -> Vec<usize> {
@@ -142,7 +135,7 @@ impl PostFlopGame {
 
         let flop = self.card_config.flop;
         let mut board_mask: u64 = (1 << flop[0]) | (1 << flop[1]) | (1 << flop[2]);
-        let mut dead_mask: u64 = 0;
+        let mut dead_mask: u64 = self.card_config.deck.removed_cards_mask();
 
         // no bunching
         if self.bunching_num_dead_cards == 0 {
@@ -641,18 +634,21 @@ impl PostFlopGame {
 
         let tmp = if self.bunching_num_dead_cards == 0 {
             let mut tmp = vec![0.0; num_hands];
+            let num_river = (self.card_config.deck.num_cards() - 8) as f64;
+            let num_turn = num_river + 1.0;
             if self.river != NOT_DEALT {
                 self.equity_internal(&mut tmp, player, self.turn, self.river, 0.5);
             } else if self.turn != NOT_DEALT {
                 for river in 0..52 {
                     if self.turn != river {
-                        self.equity_internal(&mut tmp, player, self.turn, river, 0.5 / 44.0);
+                        self.equity_internal(&mut tmp, player, self.turn, river, 0.5 / num_river);
                     }
                 }
             } else {
                 for turn in 0..52 {
                     for river in turn + 1..52 {
-                        self.equity_internal(&mut tmp, player, turn, river, 1.0 / (45.0 * 44.0));
+                        let amount = 1.0 / (num_turn * num_river);
+                        self.equity_internal(&mut tmp, player, turn, river, amount);
                     }
                 }
             }
@@ -678,6 +674,9 @@ impl PostFlopGame {
 
     /// Returns the expected values of each private hand of the given player.
     ///
+    /// If a terminal payoff is set, the expected values are the dollar equity of the player after
+    /// the hand (see [`set_terminal_payoff`]).
+    ///
     /// Panics if the game is not solved.
     ///
     /// After mutating the current node, you must call the [`cache_normalized_weights`] method
@@ -685,6 +684,7 @@ impl PostFlopGame {
     ///
     /// **Time complexity:** see [`expected_values_detail`].
     ///
+    /// [`set_terminal_payoff`]: #method.set_terminal_payoff
     /// [`cache_normalized_weights`]: #method.cache_normalized_weights
     /// [`expected_values_detail`]: #method.expected_values_detail
     pub fn expected_values(&self, player: usize) -> Vec<f32> {
@@ -753,12 +753,13 @@ impl PostFlopGame {
         let node = self.node();
         let num_hands = self.num_private_hands(player);
 
+        let num_cards = self.card_config.deck.num_cards();
         let mut chance_factor = 1;
         if self.card_config.turn == NOT_DEALT && self.turn != NOT_DEALT {
-            chance_factor *= 45 - self.bunching_num_dead_cards;
+            chance_factor *= num_cards - 7 - self.bunching_num_dead_cards;
         }
         if self.card_config.river == NOT_DEALT && self.river != NOT_DEALT {
-            chance_factor *= 44 - self.bunching_num_dead_cards;
+            chance_factor *= num_cards - 8 - self.bunching_num_dead_cards;
         }
 
         let num_combinations = match self.bunching_num_dead_cards {
@@ -810,10 +811,18 @@ impl PostFlopGame {
         let total_bet_amount = self.total_bet_amount();
         let bias = (total_bet_amount[player] - total_bet_amount[player ^ 1]).max(0);
 
+        // with a terminal payoff, the values are the dollar equity after the hand
+        let offset = match &self.terminal_payoff {
+            None => starting_pot as f32 * 0.5 + (self.node().amount + bias) as f32,
+            Some(payoff) => payoff.base_equity(player, starting_pot as f64) as f32,
+        };
+
         ret.chunks_exact_mut(num_hands)
             .enumerate()
             .for_each(|(action, row)| {
-                let is_fold = have_actions && self.node().play(action).prev_action == Action::Fold;
+                let is_fold = have_actions
+                    && self.terminal_payoff.is_none()
+                    && self.node().play(action).prev_action == Action::Fold;
                 self.apply_swap(row, player, false);
                 row.iter_mut()
                     .zip(self.weights[player].iter())
@@ -823,7 +832,7 @@ impl PostFlopGame {
                             *v = 0.0;
                         } else {
                             *v *= normalizer * (w_raw / w_normalized);
-                            *v += starting_pot as f32 * 0.5 + (self.node().amount + bias) as f32;
+                            *v += offset;
                         }
                     });
             });
@@ -884,6 +893,50 @@ impl PostFlopGame {
         self.total_bet_amount
     }
 
+    /// Returns the stack of each player (OOP, IP) behind at the current node.
+    ///
+    /// The stacks are computed from [`TreeConfig::stacks`], so the deeper player's stack can be
+    /// larger than the effective stack.
+    #[inline]
+    pub fn current_stacks(&self) -> [i32; 2] {
+        let stacks = self.tree_config.player_stacks();
+        [
+            stacks[0] - self.total_bet_amount[0],
+            stacks[1] - self.total_bet_amount[1],
+        ]
+    }
+
+    /// Returns the pot size at the current node, including the dead money and the bets that have
+    /// not been called yet.
+    #[inline]
+    pub fn current_pot(&self) -> i32 {
+        self.tree_config.starting_pot + self.total_bet_amount[0] + self.total_bet_amount[1]
+    }
+
+    /// Returns the size of the bet action at the current node relative to the pot, or `None` if
+    /// `action` is not a bet action.
+    ///
+    /// The ratio follows the same convention as [`BetSize::PotRelative`]: the amount added to the
+    /// previous bet divided by the pot after calling it. Therefore, a bet or raise created from a
+    /// "75%" bet size returns approximately `0.75`.
+    ///
+    /// [`BetSize::PotRelative`]: crate::BetSize::PotRelative
+    pub fn bet_pot_ratio(&self, action: Action) -> Option<f64> {
+        let amount = match action {
+            Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => amount,
+            _ => return None,
+        };
+
+        let prev_amount = match self.node().prev_action {
+            Action::Bet(a) | Action::Raise(a) | Action::AllIn(a) => a,
+            _ => 0,
+        };
+
+        let [oop_amount, ip_amount] = self.total_bet_amount;
+        let pot = self.current_pot() + (oop_amount - ip_amount).abs();
+        Some((amount - prev_amount) as f64 / pot as f64)
+    }
+
     /// Locks the strategy of the current node.
     ///
     /// The `strategy` argument must be a slice of the length of `#(actions) * #(private hands)`.
@@ -899,22 +952,39 @@ impl PostFlopGame {
     /// This method must be called after allocating memory and before solving the game.
     /// Panics if the memory is not yet allocated or the game is already solved.
     /// Also, panics if the current node is a terminal node or a chance node.
+    /// See [`try_lock_current_strategy`] for a non-panicking version.
+    ///
+    /// [`try_lock_current_strategy`]: #method.try_lock_current_strategy
     pub fn lock_current_strategy(&mut self, strategy: &[f32]) {
+        if let Err(e) = self.try_lock_current_strategy(strategy) {
+            panic!("{e}");
+        }
+    }
+
+    /// Locks the strategy of the current node, returning [`Error::LockedNode`] instead of
+    /// panicking if the strategy cannot be locked.
+    ///
+    /// The requirements on `strategy` and the game state are the same as
+    /// [`lock_current_strategy`].
+    ///
+    /// [`lock_current_strategy`]: #method.lock_current_strategy
+    pub fn try_lock_current_strategy(&mut self, strategy: &[f32]) -> Result<(), Error> {
         if self.state < State::MemoryAllocated {
-            panic!("Memory is not allocated");
+            return Err(Error::LockedNode("Memory is not allocated".to_string()));
         }
 
         if self.state == State::Solved {
-            panic!("Game is already solved");
+            return Err(Error::LockedNode("Game is already solved".to_string()));
         }
 
         if self.is_terminal_node() {
-            panic!("Terminal node is not allowed");
+            return Err(Error::LockedNode(
+                "Terminal node is not allowed".to_string(),
+            ));
         }
 
         if self.is_chance_node() {
-            println!("lock_current_strategy() - Chance node is not allowed");
-            panic!("Chance node is not allowed");
+            return Err(Error::LockedNode("Chance node is not allowed".to_string()));
         }
 
         let mut node = self.node();
@@ -923,7 +993,11 @@ impl PostFlopGame {
         let num_hands = self.num_private_hands(player);
 
         if strategy.len() != num_actions * num_hands {
-            panic!("Invalid strategy length");
+            return Err(Error::LockedNode(format!(
+                "Invalid strategy length: expected {}, got {}",
+                num_actions * num_hands,
+                strategy.len()
+            )));
         }
 
         let mut locking = vec![-1.0; num_actions * num_hands];
@@ -955,6 +1029,8 @@ impl PostFlopGame {
         node.is_locked = true;
         let index = self.node_index(&node);
         self.locking_strategy.insert(index, locking);
+
+        Ok(())
     }
 
     /// Unlocks the strategy of the current node.
@@ -1029,7 +1105,7 @@ impl PostFlopGame {
 
     /// Returns the reference to the current node.
     #[inline]
-    fn node(&self) -> MutexGuardLike<PostFlopNode> {
+    pub(super) fn node(&self) -> MutexGuardLike<PostFlopNode> {
         self.node_arena[self.node_history.last().cloned().unwrap_or(0)].lock()
     }
 
@@ -1,16 +1,29 @@
 mod base;
+mod estimate;
 mod evaluation;
 mod interpreter;
 mod node;
+mod subgame;
+mod warm_start;
 
 #[cfg(feature = "bincode")]
 mod serialization;
 
+pub use estimate::*;
+
+#[cfg(feature = "bincode")]
+pub(crate) use serialization::{check_version_str, Checkpoint, CheckpointRef};
+
+#[cfg(all(test, feature = "bincode"))]
+pub(crate) use serialization::VERSION_STR;
+
 #[cfg(test)]
 mod tests;
 
 use crate::action_tree::*;
 use crate::card::*;
+use crate::error::*;
+use crate::icm::*;
 use crate::mutex_like::*;
 use std::collections::BTreeMap;
 
@@ -79,6 +92,10 @@ pub struct PostFlopGame {
     bunching_coef_flop: [Vec<usize>; 2],
     bunching_coef_turn: [Vec<Vec<usize>>; 2],
 
+    // terminal payoff (linear in chips if `None`) and its payoffs of each player at each amount
+    terminal_payoff: Option<TerminalPayoff>,
+    terminal_payoff_table: Vec<(i32, [[f64; 3]; 2])>,
+
     // store options
     storage_mode: BoardState,
     target_storage_mode: BoardState,
@@ -97,8 +114,14 @@ pub struct PostFlopGame {
     storage2: Vec<u8>,
     storage_ip: Vec<u8>,
     storage_chance: Vec<u8>,
+    storage_prediction: Vec<u8>,
     locking_strategy: BTreeMap<usize, Vec<f32>>,
 
+    // re-solving gadget
+    gadget_player: Option<usize>,
+    gadget_values: Vec<f32>,
+    gadget_regrets: MutexLike<Vec<f32>>,
+
     // result interpreter
     action_history: Vec<usize>,
     node_history: Vec<usize>,
@@ -136,6 +159,7 @@ pub struct PostFlopNode {
     storage1: *mut u8, // strategy
     storage2: *mut u8, // regrets or cfvalues
     storage3: *mut u8, // IP cfvalues
+    storage4: *mut u8, // predicted regrets (Predictive CFR+ only)
 }
 
 unsafe impl Send for PostFlopNode {}
@@ -30,13 +30,17 @@ impl PostFlopGame {
 
     /// Sets the target storage mode.
     #[inline]
-    pub fn set_target_storage_mode(&mut self, mode: BoardState) -> Result<(), String> {
+    pub fn set_target_storage_mode(&mut self, mode: BoardState) -> Result<(), Error> {
         if mode > self.storage_mode {
-            return Err("Cannot set target to a higher value than the current storage".to_string());
+            return Err(Error::InvalidState(
+                "Cannot set target to a higher value than the current storage".to_string(),
+            ));
         }
 
         if mode < self.tree_config.initial_state {
-            return Err("Cannot set target to a lower value than the initial state".to_string());
+            return Err(Error::InvalidState(
+                "Cannot set target to a lower value than the initial state".to_string(),
+            ));
         }
 
         self.target_storage_mode = mode;
@@ -100,7 +104,18 @@ impl PostFlopGame {
     }
 }
 
-static VERSION_STR: &str = "2023-03-19";
+pub(crate) static VERSION_STR: &str = "2026-10-17";
+
+/// Checks the version string at the beginning of the encoded game data.
+pub(crate) fn check_version_str(version: &str) -> Result<(), Error> {
+    if version != VERSION_STR {
+        return Err(Error::VersionMismatch {
+            found: version.to_string(),
+            expected: VERSION_STR.to_string(),
+        });
+    }
+    Ok(())
+}
 
 thread_local! {
     static PTR_BASE: Cell<[*const u8; 2]> = Cell::new([ptr::null(); 2]);
@@ -115,6 +130,10 @@ impl Encode for PostFlopGame {
             return Err(EncodeError::Other("Game is not successfully initialized"));
         }
 
+        if self.terminal_payoff.is_some() {
+            return Err(EncodeError::Other("Terminal payoff cannot be saved"));
+        }
+
         let num_storage = self.num_target_storage();
 
         // version
@@ -178,11 +197,7 @@ impl Decode<()> for PostFlopGame {
     fn decode<D: Decoder<Context = ()>>(decoder: &mut D) -> Result<Self, DecodeError> {
         // version check
         let version = String::decode(decoder)?;
-        if version != VERSION_STR {
-            return Err(DecodeError::OtherString(format!(
-                "Version mismatch: expected '{VERSION_STR}', but got '{version}'"
-            )));
-        }
+        check_version_str(&version).map_err(|e| DecodeError::OtherString(e.to_string()))?;
 
         // game instance
         let mut game = Self {
@@ -240,7 +255,8 @@ impl Decode<()> for PostFlopGame {
         game.node_arena = Decode::decode(decoder)?;
 
         // initialization
-        game.check_card_config().map_err(DecodeError::OtherString)?;
+        game.check_card_config()
+            .map_err(|e| DecodeError::OtherString(e.to_string()))?;
         game.init_card_fields();
         game.init_interpreter();
         game.back_to_root();
@@ -334,3 +350,111 @@ impl Decode<()> for PostFlopNode {
         Ok(node)
     }
 }
+
+/// A reference to a game in the middle of solving, used to save a checkpoint.
+pub(crate) struct CheckpointRef<'a> {
+    pub game: &'a PostFlopGame,
+    pub current_iteration: u32,
+}
+
+/// A game in the middle of solving, restored from a checkpoint.
+pub(crate) struct Checkpoint {
+    pub game: PostFlopGame,
+    pub current_iteration: u32,
+}
+
+impl PostFlopGame {
+    /// Checks whether the game can be saved as a checkpoint.
+    pub(crate) fn check_checkpoint(&self) -> Result<(), Error> {
+        if self.state != State::MemoryAllocated {
+            return Err(Error::InvalidState(
+                "Game is not in the middle of solving".to_string(),
+            ));
+        }
+
+        if self.storage_mode != BoardState::River || self.target_storage_mode != BoardState::River {
+            return Err(Error::InvalidState(
+                "Checkpoint requires the full storage mode".to_string(),
+            ));
+        }
+
+        if self.terminal_payoff.is_some() {
+            return Err(Error::InvalidState(
+                "Game with a terminal payoff cannot be saved as a checkpoint".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Encode for CheckpointRef<'a> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.game
+            .check_checkpoint()
+            .map_err(|_| EncodeError::Other("Game cannot be saved as a checkpoint"))?;
+
+        // the game comes first so that the data starts with the version string
+        self.game.encode(encoder)?;
+        self.current_iteration.encode(encoder)?;
+
+        // `PostFlopGame` itself does not store the cumulative regrets
+        self.game.storage2.encode(encoder)?;
+        self.game.storage_prediction.encode(encoder)?;
+
+        // re-solving gadget of a subgame
+        self.game.gadget_player.encode(encoder)?;
+        self.game.gadget_values.encode(encoder)?;
+        self.game.gadget_regrets.lock().encode(encoder)?;
+
+        Ok(())
+    }
+}
+
+impl Decode<()> for Checkpoint {
+    fn decode<D: Decoder<Context = ()>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut game = PostFlopGame::decode(decoder)?;
+        let current_iteration = u32::decode(decoder)?;
+
+        game.check_checkpoint()
+            .map_err(|e| DecodeError::OtherString(e.to_string()))?;
+
+        // the storage is already allocated by `PostFlopGame::decode` (do not reallocate, since
+        // nodes hold pointers into it)
+        let storage2 = Vec::<u8>::decode(decoder)?;
+        if storage2.len() != game.storage2.len() {
+            return Err(DecodeError::Other("Regret storage size mismatch"));
+        }
+        game.storage2.copy_from_slice(&storage2);
+
+        let storage_prediction = Vec::<u8>::decode(decoder)?;
+        if !storage_prediction.is_empty() {
+            if game.is_compression_enabled || storage_prediction.len() != game.storage2.len() {
+                return Err(DecodeError::Other("Prediction storage size mismatch"));
+            }
+            game.allocate_prediction_memory();
+            game.storage_prediction.copy_from_slice(&storage_prediction);
+        }
+
+        let gadget_player = Option::<usize>::decode(decoder)?;
+        let gadget_values = Vec::<f32>::decode(decoder)?;
+        let gadget_regrets = Vec::<f32>::decode(decoder)?;
+        if let Some(player) = gadget_player {
+            let num_hands = match player {
+                0 | 1 => game.num_private_hands(player),
+                _ => return Err(DecodeError::Other("Invalid gadget player")),
+            };
+            if gadget_values.len() != num_hands || gadget_regrets.len() != 2 * num_hands {
+                return Err(DecodeError::Other("Gadget size mismatch"));
+            }
+            game.gadget_player = gadget_player;
+            game.gadget_values = gadget_values;
+            game.gadget_regrets = MutexLike::new(gadget_regrets);
+        }
+
+        Ok(Self {
+            game,
+            current_iteration,
+        })
+    }
+}
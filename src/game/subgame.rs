@@ -0,0 +1,220 @@
+use super::*;
+use crate::range::*;
+
+impl PostFlopGame {
+    /// Returns the tree configuration of the subgame rooted at the current node.
+    ///
+    /// The initial state, the starting pot, the effective stack, and the stacks are set according
+    /// to the first node of the current street, and the other fields (e.g., bet sizes) are copied
+    /// from the current configuration. Modify the bet sizes as needed and pass the resulting
+    /// [`ActionTree`] to [`create_subgame`].
+    ///
+    /// [`create_subgame`]: #method.create_subgame
+    pub fn subgame_tree_config(&self) -> Result<TreeConfig, Error> {
+        self.check_subgame_root()?;
+
+        let (amount, _) = self.current_street_line();
+        let initial_state = match self.current_board().len() {
+            3 => BoardState::Flop,
+            4 => BoardState::Turn,
+            _ => BoardState::River,
+        };
+
+        let stacks = match self.tree_config.stacks {
+            [0, 0] => [0, 0],
+            stacks => stacks.map(|stack| stack - amount),
+        };
+
+        Ok(TreeConfig {
+            initial_state,
+            starting_pot: self.tree_config.starting_pot + 2 * amount,
+            effective_stack: self.tree_config.effective_stack - amount,
+            stacks,
+            ..self.tree_config.clone()
+        })
+    }
+
+    /// Creates a subgame rooted at the current node for safe re-solving.
+    ///
+    /// The ranges of the subgame are the reach probabilities of the current node (i.e.,
+    /// [`weights`]). The opponent of `player` is given the option to terminate at the root of the
+    /// subgame and receive the expected value of the current (solved) strategy, so that `player`
+    /// cannot be exploited more than in the original game by re-solving (re-solving gadget).
+    /// The subgame is solved by the usual functions such as [`solve`], and its action tree may use
+    /// a finer action abstraction than the original game.
+    ///
+    /// The configuration of `action_tree` must be consistent with [`subgame_tree_config`], so the
+    /// root of the subgame is the first node of the current street. If the current node is in the
+    /// middle of a street, the actions taken on the street before the current node are forced
+    /// (i.e., the other actions are removed from `action_tree`), and the current node of the
+    /// subgame is reached by playing them from the root. Bunching effect is not supported. The
+    /// gadget is not saved by serialization.
+    ///
+    /// [`weights`]: #method.weights
+    /// [`solve`]: crate::solve
+    /// [`subgame_tree_config`]: #method.subgame_tree_config
+    pub fn create_subgame(
+        &mut self,
+        mut action_tree: ActionTree,
+        player: usize,
+    ) -> Result<PostFlopGame, Error> {
+        if self.state != State::Solved {
+            return Err(Error::InvalidState("Game is not solved".to_string()));
+        }
+
+        if self.bunching_num_dead_cards != 0 {
+            return Err(Error::InvalidState(
+                "Bunching effect is not supported".to_string(),
+            ));
+        }
+
+        if player > 1 {
+            return Err(Error::InvalidConfig(format!("Invalid player: {player}")));
+        }
+
+        let expected_config = self.subgame_tree_config()?;
+        let config = action_tree.config();
+        if config.initial_state != expected_config.initial_state
+            || config.starting_pot != expected_config.starting_pot
+            || config.effective_stack != expected_config.effective_stack
+            || config.player_stacks() != expected_config.player_stacks()
+            || config.dead_money != expected_config.dead_money
+            || config.rake_rate != expected_config.rake_rate
+            || config.rake_cap != expected_config.rake_cap
+        {
+            return Err(Error::InvalidConfig(
+                "Tree configuration does not match the current node".to_string(),
+            ));
+        }
+
+        // force the actions taken on the current street before the current node
+        let (amount, line) = self.current_street_line();
+        for (i, &action) in line.iter().enumerate() {
+            action_tree.apply_history(&line[..i])?;
+            if !action_tree.available_actions().contains(&action) {
+                action_tree.add_line(&line[..=i])?;
+            }
+            let other_actions = action_tree
+                .available_actions()
+                .iter()
+                .filter(|&&a| a != action)
+                .cloned()
+                .collect::<Vec<_>>();
+            for other_action in other_actions {
+                let mut other_line = line[..i].to_vec();
+                other_line.push(other_action);
+                action_tree.remove_line(&other_line)?;
+            }
+        }
+        action_tree.back_to_root();
+
+        let board = self.current_board();
+        let card_config = CardConfig {
+            range: [
+                Range::from_hands_weights(&self.private_cards[0], &self.weights[0])?,
+                Range::from_hands_weights(&self.private_cards[1], &self.weights[1])?,
+            ],
+            flop: self.card_config.flop,
+            turn: board.get(3).cloned().unwrap_or(NOT_DEALT),
+            river: board.get(4).cloned().unwrap_or(NOT_DEALT),
+            deck: self.card_config.deck,
+        };
+
+        // expected values of the opponent in the original game
+        let opponent = player ^ 1;
+        self.cache_normalized_weights();
+        let expected_values = self.expected_values(opponent);
+        let mut expected_values_by_cards = vec![0.0; NUM_CARD_PAIRS];
+        for (&(c1, c2), &ev) in self.private_cards[opponent].iter().zip(&expected_values) {
+            expected_values_by_cards[card_pair_to_index(c1, c2)] = ev;
+        }
+
+        // the counterfactual values are the same in the original game and the subgame, so the
+        // offset of the expected values at the current node is removed
+        let starting_pot = self.tree_config.starting_pot;
+        let offset = match &self.terminal_payoff {
+            None => {
+                let bias = (self.total_bet_amount[opponent] - self.total_bet_amount[player]).max(0);
+                starting_pot as f64 * 0.5 + (self.node().amount + bias) as f64
+            }
+            Some(payoff) => payoff.base_equity(opponent, starting_pot as f64),
+        };
+
+        let mut subgame = PostFlopGame::with_config(card_config, action_tree)?;
+
+        // the stacks of the subgame root are reduced by the amount already bet
+        let amount = amount as f64;
+        subgame.terminal_payoff = self.terminal_payoff.as_ref().map(|p| p.after_bets(amount));
+        subgame.init_terminal_payoff_table();
+        let player_weights = &subgame.initial_weights[player];
+        let mut weight_sum = 0.0;
+        let mut weight_sum_minus = [0.0; 52];
+        for (&(c1, c2), &w) in subgame.private_cards[player].iter().zip(player_weights) {
+            weight_sum += w as f64;
+            weight_sum_minus[c1 as usize] += w as f64;
+            weight_sum_minus[c2 as usize] += w as f64;
+        }
+
+        let gadget_values = subgame.private_cards[opponent]
+            .iter()
+            .enumerate()
+            .map(|(i, &(c1, c2))| {
+                let same_i = subgame.same_hand_index[opponent][i];
+                let weight_same = if same_i == u16::MAX {
+                    0.0
+                } else {
+                    player_weights[same_i as usize] as f64
+                };
+                let weight = weight_sum + weight_same
+                    - weight_sum_minus[c1 as usize]
+                    - weight_sum_minus[c2 as usize];
+                let ev = expected_values_by_cards[card_pair_to_index(c1, c2)] as f64;
+                ((ev - offset) * weight / subgame.num_combinations) as f32
+            })
+            .collect::<Vec<_>>();
+
+        subgame.gadget_player = Some(opponent);
+        subgame.gadget_regrets = MutexLike::new(vec![0.0; 2 * gadget_values.len()]);
+        subgame.gadget_values = gadget_values;
+
+        Ok(subgame)
+    }
+
+    /// Checks whether the current node can be the root of a subgame.
+    fn check_subgame_root(&self) -> Result<(), Error> {
+        if self.state < State::MemoryAllocated {
+            return Err(Error::InvalidState("Memory is not allocated".to_string()));
+        }
+
+        if self.is_terminal_node() || self.is_chance_node() {
+            return Err(Error::InvalidState(
+                "Current node must be a player node".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount at the first node of the current street and the actions taken on the
+    /// current street before the current node.
+    fn current_street_line(&self) -> (i32, Vec<Action>) {
+        let street_start = self.node_history.iter().rposition(|&index| {
+            matches!(self.node_arena[index].lock().prev_action, Action::Chance(_))
+        });
+
+        let (amount, street_nodes) = match street_start {
+            Some(i) => (
+                self.node_arena[self.node_history[i]].lock().amount,
+                &self.node_history[i + 1..],
+            ),
+            None => (self.node_arena[0].lock().amount, &self.node_history[..]),
+        };
+
+        let line = street_nodes
+            .iter()
+            .map(|&index| self.node_arena[index].lock().prev_action)
+            .collect();
+
+        (amount, line)
+    }
+}
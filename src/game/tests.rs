@@ -1,4 +1,6 @@
 use super::*;
+use crate::bet_size::*;
+use crate::interface::*;
 use crate::range::*;
 use crate::solver::*;
 use crate::utility::*;
@@ -350,6 +352,7 @@ fn one_raise_all_range_with_river() {
         flop: flop_from_str("Td9d6h").unwrap(),
         turn: card_from_str("Qc").unwrap(),
         river: card_from_str("7s").unwrap(),
+        deck: DeckType::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -435,6 +438,364 @@ fn one_raise_all_range_with_river() {
     assert!((ev_ip - 0.0).abs() < 1e-4);
 }
 
+#[test]
+fn solve_with_algorithms() {
+    fn river_game() -> PostFlopGame {
+        let card_config = CardConfig {
+            range: [
+                "TT+,AKs,AQo,76s".parse().unwrap(),
+                "99+,AJs+,KQs".parse().unwrap(),
+            ],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            river: card_from_str("7s").unwrap(),
+            deck: DeckType::Standard,
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: BoardState::River,
+            starting_pot: 60,
+            effective_stack: 300,
+            river_bet_sizes: [("50%, a", "60%").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        PostFlopGame::with_config(card_config, action_tree).unwrap()
+    }
+
+    let target = 60.0 * 0.005;
+    let algorithms: [&dyn CfrAlgorithm; 5] = [
+        &VanillaCfr,
+        &CfrPlus,
+        &LinearCfr,
+        &DiscountedCfr::default(),
+        &PredictiveCfrPlus,
+    ];
+
+    for algorithm in algorithms {
+        let mut game = river_game();
+        game.allocate_memory(false);
+        if algorithm.is_predictive() {
+            game.allocate_prediction_memory();
+        }
+        let exploitability = solve_with(&mut game, algorithm, 2000, target, false);
+        assert!(exploitability <= target);
+        assert!(!game.is_prediction_enabled());
+    }
+
+    // the default parameters reproduce the original implementation
+    let dcfr = DiscountedCfr::default();
+    for t in [0, 1, 2, 3, 4, 5, 15, 16, 17, 100, 1000] {
+        let params = dcfr.discount_params(t);
+        let t_alpha = (t as i32 - 1).max(0) as f64;
+        let pow_alpha = t_alpha * t_alpha.sqrt();
+        assert!((params.alpha_t - (pow_alpha / (pow_alpha + 1.0)) as f32).abs() < 1e-6);
+        assert_eq!(params.beta_t, 0.5);
+    }
+    assert_eq!(dcfr.discount_params(16).gamma_t, 0.0);
+}
+
+#[test]
+fn solve_with_callback_stop() {
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("7s").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 970,
+        river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    let mut events = Vec::new();
+    let exploitability =
+        solve_with_callback(&mut game, &DiscountedCfr::default(), 100, 0.0, |_, p| {
+            events.push(*p);
+            if p.iteration == 5 {
+                SolveControl::Stop
+            } else {
+                SolveControl::Continue
+            }
+        });
+
+    assert!(game.is_solved());
+    assert!(exploitability.is_finite());
+    assert_eq!(events.len(), 6);
+    assert!(events
+        .iter()
+        .enumerate()
+        .all(|(i, p)| p.iteration == i as u32));
+    assert!(events[0].exploitability.is_some());
+    assert!(events[1..].iter().all(|p| p.exploitability.is_none()));
+    assert!(events.iter().all(|p| p.memory_usage > 0));
+}
+
+#[test]
+fn warm_start() {
+    let oop_range = "TT+,AKs,AQo,76s,55";
+    let ip_range = "99+,AJs+,KQs,87s";
+    let card_config = CardConfig {
+        range: [oop_range.parse().unwrap(), ip_range.parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("7s").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 300,
+        river_bet_sizes: [("50%", "60%").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut source = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    source.allocate_memory(false);
+    let source_exploitability = solve(&mut source, 1000, 0.05, false);
+
+    // same configuration
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    game.allocate_memory(true);
+    game.warm_start_from(&source).unwrap();
+    assert!((compute_exploitability(&game) - source_exploitability).abs() < 0.01);
+
+    // halving the IP range keeps the counterfactual values of OOP hands and doubles those of IP
+    // hands, so the seeded regrets must be scaled accordingly
+    let half_config = CardConfig {
+        range: [
+            oop_range.parse().unwrap(),
+            ip_range.parse::<Range>().unwrap().scale(0.5).unwrap(),
+        ],
+        ..card_config.clone()
+    };
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut same = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    same.allocate_memory(false);
+    same.warm_start_from(&source).unwrap();
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut half = PostFlopGame::with_config(half_config, action_tree).unwrap();
+    half.allocate_memory(false);
+    half.warm_start_from(&source).unwrap();
+
+    let (same_root, half_root) = (same.root(), half.root());
+    for (x, y) in same_root.regrets().iter().zip(half_root.regrets()) {
+        assert!((x - y).abs() <= 1e-4 * x.abs().max(1.0));
+    }
+    let (same_ip, half_ip) = (same_root.play(1), half_root.play(1));
+    assert!(same_ip.regrets().iter().any(|&x| x.abs() > 1e-3));
+    for (x, y) in same_ip.regrets().iter().zip(half_ip.regrets()) {
+        assert!((2.0 * x - y).abs() <= 1e-4 * x.abs().max(1.0));
+    }
+
+    // different range and additional bet size
+    let card_config = CardConfig {
+        range: [
+            oop_range.parse().unwrap(),
+            "99+,AJs+,KQs,87s,65s".parse().unwrap(),
+        ],
+        ..card_config
+    };
+    let tree_config = TreeConfig {
+        river_bet_sizes: [("50%, 100%", "60%").try_into().unwrap(), Default::default()],
+        ..tree_config
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    let cold_exploitability = compute_exploitability(&game);
+    game.warm_start_from(&source).unwrap();
+    assert!(compute_exploitability(&game) < cold_exploitability);
+
+    let exploitability = resume_with_callback(
+        &mut game,
+        &DiscountedCfr::default(),
+        16,
+        1000,
+        0.05,
+        |_, _| SolveControl::Continue,
+    );
+    assert!(exploitability <= 0.05);
+
+    // board mismatch
+    let card_config = CardConfig {
+        range: [oop_range.parse().unwrap(), ip_range.parse().unwrap()],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        deck: DeckType::Standard,
+    };
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 300,
+        ..Default::default()
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    assert!(game.warm_start_from(&source).is_err());
+
+    // isomorphism mismatch: the river cards of clubs, hearts, and spades are isomorphic in the
+    // source, but KhQh breaks the symmetry of hearts
+    let card_config = CardConfig {
+        range: ["TT+,AKs".parse().unwrap(), "99+,AQs".parse().unwrap()],
+        flop: flop_from_str("Td9d6d").unwrap(),
+        turn: card_from_str("Qd").unwrap(),
+        ..Default::default()
+    };
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 300,
+        ..Default::default()
+    };
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut source = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    source.allocate_memory(false);
+    solve(&mut source, 10, 0.0, false);
+
+    let asymmetric_config = CardConfig {
+        range: ["TT+,AKs,KhQh".parse().unwrap(), "99+,AQs".parse().unwrap()],
+        ..card_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(asymmetric_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    assert!(matches!(
+        game.warm_start_from(&source),
+        Err(Error::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn subgame() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AKs,AQo,76s,55".parse().unwrap(),
+            "99+,AJs+,KQs,87s".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: NOT_DEALT,
+        deck: DeckType::Standard,
+    };
+
+    let bet_sizes: BetSizeOptions = ("50%", "60%").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 60,
+        effective_stack: 300,
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 1000, 0.1, false);
+
+    // middle of a street (OOP check - IP bet)
+    game.play(0);
+    game.play(1);
+    let tree_config = game.subgame_tree_config().unwrap();
+    assert_eq!(tree_config.initial_state, BoardState::Turn);
+    assert_eq!(tree_config.starting_pot, 60);
+    assert_eq!(tree_config.effective_stack, 300);
+
+    // OOP may raise 150% in the subgame
+    let tree_config = TreeConfig {
+        turn_bet_sizes: [
+            ("50%", "60%, 150%").try_into().unwrap(),
+            ("40%", "60%").try_into().unwrap(),
+        ],
+        ..tree_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut subgame = game.create_subgame(action_tree, 0).unwrap();
+    subgame.allocate_memory(false);
+    assert_eq!(subgame.available_actions(), vec![Action::Check]);
+    subgame.play(0);
+    assert_eq!(subgame.available_actions(), vec![Action::Bet(30)]);
+    subgame.play(0);
+    assert_eq!(subgame.total_bet_amount(), game.total_bet_amount());
+    assert_eq!(
+        subgame.available_actions(),
+        vec![
+            Action::Fold,
+            Action::Call,
+            Action::Raise(102),
+            Action::Raise(210)
+        ]
+    );
+    let weight_sum = |w: &[f32]| w.iter().sum::<f32>();
+    assert!((weight_sum(subgame.weights(0)) - weight_sum(game.weights(0))).abs() < 1e-4);
+    assert!((weight_sum(subgame.weights(1)) - weight_sum(game.weights(1))).abs() < 1e-4);
+
+    // OOP is not worse off than in the original game
+    game.cache_normalized_weights();
+    let ev_oop = compute_average(&game.expected_values(0), game.normalized_weights(0));
+    subgame.back_to_root();
+    solve(&mut subgame, 1000, 0.1, false);
+    subgame.apply_history(&[0, 0]);
+    subgame.cache_normalized_weights();
+    let subgame_ev_oop =
+        compute_average(&subgame.expected_values(0), subgame.normalized_weights(0));
+    assert!(subgame_ev_oop >= ev_oop - 0.1);
+
+    // bet - call - river
+    game.back_to_root();
+    game.play(1);
+    game.play(1);
+    let river = game.possible_cards().trailing_zeros() as u8;
+    game.play(river as usize);
+
+    let tree_config = game.subgame_tree_config().unwrap();
+    assert_eq!(tree_config.initial_state, BoardState::River);
+    assert_eq!(tree_config.starting_pot, 120);
+    assert_eq!(tree_config.effective_stack, 270);
+
+    // mismatched configuration
+    let action_tree = ActionTree::new(TreeConfig {
+        starting_pot: 60,
+        ..tree_config.clone()
+    })
+    .unwrap();
+    assert!(game.create_subgame(action_tree, 0).is_err());
+
+    // finer action abstraction
+    let tree_config = TreeConfig {
+        river_bet_sizes: [
+            ("33%, 75%", "60%, 100%").try_into().unwrap(),
+            Default::default(),
+        ],
+        ..tree_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut subgame = game.create_subgame(action_tree, 0).unwrap();
+    assert_eq!(subgame.card_config().river, river);
+    assert!((weight_sum(subgame.weights(1)) - weight_sum(game.weights(1))).abs() < 1e-4);
+
+    subgame.allocate_memory(false);
+    let exploitability = solve(&mut subgame, 1000, 0.1, false);
+    assert!(exploitability <= 0.1);
+}
+
 #[test]
 fn always_win() {
     // be careful for straight flushes
@@ -847,6 +1208,76 @@ fn isomorphism_monotone() {
     check(&[0, 0, 7, 0, 0, 11], Some(3), None);
 }
 
+#[test]
+fn estimate_size() {
+    let bet_sizes = BetSizeOptions::try_from(("50%, a", "2.5x")).unwrap();
+    let tree_config = TreeConfig {
+        starting_pot: 100,
+        effective_stack: 300,
+        flop_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let ranges = ["QQ+,AK,T9s", "JJ-88,AQs,KQ,J9s"].map(|r| r.parse().unwrap());
+
+    // monotone flop, then a turn spot
+    for (flop, turn) in [
+        ("QhJh2h", NOT_DEALT),
+        ("Td9d6h", card_from_str("Qc").unwrap()),
+    ] {
+        let card_config = CardConfig {
+            range: ranges,
+            flop: flop_from_str(flop).unwrap(),
+            turn,
+            ..Default::default()
+        };
+
+        let tree_config = TreeConfig {
+            initial_state: match turn {
+                NOT_DEALT => BoardState::Flop,
+                _ => BoardState::Turn,
+            },
+            ..tree_config.clone()
+        };
+
+        let action_tree = ActionTree::new(tree_config).unwrap();
+        let estimate = PostFlopGame::estimate_size(&card_config, &action_tree).unwrap();
+        let game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+        assert_eq!(estimate.num_nodes, game.num_nodes);
+
+        let num_chance_nodes = game
+            .node_arena
+            .iter()
+            .filter(|node| node.lock().is_chance())
+            .count() as u64;
+        let estimated_chance_nodes = estimate.num_chance_nodes[0] + estimate.num_chance_nodes[1];
+        assert_eq!(estimated_chance_nodes, num_chance_nodes);
+
+        // the difference between uncompressed and compressed sizes is the storage size
+        let (uncompressed, compressed) = game.memory_usage();
+        let (estimated_uncompressed, estimated_compressed) = estimate.memory_usage;
+        assert_eq!(
+            estimated_uncompressed - estimated_compressed,
+            uncompressed - compressed
+        );
+        assert!(estimated_uncompressed <= uncompressed);
+    }
+
+    // the turn cards of two of the three non-heart suits are skipped by the isomorphism
+    let card_config = CardConfig {
+        range: ranges,
+        flop: flop_from_str("QhJh2h").unwrap(),
+        ..Default::default()
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let estimate = PostFlopGame::estimate_size(&card_config, &action_tree).unwrap();
+    assert_eq!(estimate.num_deals[0], 49 - 2 * 13);
+    assert!(estimate.num_deals[1] > 0);
+}
+
 #[test]
 fn node_locking() {
     let card_config = CardConfig {
@@ -854,6 +1285,7 @@ fn node_locking() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: DeckType::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -908,6 +1340,45 @@ fn node_locking() {
     assert!((strategy_oop[3] - 1.0).abs() < 1e-3); // AA bet
 }
 
+#[test]
+fn node_locking_errors() {
+    let card_config = CardConfig {
+        range: ["AsAh,QsQh".parse().unwrap(), "KsKh".parse().unwrap()],
+        flop: flop_from_str("2s3h4d").unwrap(),
+        turn: card_from_str("6c").unwrap(),
+        river: card_from_str("7c").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 20,
+        effective_stack: 10,
+        river_bet_sizes: [("a", "").try_into().unwrap(), ("a", "").try_into().unwrap()],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+    let err = game.try_lock_current_strategy(&[1.0, 0.0, 0.0, 1.0]);
+    assert!(matches!(err, Err(Error::LockedNode(_))));
+
+    game.allocate_memory(false);
+    let err = game.try_lock_current_strategy(&[1.0, 0.0]);
+    assert!(matches!(err, Err(Error::LockedNode(_))));
+
+    game.play(0); // check
+    game.play(0); // check
+    let err = game.try_lock_current_strategy(&[]);
+    assert!(matches!(err, Err(Error::LockedNode(_))));
+
+    game.back_to_root();
+    assert!(game
+        .try_lock_current_strategy(&[1.0, 0.0, 0.0, 1.0])
+        .is_ok());
+}
+
 #[test]
 fn node_locking_partial() {
     let card_config = CardConfig {
@@ -915,6 +1386,7 @@ fn node_locking_partial() {
         flop: flop_from_str("2s3h4d").unwrap(),
         turn: card_from_str("6c").unwrap(),
         river: card_from_str("7c").unwrap(),
+        deck: DeckType::Standard,
     };
 
     let tree_config = TreeConfig {
@@ -1141,6 +1613,454 @@ fn set_bunching_effect_always_win() {
     assert!((ev_ip - 0.0).abs() < 1e-4);
 }
 
+#[test]
+fn short_deck() {
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        deck: DeckType::ShortDeck,
+        ..Default::default()
+    };
+
+    let tree_config = TreeConfig {
+        starting_pot: 60,
+        effective_stack: 970,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+
+    // hands containing deuces through fives are excluded
+    assert_eq!(game.num_private_hands(0), 33 * 32 / 2);
+    assert!(game
+        .private_cards(0)
+        .iter()
+        .all(|&(c1, c2)| c1 >= 16 && c2 >= 16));
+
+    game.allocate_memory(false);
+    finalize(&mut game);
+
+    game.cache_normalized_weights();
+    let weights_oop = game.normalized_weights(0);
+    let weights_ip = game.normalized_weights(1);
+    let equity_oop = compute_average(&game.equity(0), weights_oop);
+    let ev_oop = compute_average(&game.expected_values(0), weights_oop);
+    let ev_ip = compute_average(&game.expected_values(1), weights_ip);
+    assert!((equity_oop - 0.5).abs() < 1e-5);
+    assert!((ev_oop - 30.0).abs() < 1e-4);
+    assert!((ev_ip - 30.0).abs() < 1e-4);
+
+    // only the remaining cards of the short deck can be dealt
+    game.play(0);
+    game.play(0);
+    let possible_cards = game.possible_cards();
+    assert_eq!(possible_cards.count_ones(), 33);
+    assert_eq!(possible_cards & 0xffff, 0);
+
+    game.play(card_from_str("Ac").unwrap() as usize);
+    game.play(0);
+    game.play(0);
+    assert_eq!(game.possible_cards().count_ones(), 32);
+
+    // board cards must be contained in the deck
+    let card_config = CardConfig {
+        flop: flop_from_str("Td9d5h").unwrap(),
+        ..card_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    assert!(PostFlopGame::with_config(card_config, action_tree).is_err());
+}
+
+#[test]
+fn fixed_limit() {
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 100,
+        fixed_limit: Some(FixedLimit::new(10, 20, 2)),
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Check, Action::Bet(20)]
+    );
+
+    action_tree.play(Action::Bet(20)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::Raise(40)]
+    );
+
+    action_tree.play(Action::Raise(40)).unwrap();
+    action_tree.play(Action::Raise(60)).unwrap();
+
+    // the raise cap is reached
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call]
+    );
+    assert!(action_tree.add_action(Action::Raise(80)).is_err());
+
+    // only the bet unit is allowed
+    action_tree.back_to_root();
+    assert!(action_tree.add_action(Action::Bet(30)).is_err());
+    assert!(action_tree.add_action(Action::AllIn(100)).is_err());
+
+    // short stack: the last raise becomes an all-in
+    let short_config = TreeConfig {
+        effective_stack: 50,
+        ..tree_config.clone()
+    };
+    let mut action_tree = ActionTree::new(short_config).unwrap();
+    action_tree.play(Action::Bet(20)).unwrap();
+    action_tree.play(Action::Raise(40)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::AllIn(50)]
+    );
+
+    // invalid configurations
+    let invalid_units = TreeConfig {
+        fixed_limit: Some(FixedLimit::new(10, 0, 2)),
+        ..tree_config.clone()
+    };
+    assert!(ActionTree::new(invalid_units).is_err());
+
+    let invalid_pot_limit = TreeConfig {
+        pot_limit: true,
+        ..tree_config.clone()
+    };
+    assert!(ActionTree::new(invalid_pot_limit).is_err());
+
+    // the game is solved without any changes
+    let card_config = CardConfig {
+        range: [
+            "TT+,AKs,AQo,76s,55".parse().unwrap(),
+            "99+,AJs+,KQs,87s".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    let exploitability = solve(&mut game, 1000, 0.05, false);
+    assert!(exploitability <= 0.05);
+}
+
+#[test]
+fn bet_size_rules() {
+    let bet_sizes = BetSizeOptions::try_from(("50%", "3x"))
+        .unwrap()
+        .with_rule("depth=2", "2.5x")
+        .unwrap()
+        .with_rule("depth>=3", "a")
+        .unwrap()
+        .with_rule("spr<2", "33%, a")
+        .unwrap();
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 1000,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Check, Action::Bet(50)]
+    );
+
+    // raise: 3x
+    action_tree.play(Action::Bet(50)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::Raise(150)]
+    );
+
+    // 3-bet: 2.5x
+    action_tree.play(Action::Raise(150)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::Raise(375)]
+    );
+
+    // 4-bet: all-in only
+    action_tree.play(Action::Raise(375)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::AllIn(1000)]
+    );
+
+    // low SPR (the first matching rule is used)
+    let tree_config = TreeConfig {
+        effective_stack: 150,
+        ..tree_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Check, Action::Bet(33), Action::AllIn(150)]
+    );
+}
+
+#[test]
+fn stacks_and_dead_money() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AKs,AQo".parse().unwrap(),
+            "99+,AJs+,KQs".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        ..Default::default()
+    };
+
+    // BB (OOP) vs BTN (IP) with the SB folded: 40 chips committed by each player
+    let mut tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        turn_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+    tree_config.set_stacks([340, 540], 40, 10);
+    assert_eq!(tree_config.starting_pot, 90);
+    assert_eq!(tree_config.effective_stack, 300);
+    assert_eq!(tree_config.player_stacks(), [300, 500]);
+    assert_eq!(tree_config.committed_amount(), 40.0);
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 100, 0.0, false);
+    assert_eq!(game.current_stacks(), [300, 500]);
+    assert_eq!(game.current_pot(), 90);
+
+    // bet - call
+    game.play(1);
+    assert_eq!(game.current_stacks(), [255, 500]);
+    assert_eq!(game.current_pot(), 135);
+    game.play(1);
+    assert_eq!(game.current_stacks(), [255, 455]);
+    assert_eq!(game.current_pot(), 180);
+
+    let river = game.possible_cards().trailing_zeros() as usize;
+    game.play(river);
+
+    let subgame_config = game.subgame_tree_config().unwrap();
+    assert_eq!(subgame_config.starting_pot, 180);
+    assert_eq!(subgame_config.effective_stack, 255);
+    assert_eq!(subgame_config.stacks, [255, 455]);
+    assert_eq!(subgame_config.dead_money, 10);
+
+    // invalid configurations
+    let invalid_stacks = TreeConfig {
+        effective_stack: 500,
+        ..tree_config.clone()
+    };
+    assert!(ActionTree::new(invalid_stacks).is_err());
+
+    let invalid_dead_money = TreeConfig {
+        dead_money: 100,
+        ..tree_config
+    };
+    assert!(ActionTree::new(invalid_dead_money).is_err());
+}
+
+#[test]
+fn terminal_payoff_identity() {
+    let card_config = CardConfig {
+        range: [
+            "TT+,AKs,AQo,76s,55".parse().unwrap(),
+            "99+,AJs+,KQs,87s".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let bet_sizes: BetSizeOptions = ("50%, a", "60%").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 60,
+        effective_stack: 300,
+        rake_rate: 0.05,
+        rake_cap: 10.0,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+    game.allocate_memory(false);
+    solve(&mut game, 200, 0.0, false);
+
+    // the identity function reproduces the chip utility
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game_identity = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    let payoff = TerminalPayoff::custom(vec![300.0, 500.0], |chips| chips).unwrap();
+    game_identity.set_terminal_payoff(payoff).unwrap();
+    game_identity.allocate_memory(false);
+    solve(&mut game_identity, 200, 0.0, false);
+
+    let ev = compute_current_ev(&game);
+    let ev_identity = compute_current_ev(&game_identity);
+    assert!((ev[0] - ev_identity[0]).abs() < 1e-3);
+    assert!((ev[1] - ev_identity[1]).abs() < 1e-3);
+
+    game.cache_normalized_weights();
+    game_identity.cache_normalized_weights();
+    for player in 0..2 {
+        let weights = game.normalized_weights(player);
+        let ev = compute_average(&game.expected_values(player), weights);
+        let ev_identity = compute_average(&game_identity.expected_values(player), weights);
+        let base = [300.0, 500.0][player];
+        assert!((ev + base - ev_identity).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn terminal_payoff_icm() {
+    let card_config = CardConfig {
+        range: [
+            "AA,TT,66,KQ,KJ,QJ,A5s,A4s".parse().unwrap(),
+            "JJ,99,A8,T9,98".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td6h2c").unwrap(),
+        turn: card_from_str("3s").unwrap(),
+        river: card_from_str("8d").unwrap(),
+        deck: DeckType::Standard,
+    };
+
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 40,
+        effective_stack: 200,
+        river_bet_sizes: [("a", "").try_into().unwrap(), Default::default()],
+        ..Default::default()
+    };
+
+    // call frequency of IP facing the all-in
+    let call_frequency = |payoff: Option<TerminalPayoff>| {
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let mut game = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        if let Some(payoff) = payoff {
+            game.set_terminal_payoff(payoff).unwrap();
+        }
+        game.allocate_memory(false);
+        solve(&mut game, 1000, 0.0, false);
+
+        game.play(1);
+        game.cache_normalized_weights();
+        let num_hands = game.num_private_hands(1);
+        let strategy = game.strategy();
+        compute_average(&strategy[num_hands..], game.normalized_weights(1))
+    };
+
+    // spin format on the bubble: the short stack is not involved in the hand
+    let payoff = TerminalPayoff::icm(vec![200.0, 200.0, 100.0], vec![1.0, 0.0]).unwrap();
+    let chip_call = call_frequency(None);
+    let winner_take_all_call = call_frequency(Some(payoff));
+    assert!((chip_call - winner_take_all_call).abs() < 1e-2);
+
+    let payoff = TerminalPayoff::icm(vec![200.0, 200.0, 100.0], vec![0.7, 0.3]).unwrap();
+    let icm_call = call_frequency(Some(payoff));
+    assert!(icm_call < chip_call - 0.05);
+
+    // invalid configurations
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    let payoff = TerminalPayoff::icm(vec![150.0, 200.0], vec![1.0]).unwrap();
+    assert!(game.set_terminal_payoff(payoff).is_err());
+    assert!(game.terminal_payoff().is_none());
+}
+
+#[test]
+fn big_blind_sizes() {
+    use crate::{format_action, AmountUnit};
+
+    let card_config = CardConfig {
+        range: [Range::ones(); 2],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+        ..Default::default()
+    };
+
+    let bet_sizes = BetSizeOptions::try_from(("2.5bb, 75%", "5bb, a")).unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 1000,
+        big_blind: 20,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    };
+
+    // "bb" bet sizes require the big blind
+    let config = TreeConfig {
+        big_blind: 0,
+        ..tree_config.clone()
+    };
+    assert!(ActionTree::new(config).is_err());
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = PostFlopGame::with_config(card_config, action_tree).unwrap();
+    game.allocate_memory(false);
+
+    let actions = game.available_actions();
+    assert_eq!(actions, [Action::Check, Action::Bet(50), Action::Bet(75)]);
+
+    let labels = |game: &PostFlopGame, unit| {
+        game.available_actions()
+            .into_iter()
+            .map(|action| format_action(game, action, unit))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        labels(&game, AmountUnit::Chips),
+        ["Check", "Bet 50", "Bet 75"]
+    );
+    assert_eq!(
+        labels(&game, AmountUnit::BigBlinds),
+        ["Check", "Bet 2.5bb", "Bet 3.8bb"]
+    );
+    assert_eq!(
+        labels(&game, AmountUnit::PotPercent),
+        ["Check", "Bet 50%", "Bet 75%"]
+    );
+
+    // the raise of 5bb is added to the previous bet
+    game.play(2);
+    assert_eq!(
+        game.available_actions(),
+        [
+            Action::Fold,
+            Action::Call,
+            Action::Raise(175),
+            Action::AllIn(1000)
+        ]
+    );
+    assert_eq!(
+        labels(&game, AmountUnit::BigBlinds),
+        ["Fold", "Call", "Raise 8.8bb", "AllIn 50bb"]
+    );
+    assert_eq!(
+        labels(&game, AmountUnit::PotPercent),
+        ["Fold", "Call", "Raise 40%", "AllIn 370%"]
+    );
+}
+
 #[test]
 #[ignore]
 fn solve_pio_preset_normal() {
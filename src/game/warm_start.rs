@@ -0,0 +1,274 @@
+use super::*;
+use crate::interface::*;
+use crate::utility::*;
+
+impl PostFlopGame {
+    /// Seeds the cumulative strategy and regrets with the results of a solved game.
+    ///
+    /// This method is intended to speed up solving a slightly modified configuration (e.g.,
+    /// different ranges or an additional bet size) by reusing the results of `source`.
+    /// Nodes are matched by walking both trees from the root and comparing the [`Action`]s, and
+    /// hands are matched by their private cards. For each matched node, the cumulative strategy
+    /// is set to the average strategy of `source` and the cumulative regrets are set to the
+    /// regrets of each action against that strategy. Actions and hands that do not exist in
+    /// `source` are left untouched.
+    ///
+    /// The regrets are rescaled to the ranges of `self`: the counterfactual values of a hand are
+    /// proportional to the weights of the opponent hands divided by the number of combinations,
+    /// which change when the ranges change.
+    ///
+    /// Both games must have the same board and deck, and their ranges must allow the same suit
+    /// isomorphism so that the chance nodes have the same cards. `self` must be allocated but not
+    /// yet solved, and
+    /// `source` must be solved with the full storage mode (e.g., loaded by `load_data_from_file`).
+    ///
+    /// Note that the default Discounted CFR schedule discards the cumulative values in the first
+    /// few iterations. To take advantage of the seeded values, resume the solving process from a
+    /// later iteration, e.g., with [`resume_with_callback`].
+    ///
+    /// [`resume_with_callback`]: crate::resume_with_callback
+    pub fn warm_start_from(&mut self, source: &PostFlopGame) -> Result<(), Error> {
+        if self.state != State::MemoryAllocated || self.storage_mode != BoardState::River {
+            return Err(Error::InvalidState("Memory is not allocated".to_string()));
+        }
+
+        if source.state != State::Solved {
+            return Err(Error::InvalidState("Source game is not solved".to_string()));
+        }
+
+        if source.storage_mode != BoardState::River {
+            return Err(Error::InvalidState(
+                "Source game does not have the full storage".to_string(),
+            ));
+        }
+
+        let (config, source_config) = (&self.card_config, &source.card_config);
+        if config.flop != source_config.flop
+            || config.turn != source_config.turn
+            || config.river != source_config.river
+        {
+            return Err(Error::InvalidConfig("Board does not match".to_string()));
+        }
+
+        if config.deck != source_config.deck {
+            return Err(Error::InvalidConfig("Deck does not match".to_string()));
+        }
+
+        if self.tree_config.initial_state != source.tree_config.initial_state {
+            return Err(Error::InvalidConfig(
+                "Initial state does not match".to_string(),
+            ));
+        }
+
+        // chance nodes are matched by their cards, so the cards eliminated by the suit isomorphism
+        // must be the same (otherwise, the subtrees of the unmatched cards would not be seeded)
+        if self.isomorphism_card_turn != source.isomorphism_card_turn
+            || self.isomorphism_card_river != source.isomorphism_card_river
+        {
+            return Err(Error::InvalidConfig(
+                "Suit isomorphism does not match (the ranges are symmetric in different suits)"
+                    .to_string(),
+            ));
+        }
+
+        let hand_map = [0, 1].map(|player| {
+            let mut reverse_table = vec![usize::MAX; NUM_CARD_PAIRS];
+            for (i, &(c1, c2)) in source.private_cards[player].iter().enumerate() {
+                reverse_table[card_pair_to_index(c1, c2)] = i;
+            }
+
+            self.private_cards[player]
+                .iter()
+                .map(|&(c1, c2)| reverse_table[card_pair_to_index(c1, c2)])
+                .collect::<Vec<_>>()
+        });
+
+        let scales = self.cfvalue_scales();
+        let source_scales = source.cfvalue_scales();
+        let regret_scales = [0, 1].map(|player| {
+            hand_map[player]
+                .iter()
+                .zip(&scales[player])
+                .map(|(&source_hand, &scale)| match source_hand {
+                    usize::MAX => 0.0,
+                    _ if source_scales[player][source_hand] == 0.0 => 0.0,
+                    _ => (scale / source_scales[player][source_hand]) as f32,
+                })
+                .collect::<Vec<_>>()
+        });
+
+        self.warm_start_recursive(
+            &mut self.root(),
+            source,
+            &source.root(),
+            &hand_map,
+            &regret_scales,
+        );
+
+        Ok(())
+    }
+
+    /// Returns the scale of the counterfactual values of each hand, i.e., the sum of the initial
+    /// weights of the opponent hands that do not conflict with the hand divided by the number of
+    /// combinations.
+    fn cfvalue_scales(&self) -> [Vec<f64>; 2] {
+        let num_combinations = match self.bunching_num_dead_cards {
+            0 => self.num_combinations,
+            _ => self.bunching_num_combinations,
+        };
+
+        [0, 1].map(|player| {
+            let opponent_cards = &self.private_cards[player ^ 1];
+            let opponent_weights = &self.initial_weights[player ^ 1];
+            self.private_cards[player]
+                .iter()
+                .map(|&(c1, c2)| {
+                    let mask: u64 = (1 << c1) | (1 << c2);
+                    let sum = opponent_cards
+                        .iter()
+                        .zip(opponent_weights)
+                        .filter(|&(&(o1, o2), _)| mask & ((1 << o1) | (1 << o2)) == 0)
+                        .fold(0.0, |acc, (_, &w)| acc + w as f64);
+                    sum / num_combinations
+                })
+                .collect()
+        })
+    }
+
+    /// Recursively copies the strategy and regrets of matched nodes.
+    fn warm_start_recursive(
+        &self,
+        node: &mut PostFlopNode,
+        source: &PostFlopGame,
+        source_node: &PostFlopNode,
+        hand_map: &[Vec<usize>; 2],
+        regret_scales: &[Vec<f32>; 2],
+    ) {
+        if node.is_terminal() || source_node.is_terminal() {
+            return;
+        }
+
+        if node.player != source_node.player {
+            return;
+        }
+
+        // match actions by their values
+        let action_map = node
+            .children()
+            .iter()
+            .map(|child| {
+                let action = child.lock().prev_action;
+                source_node
+                    .children()
+                    .iter()
+                    .position(|c| c.lock().prev_action == action)
+            })
+            .collect::<Vec<_>>();
+
+        if !node.is_chance() {
+            self.warm_start_node(
+                node,
+                source,
+                source_node,
+                &action_map,
+                hand_map,
+                regret_scales,
+            );
+        }
+
+        for (action, source_action) in action_map.iter().enumerate() {
+            if let Some(source_action) = *source_action {
+                self.warm_start_recursive(
+                    &mut node.play(action),
+                    source,
+                    &source_node.play(source_action),
+                    hand_map,
+                    regret_scales,
+                );
+            }
+        }
+    }
+
+    /// Copies the strategy and regrets of a player node.
+    fn warm_start_node(
+        &self,
+        node: &mut PostFlopNode,
+        source: &PostFlopGame,
+        source_node: &PostFlopNode,
+        action_map: &[Option<usize>],
+        hand_map: &[Vec<usize>; 2],
+        regret_scales: &[Vec<f32>; 2],
+    ) {
+        let player = node.player();
+        let num_actions = node.num_actions();
+        let num_hands = self.num_private_hands(player);
+        let source_num_actions = source_node.num_actions();
+        let source_num_hands = source.num_private_hands(player);
+
+        let source_strategy = if source.is_compression_enabled {
+            normalized_strategy_compressed(source_node.strategy_compressed(), source_num_actions)
+        } else {
+            normalized_strategy(source_node.strategy(), source_num_actions)
+        };
+
+        let source_cfvalues = if source.is_compression_enabled {
+            decode_signed_slice(
+                source_node.cfvalues_compressed(),
+                source_node.cfvalue_scale(),
+            )
+        } else {
+            source_node.cfvalues().to_vec()
+        };
+
+        // expected values of the average strategy
+        let mut source_ev = vec![0.0; source_num_hands];
+        for (s, c) in source_strategy
+            .chunks_exact(source_num_hands)
+            .zip(source_cfvalues.chunks_exact(source_num_hands))
+        {
+            source_ev.iter_mut().zip(s).zip(c).for_each(|((ev, s), c)| {
+                *ev += s * c;
+            });
+        }
+
+        let mut strategy = if self.is_compression_enabled {
+            normalized_strategy_compressed(node.strategy_compressed(), num_actions)
+        } else {
+            normalized_strategy(node.strategy(), num_actions)
+        };
+
+        let mut regrets = if self.is_compression_enabled {
+            decode_signed_slice(node.regrets_compressed(), node.regret_scale())
+        } else {
+            node.regrets().to_vec()
+        };
+
+        for (action, source_action) in action_map.iter().enumerate() {
+            let Some(source_action) = *source_action else {
+                continue;
+            };
+
+            for (hand, &source_hand) in hand_map[player].iter().enumerate() {
+                if source_hand == usize::MAX {
+                    continue;
+                }
+
+                let index = action * num_hands + hand;
+                let source_index = source_action * source_num_hands + source_hand;
+                strategy[index] = source_strategy[source_index];
+                let regret = source_cfvalues[source_index] - source_ev[source_hand];
+                regrets[index] = regret * regret_scales[player][hand];
+            }
+        }
+
+        if self.is_compression_enabled {
+            let scale = encode_unsigned_slice(node.strategy_compressed_mut(), &strategy);
+            node.set_strategy_scale(scale);
+            let scale = encode_signed_slice(node.regrets_compressed_mut(), &regrets);
+            node.set_regret_scale(scale);
+        } else {
+            node.strategy_mut().copy_from_slice(&strategy);
+            node.regrets_mut().copy_from_slice(&regrets);
+        }
+    }
+}
@@ -17,14 +17,34 @@ fn keep_n_msb(mut x: i32, n: i32) -> i32 {
     ret
 }
 
+/// A-2-3-4-5 straight.
+const WHEEL: i32 = 0b1_0000_0000_1111;
+
+/// A-6-7-8-9 straight (short-deck).
+const SHORT_DECK_WHEEL: i32 = 0b1_0000_1111_0000;
+
+/// Returns the index of the first entry of `HAND_TABLE` whose hand category is `category` or
+/// higher.
+const fn category_begin(category: i32) -> usize {
+    let mut i = 0;
+    while i < HAND_TABLE.len() && HAND_TABLE[i] < category << 26 {
+        i += 1;
+    }
+    i
+}
+
+/// Beginnings of the flush, full house, and quads blocks in `HAND_TABLE`.
+const FLUSH_BEGIN: usize = category_begin(5);
+const FULL_HOUSE_BEGIN: usize = category_begin(6);
+const QUADS_BEGIN: usize = category_begin(7);
+
 #[inline]
-fn find_straight(rankset: i32) -> i32 {
-    const WHEEL: i32 = 0b1_0000_0000_1111;
+fn find_straight(rankset: i32, wheel: i32) -> i32 {
     let is_straight = rankset & (rankset << 1) & (rankset << 2) & (rankset << 3) & (rankset << 4);
     if is_straight != 0 {
         keep_n_msb(is_straight, 1)
-    } else if (rankset & WHEEL) == WHEEL {
-        1 << 3
+    } else if (rankset & wheel) == wheel {
+        keep_n_msb(wheel ^ (1 << 12), 1)
     } else {
         0
     }
@@ -54,13 +74,47 @@ impl Hand {
         HAND_TABLE.binary_search(&self.evaluate_internal()).unwrap() as u16
     }
 
+    /// Evaluates the hand under the short-deck (6+) hand rankings.
+    ///
+    /// A-6-7-8-9 is the lowest straight, and a flush beats a full house. The hand must not
+    /// contain the removed ranks (deuces through fives).
+    #[inline]
+    pub fn evaluate_short_deck(&self) -> u16 {
+        let raw = self.evaluate_internal_with(SHORT_DECK_WHEEL);
+        let index = HAND_TABLE.binary_search(&raw).unwrap();
+
+        // swap the flush block and the full house block, which are adjacent in the table
+        let index = if (FLUSH_BEGIN..FULL_HOUSE_BEGIN).contains(&index) {
+            index + (QUADS_BEGIN - FULL_HOUSE_BEGIN)
+        } else if (FULL_HOUSE_BEGIN..QUADS_BEGIN).contains(&index) {
+            index - (FULL_HOUSE_BEGIN - FLUSH_BEGIN)
+        } else {
+            index
+        };
+
+        index as u16
+    }
+
+    /// Evaluates the hand without the table lookup; a larger value means a stronger hand.
+    ///
+    /// Unlike [`evaluate`](Self::evaluate), this method accepts hands with five or six cards.
+    #[inline]
+    pub(crate) fn evaluate_raw(&self) -> i32 {
+        self.evaluate_internal()
+    }
+
+    #[inline]
     fn evaluate_internal(&self) -> i32 {
+        self.evaluate_internal_with(WHEEL)
+    }
+
+    fn evaluate_internal_with(&self, wheel: i32) -> i32 {
         let mut rankset = 0i32;
         let mut rankset_suit = [0i32; 4];
         let mut rankset_of_count = [0i32; 5];
         let mut rank_count = [0i32; 13];
 
-        for &card in &self.cards {
+        for &card in &self.cards[..self.num_cards] {
             let rank = card / 4;
             let suit = card % 4;
             rankset |= 1 << rank;
@@ -79,10 +133,10 @@ impl Hand {
             }
         }
 
-        let is_straight = find_straight(rankset);
+        let is_straight = find_straight(rankset, wheel);
 
         if flush_suit >= 0 {
-            let is_straight_flush = find_straight(rankset_suit[flush_suit as usize]);
+            let is_straight_flush = find_straight(rankset_suit[flush_suit as usize], wheel);
             if is_straight_flush != 0 {
                 // straight flush
                 (8 << 26) | is_straight_flush
@@ -173,4 +227,59 @@ mod tests {
         assert_eq!(counter[1], 58627800); // one pair
         assert_eq!(counter[0], 23294460); // high card
     }
+
+    #[test]
+    fn test_short_deck() {
+        assert_eq!(FLUSH_BEGIN, HAND_TABLE.partition_point(|&x| x < 5 << 26));
+        assert_eq!(
+            FULL_HOUSE_BEGIN,
+            HAND_TABLE.partition_point(|&x| x < 6 << 26)
+        );
+        assert_eq!(QUADS_BEGIN, HAND_TABLE.partition_point(|&x| x < 7 << 26));
+
+        // every seven-card hand from the 36-card deck can be evaluated
+        for i in 16..52 {
+            let hand = Hand::new().add_card(i);
+            for j in (i + 1)..52 {
+                let hand = hand.add_card(j);
+                for k in (j + 1)..52 {
+                    let hand = hand.add_card(k);
+                    for m in (k + 1)..52 {
+                        let hand = hand.add_card(m);
+                        for n in (m + 1)..52 {
+                            let hand = hand.add_card(n);
+                            for p in (n + 1)..52 {
+                                let hand = hand.add_card(p);
+                                for q in (p + 1)..52 {
+                                    hand.add_card(q).evaluate_short_deck();
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // cards: 4 * rank + suit (rank 4 = six, rank 12 = ace)
+        let make = |cards: [usize; 7]| {
+            cards
+                .iter()
+                .fold(Hand::new(), |hand, &card| hand.add_card(card))
+        };
+
+        // A-6-7-8-9 is the lowest straight
+        let wheel = make([48, 16, 21, 26, 31, 37, 42]); // Ac 6c 7d 8h 9s Jd Qh
+        let ten_high = make([16, 21, 26, 31, 32, 41, 46]); // 6c 7d 8h 9s Tc Qd Kh
+        let trips = make([48, 49, 50, 16, 21, 26, 35]); // Ac Ad Ah 6c 7d 8h Ts
+        assert!(wheel.evaluate_short_deck() < ten_high.evaluate_short_deck());
+        assert!(wheel.evaluate_short_deck() > trips.evaluate_short_deck());
+
+        // a flush beats a full house
+        let flush = make([16, 24, 32, 40, 48, 21, 39]); // 6c 8c Tc Qc Ac 7d Js
+        let full_house = make([48, 49, 50, 44, 45, 16, 21]); // Ac Ad Ah Kc Kd 6c 7d
+        let quads = make([48, 49, 50, 51, 16, 21, 26]); // Ac Ad Ah As 6c 7d 8h
+        assert!(flush.evaluate() < full_house.evaluate());
+        assert!(flush.evaluate_short_deck() > full_house.evaluate_short_deck());
+        assert!(flush.evaluate_short_deck() < quads.evaluate_short_deck());
+    }
 }
@@ -0,0 +1,652 @@
+use crate::card::*;
+use crate::error::*;
+use crate::hand::*;
+use crate::range::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Made-hand category of a hand, ordered from the strongest to the weakest.
+///
+/// Only the hands made with at least one hole card are counted: when the board alone makes the
+/// best hand (e.g., a straight on the board), the hand is classified by the pairs made with the
+/// hole cards instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MadeHand {
+    StraightFlush,
+    Quads,
+    FullHouse,
+    Flush,
+    Straight,
+
+    /// A pocket pair that matches a board card.
+    Set,
+
+    /// A hole card that matches a paired board card.
+    Trips,
+
+    /// Both hole cards match different board cards.
+    TwoPair,
+
+    /// A pocket pair higher than every board card.
+    Overpair,
+
+    /// A hole card that matches the highest board card.
+    TopPair(Kicker),
+
+    /// A pocket pair between the highest and the second highest board cards.
+    PocketPairBelowTopPair,
+
+    /// A hole card that matches the second highest board card.
+    MiddlePair,
+
+    /// Any other pair made with the hole cards (e.g., bottom pair, a small pocket pair).
+    WeakPair,
+
+    /// No pair made with the hole cards.
+    HighCard,
+}
+
+/// Kicker class of a top pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Kicker {
+    /// The highest rank that is neither on the board nor paired.
+    Top,
+
+    /// Ten or higher, but not the top kicker.
+    Good,
+
+    /// Nine or lower.
+    Weak,
+}
+
+/// Category of a hand on a given board: the made hand and the draws.
+///
+/// The draws are only counted when they use at least one hole card and more cards are to come (i.e.,
+/// all draw flags are `false` on the river).
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let board = flop_from_str("KsTs4d").unwrap();
+///
+/// let hole = (card_from_str("Kh").unwrap(), card_from_str("Qh").unwrap());
+/// let category = classify_hand(hole, &board).unwrap();
+/// assert_eq!(category.made_hand, MadeHand::TopPair(Kicker::Good));
+///
+/// let hole = (card_from_str("As").unwrap(), card_from_str("Js").unwrap());
+/// let category = classify_hand(hole, &board).unwrap();
+/// assert_eq!(category.made_hand, MadeHand::HighCard);
+/// assert!(category.nut_flush_draw);
+/// assert!(category.gutshot);
+/// assert_eq!(category.to_string(), "high card, nut flush draw, gutshot");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandCategory {
+    /// The made-hand category.
+    pub made_hand: MadeHand,
+
+    /// Four cards of a suit (including at least one hole card).
+    pub flush_draw: bool,
+
+    /// A flush draw with the highest card of the suit that is not on the board.
+    pub nut_flush_draw: bool,
+
+    /// An open-ended straight draw or a double gutshot (two ranks complete a straight).
+    pub open_ended_straight_draw: bool,
+
+    /// Exactly one rank completes a straight.
+    pub gutshot: bool,
+
+    /// Three cards of a suit on the flop (including at least one hole card).
+    pub backdoor_flush_draw: bool,
+
+    /// Both hole cards are higher than every board card.
+    pub overcards: bool,
+}
+
+impl HandCategory {
+    /// Returns whether the hand has a flush draw or a straight draw (backdoor draws excluded).
+    #[inline]
+    pub fn has_draw(&self) -> bool {
+        self.flush_draw || self.open_ended_straight_draw || self.gutshot
+    }
+}
+
+/// Classifies the hand `hole` on `board` (three to five cards) in the standard 52-card deck.
+///
+/// Returns an error if a card is invalid or the cards are not distinct.
+pub fn classify_hand(hole: (Card, Card), board: &[Card]) -> Result<HandCategory, Error> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(Error::InvalidCard(format!(
+            "Board must have 3 to 5 cards: {}",
+            board.len()
+        )));
+    }
+
+    let mut mask = 0u64;
+    for &card in [hole.0, hole.1].iter().chain(board) {
+        if card >= 52 {
+            return Err(Error::InvalidCard(format!("Invalid card: {card}")));
+        }
+        if mask & (1 << card) != 0 {
+            return Err(Error::InvalidCard(format!("Duplicate cards: {card}")));
+        }
+        mask |= 1 << card;
+    }
+
+    let made_hand = made_hand(hole, board);
+    let is_river = board.len() == 5;
+
+    let hole_ranks = [hole.0 / 4, hole.1 / 4];
+    let board_rankset = rankset(board.iter().copied());
+    let top_board_rank = 15 - board_rankset.leading_zeros() as u8;
+
+    let mut result = HandCategory {
+        made_hand,
+        flush_draw: false,
+        nut_flush_draw: false,
+        open_ended_straight_draw: false,
+        gutshot: false,
+        backdoor_flush_draw: false,
+        overcards: hole_ranks[0] != hole_ranks[1] && hole_ranks.iter().all(|&r| r > top_board_rank),
+    };
+
+    if is_river {
+        return Ok(result);
+    }
+
+    // flush draws
+    if made_hand > MadeHand::Flush {
+        for suit in 0..4 {
+            let hole_suited = [hole.0, hole.1].into_iter().filter(|&c| c % 4 == suit);
+            let num_hole = hole_suited.clone().count();
+            let num_board = board.iter().filter(|&&c| c % 4 == suit).count();
+            if num_hole == 0 {
+                continue;
+            }
+            if num_hole + num_board == 4 {
+                let board_suited = rankset(board.iter().copied().filter(|&c| c % 4 == suit));
+                let nut_rank = (0..13)
+                    .rev()
+                    .find(|&r| board_suited & (1 << r) == 0)
+                    .unwrap();
+                result.flush_draw = true;
+                result.nut_flush_draw = hole_suited.clone().any(|c| c / 4 == nut_rank);
+            } else if num_hole + num_board == 3 && board.len() == 3 {
+                result.backdoor_flush_draw = true;
+            }
+        }
+    }
+
+    // straight draws
+    if made_hand > MadeHand::Straight {
+        let hole_rankset = rankset([hole.0, hole.1].into_iter());
+        let outs = (0..13)
+            .filter(|&r| {
+                let with_hole = straight_high(board_rankset | hole_rankset | (1 << r));
+                with_hole > straight_high(board_rankset | (1 << r))
+            })
+            .count();
+        result.open_ended_straight_draw = outs >= 2;
+        result.gutshot = outs == 1;
+    }
+
+    Ok(result)
+}
+
+/// Returns the made-hand category.
+fn made_hand(hole: (Card, Card), board: &[Card]) -> MadeHand {
+    let mut rank_count = [0u8; 13];
+    board.iter().for_each(|&c| rank_count[c as usize / 4] += 1);
+
+    let hand = board
+        .iter()
+        .fold(Hand::new(), |h, &c| h.add_card(c as usize));
+    let full_hand = hand.add_card(hole.0 as usize).add_card(hole.1 as usize);
+    let full_raw = full_hand.evaluate_raw();
+    let uses_hole = board.len() < 5 || full_raw > hand.evaluate_raw();
+
+    let (high, low) = if hole.0 / 4 >= hole.1 / 4 {
+        (hole.0 as usize / 4, hole.1 as usize / 4)
+    } else {
+        (hole.1 as usize / 4, hole.0 as usize / 4)
+    };
+
+    if uses_hole {
+        let has_quads = if high == low {
+            rank_count[high] == 2
+        } else {
+            rank_count[high] == 3 || rank_count[low] == 3
+        };
+        match full_raw >> 26 {
+            8 => return MadeHand::StraightFlush,
+            7 if has_quads => return MadeHand::Quads,
+            6 => return MadeHand::FullHouse,
+            5 => return MadeHand::Flush,
+            4 => return MadeHand::Straight,
+            _ => {}
+        }
+    }
+
+    let mut board_ranks = (0..13).rev().filter(|&r| rank_count[r] > 0);
+    let top_rank = board_ranks.next().unwrap();
+    let second_rank = board_ranks.next().unwrap_or(0);
+
+    if high == low {
+        return match rank_count[high] {
+            0 if high > top_rank => MadeHand::Overpair,
+            0 if high > second_rank => MadeHand::PocketPairBelowTopPair,
+            0 => MadeHand::WeakPair,
+            _ => MadeHand::Set,
+        };
+    }
+
+    if rank_count[high] >= 2 || rank_count[low] >= 2 {
+        return MadeHand::Trips;
+    }
+
+    match (rank_count[high], rank_count[low]) {
+        (1, 1) => MadeHand::TwoPair,
+        (1, _) | (_, 1) => {
+            let (pair, kicker) = if rank_count[high] == 1 {
+                (high, low)
+            } else {
+                (low, high)
+            };
+            if pair == top_rank {
+                let top_kicker = (0..13)
+                    .rev()
+                    .find(|&r| r != pair && rank_count[r] == 0)
+                    .unwrap();
+                if kicker == top_kicker {
+                    MadeHand::TopPair(Kicker::Top)
+                } else if kicker >= 8 {
+                    MadeHand::TopPair(Kicker::Good)
+                } else {
+                    MadeHand::TopPair(Kicker::Weak)
+                }
+            } else if pair == second_rank {
+                MadeHand::MiddlePair
+            } else {
+                MadeHand::WeakPair
+            }
+        }
+        _ => MadeHand::HighCard,
+    }
+}
+
+#[inline]
+fn rankset(cards: impl Iterator<Item = Card>) -> u16 {
+    cards.fold(0, |acc, c| acc | (1 << (c / 4)))
+}
+
+/// Returns the highest rank of the straights in `rankset` plus one, or zero if there is none.
+#[inline]
+fn straight_high(rankset: u16) -> u8 {
+    const WHEEL: u16 = 0b1_0000_0000_1111;
+    let straights = rankset & (rankset << 1) & (rankset << 2) & (rankset << 3) & (rankset << 4);
+    if straights != 0 {
+        16 - straights.leading_zeros() as u8
+    } else if rankset & WHEEL == WHEEL {
+        4
+    } else {
+        0
+    }
+}
+
+impl fmt::Display for MadeHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            MadeHand::StraightFlush => "straight flush",
+            MadeHand::Quads => "quads",
+            MadeHand::FullHouse => "full house",
+            MadeHand::Flush => "flush",
+            MadeHand::Straight => "straight",
+            MadeHand::Set => "set",
+            MadeHand::Trips => "trips",
+            MadeHand::TwoPair => "two pair",
+            MadeHand::Overpair => "overpair",
+            MadeHand::TopPair(Kicker::Top) => "top pair (top kicker)",
+            MadeHand::TopPair(Kicker::Good) => "top pair (good kicker)",
+            MadeHand::TopPair(Kicker::Weak) => "top pair (weak kicker)",
+            MadeHand::PocketPairBelowTopPair => "pocket pair below top pair",
+            MadeHand::MiddlePair => "middle pair",
+            MadeHand::WeakPair => "weak pair",
+            MadeHand::HighCard => "high card",
+        };
+        f.write_str(s)
+    }
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.made_hand)?;
+        let flags = [
+            (self.nut_flush_draw, "nut flush draw"),
+            (self.flush_draw && !self.nut_flush_draw, "flush draw"),
+            (self.open_ended_straight_draw, "open-ended straight draw"),
+            (self.gutshot, "gutshot"),
+            (self.backdoor_flush_draw, "backdoor flush draw"),
+            (self.overcards, "overcards"),
+        ];
+        for (_, name) in flags.iter().filter(|(flag, _)| *flag) {
+            write!(f, ", {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A filter of hand categories, parsed from an expression.
+///
+/// An expression is a comma-separated list of alternatives, and each alternative is a list of
+/// terms joined by `&` that must all match. The terms are case-insensitive, and spaces, hyphens and
+/// underscores are ignored:
+///
+/// - Made hands: `straightflush`, `quads`, `fullhouse`, `flush`, `straight`, `set`, `trips`,
+///   `twopair`, `overpair`, `toppair` (any kicker), `tptk` (top pair with the top kicker),
+///   `toppairgoodkicker`, `toppairweakkicker`, `pocketpairbelowtoppair`, `middlepair`,
+///   `weakpair`, `pair` (any one pair made with the hole cards), and `highcard`. A trailing `+`
+///   also matches the stronger made hands (e.g., `toppair+`).
+/// - Draws: `flushdraw`, `nutflushdraw`, `oesd`, `gutshot`, `backdoorflushdraw`, `overcards`,
+///   and `draw` (any flush draw or straight draw).
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let board = flop_from_str("KsTs4d").unwrap();
+/// let range = "AA,KQs,JTs,AJs,54s".parse::<Range>().unwrap();
+///
+/// // top pair or better, plus flush draws
+/// let filter = "toppair+, flushdraw".parse::<CategoryFilter>().unwrap();
+/// let filtered = range.filter_by_category(&board, &filter).unwrap();
+/// assert_eq!(filtered.to_string(), "AA,AsJs,KhQh,KdQd,KcQc,5s4s");
+///
+/// // weak pair with a flush draw
+/// let filter = "weakpair & flushdraw".parse::<CategoryFilter>().unwrap();
+/// let filtered = range.filter_by_category(&board, &filter).unwrap();
+/// assert_eq!(filtered.to_string(), "5s4s");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryFilter {
+    alternatives: Vec<Vec<Term>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Term {
+    Made(MadeHand),
+    AtLeast(MadeHand),
+    TopPair,
+    Pair,
+    FlushDraw,
+    NutFlushDraw,
+    OpenEndedStraightDraw,
+    Gutshot,
+    BackdoorFlushDraw,
+    Overcards,
+    Draw,
+}
+
+impl CategoryFilter {
+    /// Returns whether `category` matches the filter.
+    #[inline]
+    pub fn matches(&self, category: &HandCategory) -> bool {
+        self.alternatives
+            .iter()
+            .any(|terms| terms.iter().all(|term| term.matches(category)))
+    }
+}
+
+impl Term {
+    fn matches(&self, c: &HandCategory) -> bool {
+        match *self {
+            Term::Made(made_hand) => c.made_hand == made_hand,
+            Term::AtLeast(made_hand) => c.made_hand <= made_hand,
+            Term::TopPair => matches!(c.made_hand, MadeHand::TopPair(_)),
+            Term::Pair => (MadeHand::Overpair..=MadeHand::WeakPair).contains(&c.made_hand),
+            Term::FlushDraw => c.flush_draw,
+            Term::NutFlushDraw => c.nut_flush_draw,
+            Term::OpenEndedStraightDraw => c.open_ended_straight_draw,
+            Term::Gutshot => c.gutshot,
+            Term::BackdoorFlushDraw => c.backdoor_flush_draw,
+            Term::Overcards => c.overcards,
+            Term::Draw => c.has_draw(),
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let name = s
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let (name, or_better) = match name.strip_suffix('+') {
+            Some(name) => (name, true),
+            None => (name.as_str(), false),
+        };
+
+        let made_hand = match name {
+            "straightflush" => MadeHand::StraightFlush,
+            "quads" => MadeHand::Quads,
+            "fullhouse" => MadeHand::FullHouse,
+            "flush" => MadeHand::Flush,
+            "straight" => MadeHand::Straight,
+            "set" => MadeHand::Set,
+            "trips" => MadeHand::Trips,
+            "twopair" => MadeHand::TwoPair,
+            "overpair" => MadeHand::Overpair,
+            "tptk" | "toppairtopkicker" => MadeHand::TopPair(Kicker::Top),
+            "toppairgoodkicker" => MadeHand::TopPair(Kicker::Good),
+            "toppair" | "toppairweakkicker" => MadeHand::TopPair(Kicker::Weak),
+            "pocketpairbelowtoppair" => MadeHand::PocketPairBelowTopPair,
+            "middlepair" => MadeHand::MiddlePair,
+            "pair" | "weakpair" => MadeHand::WeakPair,
+            "highcard" => MadeHand::HighCard,
+            _ if or_better => return Err(format!("Invalid made hand: {s}")),
+            "flushdraw" => return Ok(Term::FlushDraw),
+            "nutflushdraw" => return Ok(Term::NutFlushDraw),
+            "oesd" => return Ok(Term::OpenEndedStraightDraw),
+            "gutshot" => return Ok(Term::Gutshot),
+            "backdoorflushdraw" => return Ok(Term::BackdoorFlushDraw),
+            "overcards" => return Ok(Term::Overcards),
+            "draw" => return Ok(Term::Draw),
+            _ => return Err(format!("Invalid hand category: {s}")),
+        };
+
+        Ok(match name {
+            _ if or_better => Term::AtLeast(made_hand),
+            "toppair" => Term::TopPair,
+            "pair" => Term::Pair,
+            _ => Term::Made(made_hand),
+        })
+    }
+}
+
+impl FromStr for CategoryFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let alternatives = s
+            .split(',')
+            .map(|alt| alt.split('&').map(Term::parse).collect())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::InvalidRange)?;
+        Ok(Self { alternatives })
+    }
+}
+
+/// Returns a mask of the combos (in the same order as [`Range::raw_data`]) whose category on
+/// `board` matches `filter`.
+///
+/// The combos that conflict with the board are `false`.
+pub fn category_mask(board: &[Card], filter: &CategoryFilter) -> Result<Vec<bool>, Error> {
+    let mut board_mask = 0u64;
+    for &card in board {
+        if card < 52 {
+            board_mask |= 1 << card;
+        }
+    }
+
+    (0..52 * 51 / 2)
+        .map(|index| {
+            let (card1, card2) = index_to_card_pair(index);
+            if board_mask & ((1 << card1) | (1 << card2)) != 0 {
+                return Ok(false);
+            }
+            let category = classify_hand((card1, card2), board)?;
+            Ok(filter.matches(&category))
+        })
+        .collect()
+}
+
+impl Range {
+    /// Returns the range of the combos whose category on `board` matches `filter`, keeping their
+    /// weights.
+    ///
+    /// The combos that conflict with the board are removed. See [`CategoryFilter`] for the syntax of
+    /// the filter.
+    pub fn filter_by_category(
+        &self,
+        board: &[Card],
+        filter: &CategoryFilter,
+    ) -> Result<Self, Error> {
+        let mask = category_mask(board, filter)?;
+        let data = self
+            .raw_data()
+            .iter()
+            .zip(mask)
+            .map(|(&w, m)| if m { w } else { 0.0 })
+            .collect::<Vec<_>>();
+        Self::from_raw_data(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(s: &str) -> Vec<Card> {
+        let mut chars = s.chars();
+        (0..s.len() / 2)
+            .map(|_| card_from_chars(&mut chars).unwrap())
+            .collect()
+    }
+
+    fn classify(hole: &str, board: &str) -> HandCategory {
+        let hole = cards(hole);
+        classify_hand((hole[0], hole[1]), &cards(board)).unwrap()
+    }
+
+    #[test]
+    fn made_hands() {
+        let tests = [
+            ("8h9h", "ThJhQh", MadeHand::StraightFlush),
+            ("7c7d", "7h7sKd", MadeHand::Quads),
+            ("KcKd", "7h7sKh", MadeHand::FullHouse),
+            ("2h3h", "ThJhQh", MadeHand::Flush),
+            ("8c9d", "ThJhQs", MadeHand::Straight),
+            ("AdTh", "2s3s4s5s6s", MadeHand::HighCard),
+            ("7c7d", "7h8sKd", MadeHand::Set),
+            ("As7d", "7h7sKd", MadeHand::Trips),
+            ("Ks7d", "7h8sKd", MadeHand::TwoPair),
+            ("AcAd", "7h8sKd", MadeHand::Overpair),
+            ("AsKs", "7h8sKd", MadeHand::TopPair(Kicker::Top)),
+            ("8cQd", "7h8sAd", MadeHand::MiddlePair),
+            ("AsKs", "7h8sAd", MadeHand::TopPair(Kicker::Top)),
+            ("AsQs", "7h8sAd", MadeHand::TopPair(Kicker::Good)),
+            ("AsTs", "7h8sAd", MadeHand::TopPair(Kicker::Good)),
+            ("As9s", "7h8sAd", MadeHand::TopPair(Kicker::Weak)),
+            ("QcQd", "7h8sKd", MadeHand::PocketPairBelowTopPair),
+            ("8c7d", "7h8sKd", MadeHand::TwoPair),
+            ("7cAd", "7h8sKd", MadeHand::WeakPair),
+            ("5c5d", "7h8sKd", MadeHand::WeakPair),
+            ("AcQd", "7h8sKd", MadeHand::HighCard),
+            ("AcQd", "7h7sKd", MadeHand::HighCard),
+            ("AcQd", "7h7s7dKd2c", MadeHand::HighCard),
+        ];
+
+        for (hole, board, expected) in tests {
+            assert_eq!(classify(hole, board).made_hand, expected, "{hole} {board}");
+        }
+    }
+
+    #[test]
+    fn draws() {
+        let c = classify("AsJs", "KsTs4d");
+        assert!(c.flush_draw && c.nut_flush_draw && c.gutshot && !c.open_ended_straight_draw);
+
+        let c = classify("QsTs", "As9s4d");
+        assert!(c.flush_draw && !c.nut_flush_draw && !c.backdoor_flush_draw);
+
+        let c = classify("Jc8d", "9hTs2d");
+        assert!(c.open_ended_straight_draw && !c.gutshot && !c.flush_draw);
+        assert!(c.has_draw());
+
+        let c = classify("AhKh", "QdJc2h");
+        assert!(c.gutshot && c.backdoor_flush_draw && c.overcards);
+
+        let c = classify("AhKh", "QdJc2hTh");
+        assert_eq!(c.made_hand, MadeHand::Straight);
+        assert!(c.flush_draw && c.nut_flush_draw && !c.gutshot && !c.backdoor_flush_draw);
+
+        // double gutshot
+        let c = classify("Jc5d", "7h8s9d");
+        assert!(c.open_ended_straight_draw);
+
+        // the board alone makes the draw
+        let c = classify("2c3d", "9hTsJdQd");
+        assert!(!c.has_draw());
+
+        // no draws on the river
+        let c = classify("AsTs", "Ks9s4d2c3h");
+        assert!(!c.has_draw() && !c.backdoor_flush_draw);
+
+        assert!(classify_hand((0, 1), &[1, 2, 3]).is_err());
+        assert!(classify_hand((0, 1), &[2, 3]).is_err());
+        assert!(classify_hand((0, 52), &[2, 3, 4]).is_err());
+    }
+
+    #[test]
+    fn category_filter() {
+        let board = cards("Qh9h5c");
+        let range = "QQ-55,AQ,KJ,T8,98,A2s".parse::<Range>().unwrap();
+
+        let filter = |s: &str| {
+            let filter = s.parse::<CategoryFilter>().unwrap();
+            range.filter_by_category(&board, &filter).unwrap()
+        };
+
+        // removes the combos that conflict with the board
+        let expected = |s: &str| {
+            let filter = "pair+, highcard".parse::<CategoryFilter>().unwrap();
+            let range = s.parse::<Range>().unwrap();
+            range.filter_by_category(&board, &filter).unwrap()
+        };
+
+        assert_eq!(filter("set"), expected("QQ,99,55"));
+        assert_eq!(filter("toppair"), expected("AQ"));
+        assert_eq!(filter("TPTK"), expected("AQ"));
+        assert_eq!(filter("two pair+"), expected("QQ,99,55"));
+        assert_eq!(filter("pair"), expected("JJ-TT,88-66,AQ,98"));
+        assert_eq!(filter("gutshot"), expected("KJ,T8"));
+        assert_eq!(filter("oesd, gutshot & weak_pair"), Range::new());
+        assert_eq!(
+            filter("nut flush draw, pocket pair below top pair"),
+            expected("JJ-TT,Ah2h")
+        );
+        assert_eq!(filter("high-card & draw"), expected("KJ,T8,Ah2h"));
+        assert_eq!(filter("flush+ , highcard & overcards"), Range::new());
+
+        let mask = category_mask(&board, &"set".parse().unwrap()).unwrap();
+        assert_eq!(mask.len(), 1326);
+        assert_eq!(mask.iter().filter(|&&m| m).count(), 3 * 3);
+
+        let error_tests = ["", "toppair,", "top pair kicker", "draw+", "flushdraw&"];
+        for s in error_tests {
+            assert!(s.parse::<CategoryFilter>().is_err(), "{s}");
+        }
+        let filter = "set".parse().unwrap();
+        assert!(range.filter_by_category(&cards("QhQh5c"), &filter).is_err());
+    }
+}
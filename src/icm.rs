@@ -0,0 +1,359 @@
+use crate::error::*;
+use std::fmt;
+use std::sync::Arc;
+
+/// Maximum number of players with chips supported by [`icm_equity`].
+pub const MAX_ICM_PLAYERS: usize = 20;
+
+/// A struct representing the mapping from chip stacks to dollar equity at the terminal nodes.
+///
+/// By default, [`PostFlopGame`] pays out the chips in the pot (minus the rake), i.e., the utility
+/// is linear in chips. In tournaments, the value of chips is not linear, so the solver should
+/// maximize the dollar equity instead. When a terminal payoff is set, the stacks of the two players
+/// after the hand are computed at each terminal node, and the utility is the dollar equity given by
+/// the payoff model.
+///
+/// `stacks` contains the chip stacks of all remaining players. The first two elements are the
+/// stacks of OOP and IP behind the starting pot (i.e., at the root node), and the rest are the
+/// stacks of the players who are not involved in the hand. The chips in the starting pot, including
+/// the dead money, go to the winner of the hand.
+///
+/// The utility is measured relative to the equity when the starting pot is split evenly between OOP
+/// and IP; this reduces to the usual chip utility when the payoff model is the identity function.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// // spin format: three players, winner takes all
+/// let payoff = TerminalPayoff::icm(vec![300.0, 200.0, 500.0], vec![1.0]).unwrap();
+/// let equity = payoff.equity(&[300.0, 200.0, 500.0]);
+/// assert!((equity[2] - 0.5).abs() < 1e-9);
+///
+/// // user-provided monotone function of the chip stack
+/// let payoff = TerminalPayoff::custom(vec![300.0, 200.0], |chips| chips.sqrt()).unwrap();
+/// assert_eq!(payoff.equity(&[400.0, 100.0]), vec![20.0, 10.0]);
+/// ```
+///
+/// [`PostFlopGame`]: crate::PostFlopGame
+#[derive(Clone)]
+pub struct TerminalPayoff {
+    stacks: Vec<f64>,
+    model: PayoffModel,
+}
+
+/// Available payoff models of [`TerminalPayoff`].
+#[derive(Clone)]
+pub enum PayoffModel {
+    /// Independent Chip Model (Malmuth-Harville) with the given payout table.
+    ///
+    /// The `i`-th element is the prize for the `(i + 1)`-th place.
+    Icm(Vec<f64>),
+
+    /// User-provided monotone function that maps a chip stack to its dollar value.
+    Custom(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+impl TerminalPayoff {
+    /// Creates a terminal payoff that converts chip stacks into dollar equity with ICM.
+    ///
+    /// Returns an error if there are less than two stacks, a stack is negative, more than
+    /// [`MAX_ICM_PLAYERS`] players have chips, or the payouts are negative or not in
+    /// non-increasing order.
+    pub fn icm(stacks: Vec<f64>, payouts: Vec<f64>) -> Result<Self, Error> {
+        check_stacks(&stacks)?;
+
+        if stacks.iter().filter(|&&s| s > 0.0).count() > MAX_ICM_PLAYERS {
+            return Err(Error::InvalidConfig(format!(
+                "Too many players: ICM supports up to {MAX_ICM_PLAYERS} players"
+            )));
+        }
+
+        if payouts.is_empty() {
+            return Err(Error::InvalidConfig("Payouts are empty".to_string()));
+        }
+
+        if payouts.iter().any(|&p| !p.is_finite() || p < 0.0) {
+            return Err(Error::InvalidConfig(format!(
+                "Invalid payouts: {payouts:?}"
+            )));
+        }
+
+        if payouts.windows(2).any(|pair| pair[0] < pair[1]) {
+            return Err(Error::InvalidConfig(format!(
+                "Payouts must be in non-increasing order: {payouts:?}"
+            )));
+        }
+
+        Ok(Self {
+            stacks,
+            model: PayoffModel::Icm(payouts),
+        })
+    }
+
+    /// Creates a terminal payoff that converts each chip stack into dollars with the given
+    /// function.
+    ///
+    /// The function must be monotone non-decreasing; otherwise, the resulting strategies are
+    /// meaningless. Returns an error if there are less than two stacks or a stack is negative.
+    pub fn custom<F>(stacks: Vec<f64>, chip_value: F) -> Result<Self, Error>
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        check_stacks(&stacks)?;
+        Ok(Self {
+            stacks,
+            model: PayoffModel::Custom(Arc::new(chip_value)),
+        })
+    }
+
+    /// Returns the chip stacks of all remaining players at the root node.
+    #[inline]
+    pub fn stacks(&self) -> &[f64] {
+        &self.stacks
+    }
+
+    /// Returns the payoff model.
+    #[inline]
+    pub fn model(&self) -> &PayoffModel {
+        &self.model
+    }
+
+    /// Computes the dollar equity of each player from the given chip stacks.
+    #[inline]
+    pub fn equity(&self, stacks: &[f64]) -> Vec<f64> {
+        match &self.model {
+            PayoffModel::Icm(payouts) => icm_equity(stacks, payouts),
+            PayoffModel::Custom(chip_value) => stacks.iter().map(|&s| chip_value(s)).collect(),
+        }
+    }
+
+    /// Returns the payoffs of `player` when the player wins, loses, and ties the pot, relative to
+    /// the equity when the starting pot is split evenly.
+    pub(crate) fn relative_payoffs(
+        &self,
+        player: usize,
+        starting_pot: f64,
+        amount: f64,
+        rake: f64,
+    ) -> [f64; 3] {
+        let opponent = player ^ 1;
+        let equity_after = |player_stack: f64, opponent_stack: f64| {
+            let mut stacks = self.stacks.clone();
+            stacks[player] += player_stack;
+            stacks[opponent] += opponent_stack;
+            self.equity(&stacks)[player]
+        };
+
+        let half_pot = 0.5 * starting_pot;
+        let base = equity_after(half_pot, half_pot);
+        let win = equity_after(starting_pot + amount - rake, -amount);
+        let lose = equity_after(-amount, starting_pot + amount - rake);
+        let tie = equity_after(half_pot - 0.5 * rake, half_pot - 0.5 * rake);
+
+        [win - base, lose - base, tie - base]
+    }
+
+    /// Returns the dollar equity of `player` when the starting pot is split evenly.
+    pub(crate) fn base_equity(&self, player: usize, starting_pot: f64) -> f64 {
+        let mut stacks = self.stacks.clone();
+        stacks[0] += 0.5 * starting_pot;
+        stacks[1] += 0.5 * starting_pot;
+        self.equity(&stacks)[player]
+    }
+
+    /// Returns the terminal payoff after both players have put `amount` into the pot.
+    pub(crate) fn after_bets(&self, amount: f64) -> Self {
+        let mut ret = self.clone();
+        ret.stacks[0] -= amount;
+        ret.stacks[1] -= amount;
+        ret
+    }
+}
+
+impl fmt::Debug for TerminalPayoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalPayoff")
+            .field("stacks", &self.stacks)
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+impl fmt::Debug for PayoffModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayoffModel::Icm(payouts) => f.debug_tuple("Icm").field(payouts).finish(),
+            PayoffModel::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Computes the dollar equity of each player with the Independent Chip Model (Malmuth-Harville).
+///
+/// The `i`-th element of `payouts` is the prize for the `(i + 1)`-th place; missing places pay
+/// nothing. Players with no chips are treated as eliminated and share the prizes of the lowest
+/// places evenly.
+///
+/// **Time complexity:** *O*(2^*n* *n*), where *n* is the number of players with chips.
+///
+/// Panics if more than [`MAX_ICM_PLAYERS`] players have chips.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let equity = icm_equity(&[50.0, 30.0, 20.0], &[0.5, 0.3, 0.2]);
+/// assert!((equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+/// assert!(equity[0] < 0.5 && equity[2] > 0.2);
+/// ```
+pub fn icm_equity(stacks: &[f64], payouts: &[f64]) -> Vec<f64> {
+    let alive = (0..stacks.len())
+        .filter(|&i| stacks[i] > 0.0)
+        .collect::<Vec<_>>();
+    let num_alive = alive.len();
+
+    if num_alive > MAX_ICM_PLAYERS {
+        panic!("Too many players: ICM supports up to {MAX_ICM_PLAYERS} players");
+    }
+
+    let payout = |place: usize| payouts.get(place).cloned().unwrap_or(0.0);
+    let mut ret = vec![0.0; stacks.len()];
+
+    // eliminated players share the prizes of the lowest places
+    let num_eliminated = stacks.len() - num_alive;
+    if num_eliminated > 0 {
+        let shared = (num_alive..stacks.len()).map(payout).sum::<f64>() / num_eliminated as f64;
+        for (r, &s) in ret.iter_mut().zip(stacks) {
+            if s <= 0.0 {
+                *r = shared;
+            }
+        }
+    }
+
+    let num_places = num_alive.min(payouts.len());
+    if num_places == 0 {
+        return ret;
+    }
+
+    // `prob[mask]`: probability that the players in `mask` take the top `popcount(mask)` places
+    let total = alive.iter().map(|&i| stacks[i]).sum::<f64>();
+    let mut prob = vec![0.0; 1 << num_alive];
+    let mut mask_sum = vec![0.0; 1 << num_alive];
+    prob[0] = 1.0;
+
+    for mask in 0..1usize << num_alive {
+        if mask != 0 {
+            let lowest = mask.trailing_zeros() as usize;
+            mask_sum[mask] = mask_sum[mask & (mask - 1)] + stacks[alive[lowest]];
+        }
+
+        let p = prob[mask];
+        let place = mask.count_ones() as usize;
+        if p == 0.0 || place >= num_places {
+            continue;
+        }
+
+        let remaining = total - mask_sum[mask];
+        for (i, &player) in alive.iter().enumerate() {
+            if mask & (1 << i) == 0 {
+                let q = p * stacks[player] / remaining;
+                ret[player] += q * payout(place);
+                prob[mask | 1 << i] += q;
+            }
+        }
+    }
+
+    ret
+}
+
+#[inline]
+fn check_stacks(stacks: &[f64]) -> Result<(), Error> {
+    if stacks.len() < 2 {
+        return Err(Error::InvalidConfig(
+            "Stacks of at least two players are required".to_string(),
+        ));
+    }
+
+    if stacks.iter().any(|&s| !s.is_finite() || s < 0.0) {
+        return Err(Error::InvalidConfig(format!("Invalid stacks: {stacks:?}")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn icm_basic() {
+        // winner takes all: equity is proportional to chips
+        assert_close(&icm_equity(&[30.0, 10.0], &[1.0]), &[0.75, 0.25]);
+        assert_close(&icm_equity(&[50.0, 30.0, 20.0], &[1.0]), &[0.5, 0.3, 0.2]);
+
+        // hand-computed values
+        let equity = icm_equity(&[50.0, 30.0, 20.0], &[0.5, 0.3, 0.2]);
+        let second = 0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0;
+        let third = 1.0 - 0.5 - second;
+        assert!((equity[0] - (0.25 + 0.3 * second + 0.2 * third)).abs() < 1e-9);
+        assert!((equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // equal stacks
+        assert_close(
+            &icm_equity(&[10.0, 10.0, 10.0], &[0.6, 0.4]),
+            &[1.0 / 3.0; 3],
+        );
+    }
+
+    #[test]
+    fn icm_eliminated() {
+        // busted player takes the last place
+        assert_close(
+            &icm_equity(&[40.0, 0.0, 20.0], &[0.5, 0.3, 0.2]),
+            &[
+                0.5 * 2.0 / 3.0 + 0.3 / 3.0,
+                0.2,
+                0.5 / 3.0 + 0.3 * 2.0 / 3.0,
+            ],
+        );
+
+        // two busted players share the last two places
+        assert_close(
+            &icm_equity(&[0.0, 30.0, 0.0], &[0.5, 0.3, 0.2]),
+            &[0.25, 0.5, 0.25],
+        );
+    }
+
+    #[test]
+    fn terminal_payoff() {
+        assert!(TerminalPayoff::icm(vec![100.0], vec![1.0]).is_err());
+        assert!(TerminalPayoff::icm(vec![100.0, -1.0], vec![1.0]).is_err());
+        assert!(TerminalPayoff::icm(vec![100.0, 100.0], vec![]).is_err());
+        assert!(TerminalPayoff::icm(vec![100.0, 100.0], vec![0.3, 0.7]).is_err());
+        assert!(TerminalPayoff::icm(vec![1.0; MAX_ICM_PLAYERS + 1], vec![1.0]).is_err());
+
+        // identity function reproduces the chip utility
+        let payoff = TerminalPayoff::custom(vec![100.0, 200.0], |chips| chips).unwrap();
+        let [win, lose, tie] = payoff.relative_payoffs(0, 40.0, 30.0, 5.0);
+        assert!((win - (20.0 + 30.0 - 5.0)).abs() < 1e-9);
+        assert!((lose + (20.0 + 30.0)).abs() < 1e-9);
+        assert!((tie + 2.5).abs() < 1e-9);
+
+        // losing an all-in on the bubble costs more than winning gains
+        let payoff = TerminalPayoff::icm(vec![100.0, 100.0, 100.0], vec![0.7, 0.3]).unwrap();
+        let [win, lose, tie] = payoff.relative_payoffs(1, 0.0, 100.0, 0.0);
+        assert!(win > 0.0 && lose < 0.0 && win < -lose);
+        assert_eq!(tie, 0.0);
+
+        let payoff = payoff.after_bets(20.0);
+        assert_eq!(payoff.stacks(), [80.0, 80.0, 100.0]);
+    }
+}
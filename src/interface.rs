@@ -77,6 +77,36 @@ pub trait Game: Send + Sync {
     fn is_compression_enabled(&self) -> bool {
         false
     }
+
+    /// Returns the allocated memory in bytes.
+    #[doc(hidden)]
+    fn allocated_memory(&self) -> u64 {
+        0
+    }
+
+    /// Returns the player who can choose to terminate at the root of the re-solving gadget.
+    #[doc(hidden)]
+    fn gadget_player(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the counterfactual values of terminating at the root of the re-solving gadget.
+    #[doc(hidden)]
+    fn gadget_values(&self) -> &[f32] {
+        unreachable!()
+    }
+
+    /// Returns the cumulative regrets of the re-solving gadget (terminate, follow).
+    #[doc(hidden)]
+    fn gadget_regrets(&self) -> MutexGuardLike<'_, Vec<f32>> {
+        unreachable!()
+    }
+
+    /// Returns whether the prediction storage for predictive algorithms is allocated.
+    #[doc(hidden)]
+    fn is_prediction_enabled(&self) -> bool {
+        false
+    }
 }
 
 /// The trait representing a node in game tree.
@@ -125,6 +155,18 @@ pub trait GameNode: Send + Sync {
     #[doc(hidden)]
     fn cfvalues_mut(&mut self) -> &mut [f32];
 
+    /// Returns the predicted regrets (instantaneous regrets of the last iteration).
+    #[doc(hidden)]
+    fn predictions(&self) -> &[f32] {
+        unreachable!()
+    }
+
+    /// Returns the mutable reference to the predicted regrets.
+    #[doc(hidden)]
+    fn predictions_mut(&mut self) -> &mut [f32] {
+        unreachable!()
+    }
+
     /// Returns whether IP's counterfactual values are stored.
     #[doc(hidden)]
     fn has_cfvalues_ip(&self) -> bool {
@@ -10,6 +10,8 @@
 //! - **Algorithm**: The solver uses the state-of-the-art [Discounted CFR] algorithm.
 //!   Currently, the value of γ is set to 3.0 instead of the 2.0 recommended in the original paper.
 //!   Also, the solver resets the cumulative strategy when the number of iterations is a power of 4.
+//!   Other CFR variants (vanilla CFR, CFR+, Linear CFR, DCFR with custom parameters, and Predictive CFR+)
+//!   can be selected by passing a [`CfrAlgorithm`] to [`solve_with`] or [`solve_step_with`].
 //! - **Performance**: The solver engine is highly optimized for performance with maintainable code.
 //!   The engine supports multithreading by default, and it takes full advantage of unsafe Rust in hot spots.
 //!   The developer reviews the assembly output from the compiler and ensures that SIMD instructions are used as much as possible.
@@ -28,6 +30,15 @@
 //!   such as manipulating the probability distribution of the deck.
 //!   Note, however, that enabling the bunching effect increases the time complexity
 //!   of the evaluation at the terminal nodes and slows down the computation significantly.
+//! - **Three-way pots**: [`ThreeWayGame`] solves river spots with three players, including side pots
+//!   when a player is all-in for less. It is solved by [`solve_three_way`] without isomorphism or compression,
+//!   so the ranges should be kept small.
+//! - **Pot-limit Omaha**: [`OmahaGame`] solves postflop spots with four-card private hands, where
+//!   exactly two hole cards and three board cards must be used at showdown.
+//!   Pot-limit betting is enabled by [`TreeConfig::pot_limit`].
+//! - **Tournament payoffs**: By default, the utility at the terminal nodes is linear in chips.
+//!   A [`TerminalPayoff`] converts the chip stacks after the hand into dollar equity with ICM
+//!   or a user-provided monotone function (see [`PostFlopGame::set_terminal_payoff`]).
 //!
 //! [Discounted CFR]: https://arxiv.org/abs/1809.04040
 //!
@@ -64,18 +75,28 @@ mod atomic_float;
 mod bet_size;
 mod bunching;
 mod card;
+mod equity;
+mod error;
 mod file_output;
 mod file_output2;
 mod game;
 mod hand;
+mod hand_category;
 mod hand_strength;
 mod hand_table;
+mod icm;
 mod interface;
 mod mutex_like;
+mod omaha;
 mod range;
+mod range_io;
 mod results;
 mod sliceop;
 mod solver;
+mod three_way;
+mod tree_diff;
+mod tree_io;
+mod tree_lint;
 mod utility;
 mod utils;
 
@@ -86,14 +107,24 @@ pub use action_tree::*;
 pub use bet_size::*;
 pub use bunching::*;
 pub use card::*;
+pub use equity::*;
+pub use error::*;
 pub use file_output::*;
 pub use file_output2::*;
 pub use game::*;
+pub use hand_category::*;
 pub use hand_strength::*;
+pub use icm::*;
 pub use interface::*;
 pub use mutex_like::*;
+pub use omaha::*;
 pub use range::*;
+pub use range_io::*;
 pub use results::*;
 pub use solver::*;
+pub use three_way::*;
+pub use tree_diff::*;
+pub use tree_io::*;
+pub use tree_lint::*;
 pub use utility::*;
 pub use utils::*;
@@ -0,0 +1,585 @@
+use super::*;
+use crate::error::*;
+use crate::interface::*;
+use crate::utility::*;
+use std::mem::{self, MaybeUninit};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+impl Game for OmahaGame {
+    type Node = OmahaNode;
+
+    #[inline]
+    fn root(&self) -> MutexGuardLike<'_, Self::Node> {
+        self.root.lock()
+    }
+
+    #[inline]
+    fn num_private_hands(&self, player: usize) -> usize {
+        self.private_cards[player].len()
+    }
+
+    #[inline]
+    fn initial_weights(&self, player: usize) -> &[f32] {
+        &self.initial_weights[player]
+    }
+
+    #[inline]
+    fn evaluate(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &Self::Node,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        self.evaluate_internal(result, node, player, cfreach);
+    }
+
+    #[inline]
+    fn chance_factor(&self, node: &Self::Node) -> usize {
+        // the eight private cards are never dealt to the board
+        if node.turn == NOT_DEALT {
+            52 - 3 - 8
+        } else {
+            52 - 4 - 8
+        }
+    }
+
+    #[inline]
+    fn is_solved(&self) -> bool {
+        self.is_solved
+    }
+
+    #[inline]
+    fn set_solved(&mut self) {
+        self.is_solved = true;
+    }
+
+    #[inline]
+    fn is_ready(&self) -> bool {
+        self.is_memory_allocated && !self.is_solved
+    }
+
+    #[inline]
+    fn is_raked(&self) -> bool {
+        self.tree_config.rake_rate > 0.0 && self.tree_config.rake_cap > 0.0
+    }
+}
+
+impl GameNode for OmahaNode {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        self.player & PLAYER_TERMINAL_FLAG != 0
+    }
+
+    #[inline]
+    fn is_chance(&self) -> bool {
+        self.player & PLAYER_CHANCE_FLAG != 0
+    }
+
+    #[inline]
+    fn player(&self) -> usize {
+        self.player as usize
+    }
+
+    #[inline]
+    fn num_actions(&self) -> usize {
+        self.children.len()
+    }
+
+    #[inline]
+    fn play(&self, action: usize) -> MutexGuardLike<'_, Self> {
+        self.children[action].lock()
+    }
+
+    #[inline]
+    fn strategy(&self) -> &[f32] {
+        &self.strategy
+    }
+
+    #[inline]
+    fn strategy_mut(&mut self) -> &mut [f32] {
+        &mut self.strategy
+    }
+
+    #[inline]
+    fn regrets(&self) -> &[f32] {
+        &self.storage
+    }
+
+    #[inline]
+    fn regrets_mut(&mut self) -> &mut [f32] {
+        &mut self.storage
+    }
+
+    #[inline]
+    fn cfvalues(&self) -> &[f32] {
+        &self.storage
+    }
+
+    #[inline]
+    fn cfvalues_mut(&mut self) -> &mut [f32] {
+        &mut self.storage
+    }
+
+    #[inline]
+    fn enable_parallelization(&self) -> bool {
+        self.river == NOT_DEALT
+    }
+}
+
+impl OmahaGame {
+    /// Creates a new empty [`OmahaGame`] (needs `update_config()` before solving).
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`OmahaGame`] with the specified configuration.
+    #[inline]
+    pub fn with_config(
+        card_config: OmahaCardConfig,
+        action_tree: ActionTree,
+    ) -> Result<Self, Error> {
+        let mut game = Self::new();
+        game.update_config(card_config, action_tree)?;
+        Ok(game)
+    }
+
+    /// Updates the game configuration. The solved result will be lost.
+    pub fn update_config(
+        &mut self,
+        card_config: OmahaCardConfig,
+        action_tree: ActionTree,
+    ) -> Result<(), Error> {
+        self.is_memory_allocated = false;
+        self.is_solved = false;
+        self.card_config = card_config;
+        let (tree_config, _, _, action_root) = action_tree.eject();
+        self.tree_config = tree_config;
+
+        self.check_card_config()?;
+        self.init_hands();
+
+        if self.num_combinations == 0.0 {
+            return Err(Error::InvalidRange(
+                "Valid card assignment does not exist".to_string(),
+            ));
+        }
+
+        let config = &self.card_config;
+        let mut root = OmahaNode {
+            turn: config.turn,
+            river: config.river,
+            ..Default::default()
+        };
+
+        let mut board_mask = self.flop_mask;
+        if config.turn != NOT_DEALT {
+            board_mask |= 1 << config.turn;
+        }
+        if config.river != NOT_DEALT {
+            board_mask |= 1 << config.river;
+        }
+
+        Self::build_tree_recursive(&mut root, &action_root.lock(), board_mask);
+        *self.root = MutexLike::new(root);
+        self.back_to_root();
+
+        Ok(())
+    }
+
+    /// Obtains the card configuration.
+    #[inline]
+    pub fn card_config(&self) -> &OmahaCardConfig {
+        &self.card_config
+    }
+
+    /// Obtains the tree configuration.
+    #[inline]
+    pub fn tree_config(&self) -> &TreeConfig {
+        &self.tree_config
+    }
+
+    /// Returns the private hands of the given player.
+    #[inline]
+    pub fn private_cards(&self, player: usize) -> &[[Card; 4]] {
+        &self.private_cards[player]
+    }
+
+    /// Returns the memory usage in bytes when the memory is allocated.
+    #[inline]
+    pub fn memory_usage(&self) -> u64 {
+        self.memory_usage_recursive(&self.root.lock())
+    }
+
+    /// Allocates the memory for the cumulative strategy and regrets.
+    #[inline]
+    pub fn allocate_memory(&mut self) {
+        let num_hands = [self.num_private_hands(0), self.num_private_hands(1)];
+        Self::allocate_memory_recursive(&mut self.root.lock(), &num_hands);
+        self.is_memory_allocated = true;
+        self.is_solved = false;
+    }
+
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.action_history.clear();
+    }
+
+    /// Returns the history of the current node (indices of the played actions).
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.action_history
+    }
+
+    /// Plays the given action. Playing an action from a terminal node is not allowed.
+    ///
+    /// - `action`
+    ///   - If the current node is a chance node, the `action` corresponds to the dealt card (see
+    ///     [`Card`] for encoding). If `usize::MAX` is passed, the card is selected as the possible
+    ///     card with the lowest index.
+    ///   - If the current node is not a chance node, plays the `action`-th action of
+    ///     [`available_actions`].
+    ///
+    /// [`available_actions`]: #method.available_actions
+    pub fn play(&mut self, action: usize) {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        let node = self.node();
+        let index = if node.is_chance() {
+            let position = if action == usize::MAX {
+                Some(0)
+            } else {
+                node.actions
+                    .iter()
+                    .position(|&a| a == Action::Chance(action as Card))
+            };
+            position.unwrap_or_else(|| panic!("Invalid card: {action}"))
+        } else if action < node.num_actions() {
+            action
+        } else {
+            panic!("Invalid action");
+        };
+
+        self.action_history.push(index);
+    }
+
+    /// Returns whether the current node is a terminal node.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.node().is_terminal()
+    }
+
+    /// Returns whether the current node is a chance node.
+    #[inline]
+    pub fn is_chance_node(&self) -> bool {
+        self.node().is_chance()
+    }
+
+    /// Returns the current player (`0` for OOP, `1` for IP).
+    ///
+    /// Panics if the current node is a terminal node or a chance node.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        let node = self.node();
+        if node.is_terminal() || node.is_chance() {
+            panic!("Terminal and chance nodes are not allowed");
+        }
+        node.player()
+    }
+
+    /// Returns the available actions of the current node.
+    ///
+    /// If the current node is a chance node, returns the possible deals as [`Action::Chance`].
+    #[inline]
+    pub fn available_actions(&self) -> Vec<Action> {
+        self.node().actions.clone()
+    }
+
+    /// Returns the board cards of the current node.
+    #[inline]
+    pub fn current_board(&self) -> Vec<Card> {
+        let node = self.node();
+        let mut ret = self.card_config.flop.to_vec();
+        if node.turn != NOT_DEALT {
+            ret.push(node.turn);
+        }
+        if node.river != NOT_DEALT {
+            ret.push(node.river);
+        }
+        ret
+    }
+
+    /// Returns the amount that each player has bet in the current node.
+    #[inline]
+    pub fn total_bet_amount(&self) -> i32 {
+        self.node().amount
+    }
+
+    /// Returns the average strategy of the current node.
+    ///
+    /// The return value is a vector of the length of `#(actions) * #(private hands)`. The
+    /// probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    ///
+    /// Panics if the memory is not allocated or the current node is a terminal node or a chance
+    /// node.
+    #[inline]
+    pub fn strategy(&self) -> Vec<f32> {
+        if !self.is_memory_allocated {
+            panic!("Memory is not allocated");
+        }
+
+        let node = self.node();
+        if node.is_terminal() || node.is_chance() {
+            panic!("Terminal and chance nodes are not allowed");
+        }
+
+        normalized_strategy(&node.strategy, node.num_actions())
+    }
+
+    /// Returns the reference to the current node.
+    #[inline]
+    fn node(&self) -> MutexGuardLike<'_, OmahaNode> {
+        unsafe {
+            let mut node = &*self.root as *const MutexLike<OmahaNode>;
+            for &action in &self.action_history {
+                node = &(*node).lock().children[action];
+            }
+            (*node).lock()
+        }
+    }
+
+    /// Checks the card configuration.
+    fn check_card_config(&self) -> Result<(), Error> {
+        let config = &self.card_config;
+        let (flop, turn, river) = (config.flop, config.turn, config.river);
+
+        if flop.contains(&NOT_DEALT) {
+            return Err(Error::InvalidCard("Flop cards not initialized".to_string()));
+        }
+
+        if flop.iter().any(|&c| 52 <= c) {
+            return Err(Error::InvalidCard(format!(
+                "Flop cards must be in [0, 52): flop = {flop:?}"
+            )));
+        }
+
+        if flop[0] == flop[1] || flop[0] == flop[2] || flop[1] == flop[2] {
+            return Err(Error::InvalidCard(format!(
+                "Flop cards must be unique: flop = {flop:?}"
+            )));
+        }
+
+        if turn != NOT_DEALT && (52 <= turn || flop.contains(&turn)) {
+            return Err(Error::InvalidCard(format!(
+                "Invalid turn card: turn = {turn}"
+            )));
+        }
+
+        if river != NOT_DEALT {
+            if 52 <= river || flop.contains(&river) || turn == river {
+                return Err(Error::InvalidCard(format!(
+                    "Invalid river card: river = {river}"
+                )));
+            }
+
+            if turn == NOT_DEALT {
+                return Err(Error::InvalidCard(format!(
+                    "River card specified without turn card: river = {river}"
+                )));
+            }
+        }
+
+        let expected_state = match (turn != NOT_DEALT, river != NOT_DEALT) {
+            (false, _) => BoardState::Flop,
+            (true, false) => BoardState::Turn,
+            (true, true) => BoardState::River,
+        };
+
+        if self.tree_config.initial_state != expected_state {
+            return Err(Error::InvalidConfig(format!(
+                "Invalid initial state of `tree_config`: expected = {:?}, actual = {:?}",
+                expected_state, self.tree_config.initial_state
+            )));
+        }
+
+        for (player, range) in config.range.iter().enumerate() {
+            if range.is_empty() {
+                return Err(Error::InvalidRange(format!(
+                    "Empty range (player = {player})"
+                )));
+            }
+
+            if !range.is_valid() {
+                return Err(Error::InvalidRange(format!(
+                    "Invalid range (player = {player})"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Initializes fields related to private hands.
+    fn init_hands(&mut self) {
+        let config = &self.card_config;
+        self.flop_mask = config.flop.iter().fold(0, |mask, &c| mask | (1 << c));
+
+        let mut board_mask = self.flop_mask;
+        if config.turn != NOT_DEALT {
+            board_mask |= 1 << config.turn;
+        }
+        if config.river != NOT_DEALT {
+            board_mask |= 1 << config.river;
+        }
+
+        for player in 0..2 {
+            let (hands, weights) = config.range[player].get_hands_weights(board_mask);
+            self.hand_masks[player] = hands.iter().map(|&hand| hand_mask(hand)).collect();
+            self.private_cards[player] = hands;
+            self.initial_weights[player] = weights;
+        }
+
+        // count the number of valid card assignments
+        let mut num_combinations = 0.0;
+        for (&mask0, &w0) in self.hand_masks[0].iter().zip(&self.initial_weights[0]) {
+            for (&mask1, &w1) in self.hand_masks[1].iter().zip(&self.initial_weights[1]) {
+                if mask0 & mask1 == 0 {
+                    num_combinations += w0 as f64 * w1 as f64;
+                }
+            }
+        }
+        self.num_combinations = num_combinations;
+
+        // precompute the hand strengths for each possible (turn, river) pair
+        let mut boards = Vec::new();
+        if config.river != NOT_DEALT {
+            boards.push((config.turn, config.river));
+        } else {
+            for river in 0..52 {
+                if board_mask & (1 << river) != 0 {
+                    continue;
+                }
+                if config.turn != NOT_DEALT {
+                    boards.push((config.turn, river));
+                } else {
+                    for turn in 0..river {
+                        if board_mask & (1 << turn) == 0 {
+                            boards.push((turn, river));
+                        }
+                    }
+                }
+            }
+        }
+
+        let strengths = into_par_iter(0..boards.len())
+            .map(|i| {
+                let (turn, river) = boards[i];
+                let board = [config.flop[0], config.flop[1], config.flop[2], turn, river];
+                let board_mask: u64 = (1 << turn) | (1 << river);
+                [0, 1].map(|player| {
+                    self.private_cards[player]
+                        .iter()
+                        .zip(&self.hand_masks[player])
+                        .map(|(hand, &mask)| {
+                            if mask & board_mask == 0 {
+                                evaluate_omaha(hand, &board)
+                            } else {
+                                0
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        self.hand_strength = vec![Default::default(); 52 * 51 / 2];
+        for (&(turn, river), strength) in boards.iter().zip(strengths) {
+            self.hand_strength[card_pair_to_index(turn, river)] = strength;
+        }
+    }
+
+    /// Builds the game tree from the action tree, expanding the chance nodes into the deals.
+    fn build_tree_recursive(node: &mut OmahaNode, action_node: &ActionTreeNode, board_mask: u64) {
+        node.player = action_node.player;
+        node.amount = action_node.amount;
+
+        if node.is_chance() {
+            let action_child = action_node.children[0].lock();
+            for card in 0..52 {
+                if board_mask & (1 << card) != 0 {
+                    continue;
+                }
+
+                let mut child = OmahaNode {
+                    turn: node.turn,
+                    river: node.river,
+                    ..Default::default()
+                };
+
+                if node.turn == NOT_DEALT {
+                    child.turn = card;
+                } else {
+                    child.river = card;
+                }
+
+                Self::build_tree_recursive(&mut child, &action_child, board_mask | (1 << card));
+                node.actions.push(Action::Chance(card));
+                node.children.push(MutexLike::new(child));
+            }
+        } else {
+            node.actions = action_node.actions.clone();
+            for action_child in &action_node.children {
+                let mut child = OmahaNode {
+                    turn: node.turn,
+                    river: node.river,
+                    ..Default::default()
+                };
+                Self::build_tree_recursive(&mut child, &action_child.lock(), board_mask);
+                node.children.push(MutexLike::new(child));
+            }
+        }
+    }
+
+    fn allocate_memory_recursive(node: &mut OmahaNode, num_hands: &[usize; 2]) {
+        if node.is_terminal() {
+            return;
+        }
+
+        if !node.is_chance() {
+            let num_elements = node.num_actions() * num_hands[node.player()];
+            node.strategy = vec![0.0; num_elements];
+            node.storage = vec![0.0; num_elements];
+        }
+
+        for child in &node.children {
+            Self::allocate_memory_recursive(&mut child.lock(), num_hands);
+        }
+    }
+
+    fn memory_usage_recursive(&self, node: &OmahaNode) -> u64 {
+        if node.is_terminal() {
+            return 0;
+        }
+
+        let storage = if node.is_chance() {
+            0
+        } else {
+            let num_hands = self.num_private_hands(node.player());
+            2 * (node.num_actions() * num_hands * mem::size_of::<f32>()) as u64
+        };
+
+        storage
+            + node
+                .children
+                .iter()
+                .map(|child| self.memory_usage_recursive(&child.lock()))
+                .sum::<u64>()
+    }
+}
@@ -0,0 +1,139 @@
+use super::*;
+use crate::hand::*;
+use std::mem::MaybeUninit;
+
+/// Evaluates a pot-limit Omaha hand; a larger value means a stronger hand.
+///
+/// The best five-card hand is formed from exactly two of the four hole cards and exactly three of
+/// the five board cards. The returned value is only meaningful for comparisons between hands.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let board = [0, 2, 4, 6, 8].map(|i| card_from_str(&"AhKhQh2h3c"[i..i + 2]).unwrap());
+/// let cards = |s: &str| [0, 2, 4, 6].map(|i| card_from_str(&s[i..i + 2]).unwrap());
+///
+/// // a single heart in hand does not make a flush
+/// let one_heart = evaluate_omaha(&cards("JhAsAdAc"), &board);
+/// let two_hearts = evaluate_omaha(&cards("Jh4h7s7d"), &board);
+/// assert!(one_heart < two_hearts);
+/// ```
+pub fn evaluate_omaha(hole: &[Card; 4], board: &[Card; 5]) -> i32 {
+    let mut best = i32::MIN;
+
+    for i in 0..4 {
+        for j in i + 1..4 {
+            let hand = Hand::new()
+                .add_card(hole[i] as usize)
+                .add_card(hole[j] as usize);
+            for a in 0..5 {
+                for b in a + 1..5 {
+                    for c in b + 1..5 {
+                        let hand = hand
+                            .add_card(board[a] as usize)
+                            .add_card(board[b] as usize)
+                            .add_card(board[c] as usize);
+                        best = best.max(hand.evaluate_raw());
+                    }
+                }
+            }
+        }
+    }
+
+    best
+}
+
+impl OmahaGame {
+    pub(super) fn evaluate_internal(
+        &self,
+        result: &mut [MaybeUninit<f32>],
+        node: &OmahaNode,
+        player: usize,
+        cfreach: &[f32],
+    ) {
+        let pot = (self.tree_config.starting_pot + 2 * node.amount) as f64;
+        let half_pot = 0.5 * pot;
+        let rake = (pot * self.tree_config.rake_rate).min(self.tree_config.rake_cap);
+        let amount_win = (half_pot - rake) / self.num_combinations;
+        let amount_lose = -half_pot / self.num_combinations;
+        let amount_tie = -0.5 * rake / self.num_combinations;
+
+        result.iter_mut().for_each(|v| {
+            v.write(0.0);
+        });
+
+        let result = unsafe { &mut *(result as *mut _ as *mut [f32]) };
+
+        let mut board_mask = self.flop_mask;
+        if node.turn != NOT_DEALT {
+            board_mask |= 1 << node.turn;
+        }
+        if node.river != NOT_DEALT {
+            board_mask |= 1 << node.river;
+        }
+
+        let player_masks = &self.hand_masks[player];
+        let opponent_masks = &self.hand_masks[player ^ 1];
+
+        // someone folded
+        if node.player & PLAYER_FOLD_FLAG == PLAYER_FOLD_FLAG {
+            let folded_player = node.player & PLAYER_MASK;
+            let payoff = if folded_player as usize != player {
+                amount_win
+            } else {
+                amount_lose
+            };
+
+            let opponent_hands = opponent_masks
+                .iter()
+                .zip(cfreach)
+                .filter(|&(&mask, &reach)| reach != 0.0 && mask & board_mask == 0)
+                .map(|(&mask, &reach)| (mask, reach as f64))
+                .collect::<Vec<_>>();
+
+            for (r, &mask) in result.iter_mut().zip(player_masks) {
+                if mask & board_mask == 0 {
+                    let cfreach_sum = opponent_hands
+                        .iter()
+                        .filter(|&&(opponent_mask, _)| mask & opponent_mask == 0)
+                        .map(|&(_, reach)| reach)
+                        .sum::<f64>();
+                    *r = (payoff * cfreach_sum) as f32;
+                }
+            }
+        }
+        // showdown
+        else {
+            let strength = &self.hand_strength[card_pair_to_index(node.turn, node.river)];
+
+            let opponent_hands = opponent_masks
+                .iter()
+                .zip(&strength[player ^ 1])
+                .zip(cfreach)
+                .filter(|&((&mask, _), &reach)| reach != 0.0 && mask & board_mask == 0)
+                .map(|((&mask, &strength), &reach)| (mask, strength, reach as f64))
+                .collect::<Vec<_>>();
+
+            for ((r, &mask), &strength) in
+                result.iter_mut().zip(player_masks).zip(&strength[player])
+            {
+                if mask & board_mask == 0 {
+                    let cfvalue = opponent_hands
+                        .iter()
+                        .filter(|&&(opponent_mask, _, _)| mask & opponent_mask == 0)
+                        .map(|&(_, opponent_strength, reach)| {
+                            let payoff = match strength.cmp(&opponent_strength) {
+                                Ordering::Greater => amount_win,
+                                Ordering::Less => amount_lose,
+                                Ordering::Equal => amount_tie,
+                            };
+                            payoff * reach
+                        })
+                        .sum::<f64>();
+                    *r = cfvalue as f32;
+                }
+            }
+        }
+    }
+}
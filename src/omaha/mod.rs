@@ -0,0 +1,140 @@
+//! Pot-limit Omaha postflop game.
+//!
+//! Each player is dealt four private cards and must use exactly two of them together with exactly
+//! three board cards at showdown. The betting structure is given by an [`ActionTree`], typically
+//! with [`TreeConfig::pot_limit`] enabled.
+//!
+//! The game implements the [`Game`] trait, so it can be solved by [`solve`] and analyzed by
+//! [`compute_exploitability`] like [`PostFlopGame`]. Since there are far more private hands than
+//! in hold'em, isomorphic chances and compression are not supported, and the evaluation at the
+//! terminal nodes takes quadratic time in the range sizes; keep the ranges small.
+//!
+//! [`Game`]: crate::Game
+//! [`solve`]: crate::solve
+//! [`compute_exploitability`]: crate::compute_exploitability
+//! [`PostFlopGame`]: crate::PostFlopGame
+
+mod base;
+mod evaluation;
+mod range;
+
+#[cfg(test)]
+mod tests;
+
+pub use evaluation::*;
+pub use range::*;
+
+use crate::action_tree::*;
+use crate::card::*;
+use crate::mutex_like::*;
+use std::cmp::Ordering;
+
+/// A struct containing the card configuration of a pot-limit Omaha game.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = OmahaCardConfig {
+///     range: [
+///         "AsKsQdJd,AhAdKc9c".parse().unwrap(),
+///         "8s7s6d5d,KdKcTs9h:0.5".parse().unwrap(),
+///     ],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     turn: card_from_str("Qc").unwrap(),
+///     river: NOT_DEALT,
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct OmahaCardConfig {
+    /// Initial range of each player.
+    pub range: [OmahaRange; 2],
+
+    /// Flop cards: each card must be unique and in range [`0`, `52`).
+    pub flop: [Card; 3],
+
+    /// Turn card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    pub turn: Card,
+
+    /// River card: must be in range [`0`, `52`) or `NOT_DEALT`.
+    pub river: Card,
+}
+
+/// A struct representing a pot-limit Omaha postflop game.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = OmahaCardConfig {
+///     range: [
+///         "AsKsQdJd,AhAdKc9c,JhTh8c7c".parse().unwrap(),
+///         "8s7s6d5d,KdKcTs9h,QsQhJc5s".parse().unwrap(),
+///     ],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     turn: card_from_str("Qc").unwrap(),
+///     river: card_from_str("2s").unwrap(),
+/// };
+///
+/// let bet_sizes: BetSizeOptions = ("100%", "100%").try_into().unwrap();
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 400,
+///     river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+///     pot_limit: true,
+///     ..Default::default()
+/// };
+///
+/// let action_tree = ActionTree::new(tree_config).unwrap();
+/// let mut game = OmahaGame::with_config(card_config, action_tree).unwrap();
+/// game.allocate_memory();
+///
+/// let exploitability = solve(&mut game, 1000, 0.5, false);
+/// assert!(exploitability <= 0.5);
+/// ```
+#[derive(Default)]
+pub struct OmahaGame {
+    // configuration
+    card_config: OmahaCardConfig,
+    tree_config: TreeConfig,
+    root: Box<MutexLike<OmahaNode>>,
+
+    // computed from configurations
+    is_memory_allocated: bool,
+    is_solved: bool,
+    flop_mask: u64,
+    private_cards: [Vec<[Card; 4]>; 2],
+    initial_weights: [Vec<f32>; 2],
+    hand_masks: [Vec<u64>; 2],
+    hand_strength: Vec<[Vec<i32>; 2]>,
+    num_combinations: f64,
+
+    // interpreter
+    action_history: Vec<usize>,
+}
+
+/// A struct representing a node in a pot-limit Omaha game tree.
+#[derive(Default)]
+pub struct OmahaNode {
+    player: u8,
+    turn: Card,
+    river: Card,
+    amount: i32,
+    actions: Vec<Action>,
+    children: Vec<MutexLike<OmahaNode>>,
+    strategy: Vec<f32>,
+    storage: Vec<f32>,
+}
+
+impl Default for OmahaCardConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            range: Default::default(),
+            flop: [NOT_DEALT; 3],
+            turn: NOT_DEALT,
+            river: NOT_DEALT,
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use crate::card::*;
+use crate::error::*;
+use crate::range::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// A struct representing a pot-limit Omaha range.
+///
+/// Unlike [`Range`], which has a fixed slot for each of the 1326 hold'em combinations, an
+/// [`OmahaRange`] is a weighted list of four-card combinations because enumerating all 270725
+/// Omaha hands is rarely useful. The cards of each hand are sorted in descending order of card
+/// IDs, and the hands are kept sorted so that the same range always has the same representation.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let range = "AsKsQdJd,7c6c5h4h:0.5".parse::<OmahaRange>().unwrap();
+/// assert_eq!(range.len(), 2);
+///
+/// let hand = [
+///     card_from_str("Jd").unwrap(),
+///     card_from_str("Ks").unwrap(),
+///     card_from_str("Qd").unwrap(),
+///     card_from_str("As").unwrap(),
+/// ];
+/// assert_eq!(range.get_weight(hand), 1.0);
+/// assert_eq!(range.to_string(), "7c6c5h4h:0.5,AsKsQdJd");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OmahaRange {
+    hands: Vec<[Card; 4]>,
+    weights: Vec<f32>,
+}
+
+impl OmahaRange {
+    /// Creates an empty range.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a range from the lists of hands and weights.
+    ///
+    /// Hands with zero weight are dropped. Returns an error if a hand contains an invalid or
+    /// duplicated card, a hand appears twice, or a weight is not in `[0, 1]`.
+    pub fn from_hands_weights(hands: &[[Card; 4]], weights: &[f32]) -> Result<Self, Error> {
+        if hands.len() != weights.len() {
+            return Err(Error::InvalidRange(format!(
+                "Length mismatch: hands = {}, weights = {}",
+                hands.len(),
+                weights.len()
+            )));
+        }
+
+        let mut entries = Vec::with_capacity(hands.len());
+        for (&hand, &weight) in hands.iter().zip(weights) {
+            let hand = canonical_hand(hand)?;
+            check_weight(weight)?;
+            if weight > 0.0 {
+                entries.push((hand, weight));
+            }
+        }
+
+        entries.sort_unstable_by_key(|a| a.0);
+
+        for pair in entries.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(Error::InvalidRange(format!(
+                    "Duplicated hand: {}",
+                    hand_to_string(pair[0].0)
+                )));
+            }
+        }
+
+        Ok(Self {
+            hands: entries.iter().map(|&(hand, _)| hand).collect(),
+            weights: entries.iter().map(|&(_, weight)| weight).collect(),
+        })
+    }
+
+    /// Returns the hands in the range.
+    #[inline]
+    pub fn hands(&self) -> &[[Card; 4]] {
+        &self.hands
+    }
+
+    /// Returns the weights of the hands in the range.
+    #[inline]
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Returns the number of hands in the range.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Returns whether the range is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hands.is_empty()
+    }
+
+    /// Obtains the weight of the given hand (zero if the hand is not in the range).
+    ///
+    /// The card order in the input does not matter.
+    #[inline]
+    pub fn get_weight(&self, hand: [Card; 4]) -> f32 {
+        match canonical_hand(hand) {
+            Ok(hand) => self
+                .hands
+                .binary_search(&hand)
+                .map_or(0.0, |index| self.weights[index]),
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Sets the weight of the given hand. Setting zero weight removes the hand from the range.
+    ///
+    /// The card order in the input does not matter.
+    pub fn set_weight(&mut self, hand: [Card; 4], weight: f32) -> Result<(), Error> {
+        let hand = canonical_hand(hand)?;
+        check_weight(weight)?;
+
+        match self.hands.binary_search(&hand) {
+            Ok(index) if weight > 0.0 => self.weights[index] = weight,
+            Ok(index) => {
+                self.hands.remove(index);
+                self.weights.remove(index);
+            }
+            Err(index) if weight > 0.0 => {
+                self.hands.insert(index, hand);
+                self.weights.insert(index, weight);
+            }
+            Err(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the range is valid.
+    #[inline]
+    pub(crate) fn is_valid(&self) -> bool {
+        self.hands.len() == self.weights.len()
+            && self.hands.windows(2).all(|pair| pair[0] < pair[1])
+            && self
+                .hands
+                .iter()
+                .all(|&hand| canonical_hand(hand) == Ok(hand))
+            && self.weights.iter().all(|&w| 0.0 < w && w <= 1.0)
+    }
+
+    /// Obtains the hands and weights that do not conflict with `dead_cards_mask`.
+    #[inline]
+    pub(crate) fn get_hands_weights(&self, dead_cards_mask: u64) -> (Vec<[Card; 4]>, Vec<f32>) {
+        self.hands
+            .iter()
+            .zip(&self.weights)
+            .filter(|(&hand, _)| hand_mask(hand) & dead_cards_mask == 0)
+            .map(|(&hand, &weight)| (hand, weight))
+            .unzip()
+    }
+}
+
+impl FromStr for OmahaRange {
+    type Err = Error;
+
+    /// Parses a comma-separated list of four-card hands with optional weights, e.g.,
+    /// `"AsKsQdJd,7c6c5h4h:0.5"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hands = Vec::new();
+        let mut weights = Vec::new();
+
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (hand, weight) = match entry.split_once(':') {
+                Some((hand, weight)) => {
+                    let weight = weight
+                        .trim()
+                        .parse::<f32>()
+                        .map_err(|_| Error::InvalidRange(format!("Invalid weight: {weight}")))?;
+                    (hand.trim(), weight)
+                }
+                None => (entry, 1.0),
+            };
+
+            let mut chars = hand.chars().filter(|c| !c.is_whitespace());
+            let mut cards = [NOT_DEALT; 4];
+            for card in &mut cards {
+                *card = card_from_chars(&mut chars).map_err(|e| {
+                    Error::InvalidCard(format!("Failed to parse hand: {hand} ({e})"))
+                })?;
+            }
+
+            if chars.next().is_some() {
+                return Err(Error::InvalidCard(format!(
+                    "Expected exactly four cards: {hand}"
+                )));
+            }
+
+            hands.push(cards);
+            weights.push(weight);
+        }
+
+        Self::from_hands_weights(&hands, &weights)
+    }
+}
+
+impl fmt::Display for OmahaRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let strings = self
+            .hands
+            .iter()
+            .zip(&self.weights)
+            .map(|(&hand, &weight)| {
+                if weight == 1.0 {
+                    hand_to_string(hand)
+                } else {
+                    format!("{}:{weight}", hand_to_string(hand))
+                }
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", strings.join(","))
+    }
+}
+
+/// Returns the bit mask of the given hand.
+#[inline]
+pub(crate) fn hand_mask(hand: [Card; 4]) -> u64 {
+    hand.iter().fold(0, |mask, &card| mask | (1 << card))
+}
+
+/// Sorts the cards of the hand in descending order and checks their validity.
+#[inline]
+fn canonical_hand(mut hand: [Card; 4]) -> Result<[Card; 4], Error> {
+    if hand.iter().any(|&card| card >= 52) {
+        return Err(Error::InvalidCard(format!(
+            "Invalid card in hand: {hand:?}"
+        )));
+    }
+
+    hand.sort_unstable_by(|a, b| b.cmp(a));
+
+    if hand.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(Error::InvalidCard(format!(
+            "Duplicated card in hand: {hand:?}"
+        )));
+    }
+
+    Ok(hand)
+}
+
+/// Converts a canonical hand into a string (e.g., `"AsKsQdJd"`).
+#[inline]
+fn hand_to_string(hand: [Card; 4]) -> String {
+    hand.iter()
+        .map(|&card| card_to_string(card).unwrap())
+        .collect()
+}
+
+#[inline]
+fn check_weight(weight: f32) -> Result<(), Error> {
+    if (0.0..=1.0).contains(&weight) {
+        Ok(())
+    } else {
+        Err(Error::InvalidRange(format!("Invalid weight: {weight}")))
+    }
+}
@@ -0,0 +1,235 @@
+use super::*;
+use crate::bet_size::*;
+use crate::hand::*;
+use crate::interface::*;
+use crate::range::*;
+use crate::solver::*;
+use crate::utility::*;
+
+fn cards<const N: usize>(s: &str) -> [Card; N] {
+    let mut chars = s.chars();
+    [0; N].map(|_| card_from_chars(&mut chars).unwrap())
+}
+
+fn evaluate_five(s: &str) -> i32 {
+    cards::<5>(s)
+        .iter()
+        .fold(Hand::new(), |hand, &c| hand.add_card(c as usize))
+        .evaluate_raw()
+}
+
+#[test]
+fn evaluator() {
+    // four-flush board: a single suited hole card does not make a flush
+    let board = cards("AhKhQh2h3c");
+    assert_eq!(
+        evaluate_omaha(&cards("JhAsAdAc"), &board),
+        evaluate_five("AsAdAhKhQh")
+    );
+    assert_eq!(
+        evaluate_omaha(&cards("Jh4h7s7d"), &board),
+        evaluate_five("AhKhQhJh4h")
+    );
+
+    // four suited hole cards and two on board do not make a flush
+    let board = cards("9h5h2c2dKs");
+    assert_eq!(
+        evaluate_omaha(&cards("AhKhQhJh"), &board),
+        evaluate_five("KhKs2c2dAh")
+    );
+
+    // quads on board: only three of them can be used
+    let board = cards("7s7h7d7cKd");
+    assert_eq!(
+        evaluate_omaha(&cards("AsAhQcJc"), &board),
+        evaluate_five("AsAh7s7h7d")
+    );
+
+    // a set in hand: only two of the hole cards can be used with a board pair
+    let board = cards("2s2hKd8c4d");
+    assert_eq!(
+        evaluate_omaha(&cards("QsQhQdJc"), &board),
+        evaluate_five("QsQh2s2hKd")
+    );
+
+    // straight on board: the player must use two hole cards
+    let board = cards("5h6d7c8s9h");
+    let aces = evaluate_omaha(&cards("AsAdKcKh"), &board);
+    assert_eq!(aces, evaluate_five("AsAd9h8s7c"));
+    let straight = evaluate_omaha(&cards("TsJd2c2d"), &board);
+    assert_eq!(straight, evaluate_five("JdTs9h8s7c"));
+    assert!(aces < straight);
+}
+
+#[test]
+fn range() {
+    let range = "AsKsQdJd, 7c6c5h4h:0.5".parse::<OmahaRange>().unwrap();
+    assert_eq!(range.len(), 2);
+    assert_eq!(range.get_weight(cards("QdAsJdKs")), 1.0);
+    assert_eq!(range.get_weight(cards("4h5h6c7c")), 0.5);
+    assert_eq!(range.get_weight(cards("AhKhQhJh")), 0.0);
+    assert_eq!(range.to_string(), "7c6c5h4h:0.5,AsKsQdJd");
+    assert_eq!(range.to_string().parse::<OmahaRange>(), Ok(range.clone()));
+
+    let mut range = range;
+    range.set_weight(cards("AhKhQhJh"), 0.25).unwrap();
+    range.set_weight(cards("AsKsQdJd"), 0.0).unwrap();
+    assert_eq!(range.to_string(), "7c6c5h4h:0.5,AhKhQhJh:0.25");
+    assert!(range.is_valid());
+
+    assert!("AsKsQd".parse::<OmahaRange>().is_err());
+    assert!("AsKsQdJdTd".parse::<OmahaRange>().is_err());
+    assert!("AsAsQdJd".parse::<OmahaRange>().is_err());
+    assert!("AsKsQdJd:1.5".parse::<OmahaRange>().is_err());
+    assert!("AsKsQdJd,JdQdKsAs".parse::<OmahaRange>().is_err());
+    assert!("".parse::<OmahaRange>().unwrap().is_empty());
+}
+
+#[test]
+fn pot_limit_tree() {
+    let bet_sizes: BetSizeOptions = ("200%, a", "3x, a").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 1000,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        pot_limit: true,
+        ..Default::default()
+    };
+
+    let mut action_tree = ActionTree::new(tree_config.clone()).unwrap();
+
+    // the bet is capped at the pot size
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Check, Action::Bet(100)]
+    );
+
+    // the raise is capped at the pot size after calling: 100 + (100 + 100 + 100)
+    action_tree.play(Action::Bet(100)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [
+            Action::Fold,
+            Action::Call,
+            Action::Raise(300),
+            Action::Raise(400)
+        ]
+    );
+
+    action_tree.play(Action::Raise(400)).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Fold, Action::Call, Action::AllIn(1000)]
+    );
+
+    // all-in is not allowed when it exceeds the pot limit
+    action_tree.back_to_root();
+    assert!(action_tree.add_action(Action::AllIn(1000)).is_err());
+    assert!(action_tree.add_action(Action::Bet(150)).is_err());
+    action_tree.add_action(Action::Bet(50)).unwrap();
+
+    // without the pot limit
+    let tree_config = TreeConfig {
+        pot_limit: false,
+        ..tree_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    assert_eq!(
+        action_tree.available_actions(),
+        [Action::Check, Action::Bet(200), Action::AllIn(1000)]
+    );
+}
+
+#[test]
+fn solve_river() {
+    let card_config = OmahaCardConfig {
+        range: [
+            "AsKsQdJd,AhAdKc9c,JhTh8c7c,5s5c4d3d".parse().unwrap(),
+            "8s7s6d5d,KdKhTs9h,QsQhJc5h,AcJs8d8h".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+    };
+
+    let bet_sizes: BetSizeOptions = ("50%, 100%", "100%").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        starting_pot: 100,
+        effective_stack: 400,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        pot_limit: true,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    let mut game = OmahaGame::with_config(card_config, action_tree).unwrap();
+    assert_eq!(game.num_private_hands(0), 4);
+    game.allocate_memory();
+
+    let exploitability = solve(&mut game, 1000, 0.1, false);
+    assert!(exploitability <= 0.1);
+    assert!(game.is_solved());
+
+    let ev = compute_current_ev(&game);
+    assert!((ev[0] + ev[1]).abs() < 1e-3);
+
+    let strategy = game.strategy();
+    let num_hands = game.num_private_hands(0);
+    for hand in 0..num_hands {
+        let sum = strategy.iter().skip(hand).step_by(num_hands).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn solve_turn() {
+    let card_config = OmahaCardConfig {
+        range: [
+            "AsKsQdJd,AhAdKc9c,JhTh8c7c".parse().unwrap(),
+            "8s7s6d5d,KdKhTs9h,QsQhJc5h".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: NOT_DEALT,
+    };
+
+    let bet_sizes: BetSizeOptions = ("100%", "").try_into().unwrap();
+    let tree_config = TreeConfig {
+        initial_state: BoardState::Turn,
+        starting_pot: 100,
+        effective_stack: 300,
+        turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+        pot_limit: true,
+        ..Default::default()
+    };
+
+    let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+    let mut game = OmahaGame::with_config(card_config.clone(), action_tree).unwrap();
+
+    // check - check, then the river is dealt
+    game.play(0);
+    game.play(0);
+    assert!(game.is_chance_node());
+    assert_eq!(game.available_actions().len(), 52 - 4);
+    assert!(!game
+        .available_actions()
+        .contains(&Action::Chance(card_from_str("Qc").unwrap())));
+    game.play(card_from_str("2s").unwrap() as usize);
+    assert_eq!(game.current_board().len(), 5);
+    assert_eq!(game.current_player(), 0);
+
+    game.allocate_memory();
+    let exploitability = solve(&mut game, 1000, 0.5, false);
+    assert!(exploitability <= 0.5);
+
+    // a mismatched initial state is rejected
+    let tree_config = TreeConfig {
+        initial_state: BoardState::River,
+        ..tree_config
+    };
+    let action_tree = ActionTree::new(tree_config).unwrap();
+    assert!(OmahaGame::with_config(card_config, action_tree).is_err());
+}
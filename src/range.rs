@@ -1,6 +1,8 @@
 use crate::card::*;
+use crate::error::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Write;
 use std::str::FromStr;
 
@@ -37,6 +39,8 @@ use bincode::{Decode, Encode};
 /// // check that the hand "AKo" is not in the range
 /// assert_eq!(range.get_weight_offsuit(ace_rank, king_rank), 0.0);
 /// ```
+///
+/// With serde, a range is represented by the same string (i.e., the result of `to_string()`).
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "bincode", derive(Decode, Encode))]
 pub struct Range {
@@ -54,17 +58,17 @@ enum Suitedness {
 const COMBO_PAT: &str = r"(?:(?:[AaKkQqJjTt2-9]{2}[os]?)|(?:(?:[AaKkQqJjTt2-9][cdhs]){2}))";
 const WEIGHT_PAT: &str = r"(?:(?:[01](\.\d*)?)|(?:\.\d+))";
 
-static RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+pub(crate) static RANGE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(&format!(
         r"^(?P<range>{COMBO_PAT}(?:\+|(?:-{COMBO_PAT}))?)(?::(?P<weight>{WEIGHT_PAT}))?$"
     ))
     .unwrap()
 });
 
-static TRIM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*([-:,])\s*").unwrap());
+pub(crate) static TRIM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*([-:,])\s*").unwrap());
 
 #[inline]
-fn pair_indices(rank: u8) -> Vec<usize> {
+pub(crate) fn pair_indices(rank: u8) -> Vec<usize> {
     let mut result = Vec::with_capacity(6);
     for i in 0..4 {
         for j in i + 1..4 {
@@ -86,7 +90,7 @@ fn nonpair_indices(rank1: u8, rank2: u8) -> Vec<usize> {
 }
 
 #[inline]
-fn suited_indices(rank1: u8, rank2: u8) -> Vec<usize> {
+pub(crate) fn suited_indices(rank1: u8, rank2: u8) -> Vec<usize> {
     let mut result = Vec::with_capacity(4);
     for i in 0..4 {
         result.push(card_pair_to_index(4 * rank1 + i, 4 * rank2 + i));
@@ -95,7 +99,7 @@ fn suited_indices(rank1: u8, rank2: u8) -> Vec<usize> {
 }
 
 #[inline]
-fn offsuit_indices(rank1: u8, rank2: u8) -> Vec<usize> {
+pub(crate) fn offsuit_indices(rank1: u8, rank2: u8) -> Vec<usize> {
     let mut result = Vec::with_capacity(12);
     for i in 0..4 {
         for j in 0..4 {
@@ -133,7 +137,7 @@ fn indices_with_suitedness(rank1: u8, rank2: u8, suitedness: Suitedness) -> Vec<
 ///
 /// `'A'` => `12`, `'K'` => `11`, ..., `'2'` => `0`.
 #[inline]
-fn char_to_rank(c: char) -> Result<u8, String> {
+fn char_to_rank(c: char) -> Result<u8, Error> {
     match c {
         'A' | 'a' => Ok(12),
         'K' | 'k' => Ok(11),
@@ -141,7 +145,7 @@ fn char_to_rank(c: char) -> Result<u8, String> {
         'J' | 'j' => Ok(9),
         'T' | 't' => Ok(8),
         '2'..='9' => Ok(c as u8 - b'2'),
-        _ => Err(format!("Expected rank character: {c}")),
+        _ => Err(Error::InvalidCard(format!("Expected rank character: {c}"))),
     }
 }
 
@@ -149,13 +153,13 @@ fn char_to_rank(c: char) -> Result<u8, String> {
 ///
 /// `'c'` => `0`, `'d'` => `1`, `'h'` => `2`, `'s'` => `3`.
 #[inline]
-fn char_to_suit(c: char) -> Result<u8, String> {
+fn char_to_suit(c: char) -> Result<u8, Error> {
     match c {
         'c' => Ok(0),
         'd' => Ok(1),
         'h' => Ok(2),
         's' => Ok(3),
-        _ => Err(format!("Expected suit character: {c}")),
+        _ => Err(Error::InvalidCard(format!("Expected suit character: {c}"))),
     }
 }
 
@@ -163,7 +167,7 @@ fn char_to_suit(c: char) -> Result<u8, String> {
 ///
 /// `12` => `'A'`, `11` => `'K'`, ..., `0` => `'2'`.
 #[inline]
-fn rank_to_char(rank: u8) -> Result<char, String> {
+pub(crate) fn rank_to_char(rank: u8) -> Result<char, Error> {
     match rank {
         12 => Ok('A'),
         11 => Ok('K'),
@@ -171,7 +175,7 @@ fn rank_to_char(rank: u8) -> Result<char, String> {
         9 => Ok('J'),
         8 => Ok('T'),
         0..=7 => Ok((rank + b'2') as char),
-        _ => Err(format!("Invalid input: {rank}")),
+        _ => Err(Error::InvalidCard(format!("Invalid input: {rank}"))),
     }
 }
 
@@ -179,13 +183,13 @@ fn rank_to_char(rank: u8) -> Result<char, String> {
 ///
 /// `0` => `'c'`, `1` => `'d'`, `2` => `'h'`, `3` => `'s'`.
 #[inline]
-fn suit_to_char(suit: u8) -> Result<char, String> {
+fn suit_to_char(suit: u8) -> Result<char, Error> {
     match suit {
         0 => Ok('c'),
         1 => Ok('d'),
         2 => Ok('h'),
         3 => Ok('s'),
-        _ => Err(format!("Invalid input: {suit}")),
+        _ => Err(Error::InvalidCard(format!("Invalid input: {suit}"))),
     }
 }
 
@@ -202,7 +206,7 @@ fn suit_to_char(suit: u8) -> Result<char, String> {
 /// assert!(card_to_string(52).is_err());
 /// ```
 #[inline]
-pub fn card_to_string(card: Card) -> Result<String, String> {
+pub fn card_to_string(card: Card) -> Result<String, Error> {
     check_card(card)?;
     let rank = card >> 2;
     let suit = card & 3;
@@ -224,7 +228,7 @@ pub fn card_to_string(card: Card) -> Result<String, String> {
 /// assert!(hole_to_string((52, 53)).is_err());
 /// ```
 #[inline]
-pub fn hole_to_string(hole: (Card, Card)) -> Result<String, String> {
+pub fn hole_to_string(hole: (Card, Card)) -> Result<String, Error> {
     let max_card = Card::max(hole.0, hole.1);
     let min_card = Card::min(hole.0, hole.1);
     Ok(format!(
@@ -251,7 +255,7 @@ pub fn hole_to_string(hole: (Card, Card)) -> Result<String, String> {
 /// assert!(holes_to_strings(&[(52, 53)]).is_err());
 /// ```
 #[inline]
-pub fn holes_to_strings(holes: &[(Card, Card)]) -> Result<Vec<String>, String> {
+pub fn holes_to_strings(holes: &[(Card, Card)]) -> Result<Vec<String>, Error> {
     holes.iter().map(|&hole| hole_to_string(hole)).collect()
 }
 
@@ -269,9 +273,10 @@ pub fn holes_to_strings(holes: &[(Card, Card)]) -> Result<Vec<String>, String> {
 /// assert!(card_from_chars(&mut chars).is_err());
 /// ```
 #[inline]
-pub fn card_from_chars<T: Iterator<Item = char>>(chars: &mut T) -> Result<Card, String> {
-    let rank_char = chars.next().ok_or_else(|| "Unexpected end".to_string())?;
-    let suit_char = chars.next().ok_or_else(|| "Unexpected end".to_string())?;
+pub fn card_from_chars<T: Iterator<Item = char>>(chars: &mut T) -> Result<Card, Error> {
+    let unexpected_end = || Error::InvalidCard("Unexpected end".to_string());
+    let rank_char = chars.next().ok_or_else(unexpected_end)?;
+    let suit_char = chars.next().ok_or_else(unexpected_end)?;
 
     let rank = char_to_rank(rank_char)?;
     let suit = char_to_suit(suit_char)?;
@@ -291,12 +296,14 @@ pub fn card_from_chars<T: Iterator<Item = char>>(chars: &mut T) -> Result<Card,
 /// assert_eq!(card_from_str("As"), Ok(51));
 /// ```
 #[inline]
-pub fn card_from_str(s: &str) -> Result<Card, String> {
+pub fn card_from_str(s: &str) -> Result<Card, Error> {
     let mut chars = s.chars();
     let result = card_from_chars(&mut chars)?;
 
     if chars.next().is_some() {
-        return Err("Expected exactly two characters".to_string());
+        return Err(Error::InvalidCard(
+            "Expected exactly two characters".to_string(),
+        ));
     }
 
     Ok(result)
@@ -313,7 +320,7 @@ pub fn card_from_str(s: &str) -> Result<Card, String> {
 /// assert!(flop_from_str("2c3d4h5s").is_err());
 /// ```
 #[inline]
-pub fn flop_from_str(s: &str) -> Result<[Card; 3], String> {
+pub fn flop_from_str(s: &str) -> Result<[Card; 3], Error> {
     let mut result = [0; 3];
     let mut chars = s.chars();
 
@@ -322,13 +329,15 @@ pub fn flop_from_str(s: &str) -> Result<[Card; 3], String> {
     result[2] = card_from_chars(&mut chars.by_ref().skip_while(|c| c.is_whitespace()))?;
 
     if chars.next().is_some() {
-        return Err("Expected exactly three cards".to_string());
+        return Err(Error::InvalidCard(
+            "Expected exactly three cards".to_string(),
+        ));
     }
 
     result.sort_unstable();
 
     if result[0] == result[1] || result[1] == result[2] {
-        return Err("Cards must be unique".to_string());
+        return Err(Error::InvalidCard("Cards must be unique".to_string()));
     }
 
     Ok(result)
@@ -383,14 +392,22 @@ fn parse_compound_singleton(combo: &str) -> Result<(u8, u8, Suitedness), String>
 }
 
 #[inline]
-fn check_card(card: Card) -> Result<(), String> {
+fn check_card(card: Card) -> Result<(), Error> {
     if card < 52 {
         Ok(())
     } else {
-        Err(format!("Invalid card: {card}"))
+        Err(Error::InvalidCard(format!("Invalid card: {card}")))
     }
 }
 
+/// Converts a weight into `f64` via its shortest decimal representation.
+///
+/// We want to obtain `0.1` for `1.0 - 0.9`, not `0.100000024`.
+#[inline]
+pub(crate) fn to_decimal(weight: f32) -> f64 {
+    weight.to_string().parse().unwrap()
+}
+
 #[inline]
 fn check_weight(weight: f32) -> Result<(), String> {
     if (0.0..=1.0).contains(&weight) {
@@ -426,13 +443,16 @@ impl Range {
 
     /// Attempts to create a range from raw data.
     #[inline]
-    pub fn from_raw_data(data: &[f32]) -> Result<Self, String> {
+    pub fn from_raw_data(data: &[f32]) -> Result<Self, Error> {
         if data.len() != 52 * 51 / 2 {
-            return Err(format!("Expected exactly {} elements", 52 * 51 / 2));
+            return Err(Error::InvalidRange(format!(
+                "Expected exactly {} elements",
+                52 * 51 / 2
+            )));
         }
 
         for &weight in data {
-            check_weight(weight)?;
+            check_weight(weight).map_err(Error::InvalidRange)?;
         }
 
         Ok(Self {
@@ -448,14 +468,16 @@ impl Range {
 
     /// Attempts to create a range from a list of hands with their weights.
     #[inline]
-    pub fn from_hands_weights(hands: &[(Card, Card)], weights: &[f32]) -> Result<Self, String> {
+    pub fn from_hands_weights(hands: &[(Card, Card)], weights: &[f32]) -> Result<Self, Error> {
         let mut range = Self::default();
         for (&(card1, card2), &weight) in hands.iter().zip(weights.iter()) {
             check_card(card1)?;
             check_card(card2)?;
-            check_weight(weight)?;
+            check_weight(weight).map_err(Error::InvalidRange)?;
             if card1 == card2 {
-                return Err("Hand must consist of two different cards".to_string());
+                return Err(Error::InvalidCard(
+                    "Hand must consist of two different cards".to_string(),
+                ));
             }
             range.set_weight_by_cards(card1, card2, weight);
         }
@@ -492,7 +514,12 @@ impl Range {
     /// "Sanitized" means that the range string does not contain any invalid patterns and whitespace
     /// characters. Therefore, this method can bypass the regular expression processing. If you want
     /// to create a range from a regular string, use `parse::<Range>()` instead.
-    pub fn from_sanitized_str(ranges: &str) -> Result<Self, String> {
+    #[inline]
+    pub fn from_sanitized_str(ranges: &str) -> Result<Self, Error> {
+        Self::parse_sanitized(ranges).map_err(Error::InvalidRange)
+    }
+
+    fn parse_sanitized(ranges: &str) -> Result<Self, String> {
         let mut ranges = ranges.split(',').collect::<Vec<_>>();
 
         // remove last empty element if any
@@ -515,13 +542,35 @@ impl Range {
                 return Err(format!("Invalid range: {range}"));
             }
 
-            if range.contains('-') {
-                result.update_with_dash_range(range, weight)?;
-            } else if range.contains('+') {
-                result.update_with_plus_range(range, weight)?;
-            } else {
-                result.update_with_singleton(range, weight)?;
-            }
+            result.update_with_group(range, weight)?;
+        }
+
+        Ok(result)
+    }
+
+    fn parse_str(s: &str) -> Result<Self, String> {
+        let s = TRIM_REGEX.replace_all(s, "$1").trim().to_string();
+        let mut ranges = s.split(',').collect::<Vec<_>>();
+
+        // remove last empty element if any
+        if ranges.last().unwrap().is_empty() {
+            ranges.pop();
+        }
+
+        let mut result = Self::new();
+
+        for range in ranges.into_iter().rev() {
+            let caps = RANGE_REGEX
+                .captures(range)
+                .ok_or_else(|| format!("Failed to parse range: {range}"))?;
+
+            let range = caps.name("range").unwrap().as_str();
+            let weight = caps
+                .name("weight")
+                .map_or(1.0, |s| s.as_str().parse().unwrap());
+            check_weight(weight)?;
+
+            result.update_with_group(range, weight)?;
         }
 
         Ok(result)
@@ -548,6 +597,72 @@ impl Range {
             .for_each(|el| *el = (1.0 - el.to_string().parse::<f64>().unwrap()) as f32);
     }
 
+    /// Returns the union of the two ranges, i.e., the maximum weight of each hand.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, f64::max)
+    }
+
+    /// Returns the intersection of the two ranges, i.e., the minimum weight of each hand.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, f64::min)
+    }
+
+    /// Returns the sum of the weights of each hand. The weights above `1.0` are set to `1.0`.
+    #[inline]
+    pub fn sum(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| (a + b).min(1.0))
+    }
+
+    /// Subtracts `other` from the range. The weights below `0.0` are set to `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use postflop_solver::Range;
+    ///
+    /// let call_range = "QQ-88,AQs-ATs,KQs".parse::<Range>().unwrap();
+    /// let three_bet_range = "QQ,AQs,KQs:0.4".parse::<Range>().unwrap();
+    ///
+    /// let defend_range = call_range.subtract(&three_bet_range);
+    /// assert_eq!(defend_range.to_string(), "JJ-88,AJs-ATs,KQs:0.6");
+    /// ```
+    #[inline]
+    pub fn subtract(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| (a - b).max(0.0))
+    }
+
+    /// Multiplies the weight of each hand by `factor`. The weights above `1.0` are set to `1.0`.
+    ///
+    /// Returns `Err` if `factor` is negative or not finite.
+    #[inline]
+    pub fn scale(&self, factor: f32) -> Result<Self, Error> {
+        if !factor.is_finite() || factor < 0.0 {
+            return Err(Error::InvalidRange(format!(
+                "Invalid scale factor: {factor}"
+            )));
+        }
+        let factor = to_decimal(factor);
+        Ok(self.map(|weight| (weight * factor).min(1.0)))
+    }
+
+    /// Multiplies the weight of each hand by the weight of the same hand in `other`.
+    #[inline]
+    pub fn multiply(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    /// Clamps the weight of each hand to `[0.0, 1.0]`.
+    ///
+    /// The results of the other range operations are always valid, so this is only needed for
+    /// weights set directly (e.g., with [`set_weight_by_cards`]).
+    ///
+    /// [`set_weight_by_cards`]: #method.set_weight_by_cards
+    #[inline]
+    pub fn clamp(&self) -> Self {
+        self.map(|weight| weight.clamp(0.0, 1.0))
+    }
+
     /// Obtains the weight of a specified hand.
     ///
     /// Undefined behavior if:
@@ -629,6 +744,27 @@ impl Range {
         self.set_weight(&offsuit_indices(rank1, rank2), weight);
     }
 
+    /// Applies `f` to the weight of each hand.
+    ///
+    /// The weights are converted to their shortest decimal representations before applying `f`
+    /// (see [`to_decimal`]).
+    #[inline]
+    fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            data: self.data.map(|weight| f(to_decimal(weight)) as f32),
+        }
+    }
+
+    /// Applies `f` to the weights of each hand in the two ranges.
+    #[inline]
+    fn zip_with(&self, other: &Self, f: impl Fn(f64, f64) -> f64) -> Self {
+        let mut data = [0.0; 52 * 51 / 2];
+        for (dst, (&a, &b)) in data.iter_mut().zip(self.data.iter().zip(&other.data)) {
+            *dst = f(to_decimal(a), to_decimal(b)) as f32;
+        }
+        Self { data }
+    }
+
     /// Returns whether the range is valid, i.e., all weights are in the range `[0.0, 1.0]`.
     #[inline]
     pub(crate) fn is_valid(&self) -> bool {
@@ -705,6 +841,18 @@ impl Range {
         }
     }
 
+    /// Sets the weight of the hands of a group (e.g., "AKs", "88+", "A5s-A2s").
+    #[inline]
+    pub(crate) fn update_with_group(&mut self, group: &str, weight: f32) -> Result<(), String> {
+        if group.contains('-') {
+            self.update_with_dash_range(group, weight)
+        } else if group.contains('+') {
+            self.update_with_plus_range(group, weight)
+        } else {
+            self.update_with_singleton(group, weight)
+        }
+    }
+
     #[inline]
     fn update_with_singleton(&mut self, combo: &str, weight: f32) -> Result<(), String> {
         let (rank1, rank2, suitedness) = parse_singleton(combo)?;
@@ -953,40 +1101,11 @@ impl Range {
 }
 
 impl FromStr for Range {
-    type Err = String;
+    type Err = Error;
 
+    #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = TRIM_REGEX.replace_all(s, "$1").trim().to_string();
-        let mut ranges = s.split(',').collect::<Vec<_>>();
-
-        // remove last empty element if any
-        if ranges.last().unwrap().is_empty() {
-            ranges.pop();
-        }
-
-        let mut result = Self::new();
-
-        for range in ranges.into_iter().rev() {
-            let caps = RANGE_REGEX
-                .captures(range)
-                .ok_or_else(|| format!("Failed to parse range: {range}"))?;
-
-            let range = caps.name("range").unwrap().as_str();
-            let weight = caps
-                .name("weight")
-                .map_or(1.0, |s| s.as_str().parse().unwrap());
-            check_weight(weight)?;
-
-            if range.contains('-') {
-                result.update_with_dash_range(range, weight)?;
-            } else if range.contains('+') {
-                result.update_with_plus_range(range, weight)?;
-            } else {
-                result.update_with_singleton(range, weight)?;
-            }
-        }
-
-        Ok(result)
+        Self::parse_str(s).map_err(Error::InvalidRange)
     }
 }
 
@@ -1001,6 +1120,21 @@ impl ToString for Range {
     }
 }
 
+impl Serialize for Range {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Range {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1163,4 +1297,47 @@ mod tests {
             assert_eq!(range.unwrap().to_string(), expected);
         }
     }
+
+    #[test]
+    fn range_algebra() {
+        let a = "AA,KK:0.75,AKs:0.5".parse::<Range>().unwrap();
+        let b = "KK:0.25,QQ,AKs:0.9".parse::<Range>().unwrap();
+
+        assert_eq!(a.union(&b).to_string(), "AA,KK:0.75,QQ,AKs:0.9");
+        assert_eq!(a.intersection(&b).to_string(), "KK:0.25,AKs:0.5");
+        assert_eq!(a.sum(&b).to_string(), "QQ+,AKs");
+        assert_eq!(a.subtract(&b).to_string(), "AA,KK:0.5");
+        assert_eq!(b.subtract(&a).to_string(), "QQ,AKs:0.4");
+        assert_eq!(a.multiply(&b).to_string(), "KK:0.1875,AKs:0.45");
+        assert_eq!(
+            a.scale(0.5).unwrap().to_string(),
+            "AA:0.5,KK:0.375,AKs:0.25"
+        );
+        assert_eq!(a.scale(2.0).unwrap().to_string(), "KK+,AKs");
+        assert!(a.scale(0.0).unwrap().is_empty());
+        assert!(a.sum(&b).is_valid() && a.scale(2.0).unwrap().is_valid());
+
+        for factor in [-1.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(a.scale(factor), Err(Error::InvalidRange(_))));
+        }
+
+        let mut c = a;
+        c.data[0] = 1.5;
+        c.data[1] = -0.5;
+        assert!(!c.is_valid());
+        assert!(c.clamp().is_valid());
+        assert_eq!(c.clamp().data[..2], [1.0, 0.0]);
+
+        assert!(a.subtract(&a).is_empty());
+        assert!(Range::ones().subtract(&a).sum(&a) == Range::ones());
+    }
+
+    #[test]
+    fn range_serde() {
+        let range = "AA:0.5,KK-QQ,AJs+,KsJh".parse::<Range>().unwrap();
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(json, r#""AA:0.5,KK-QQ,AJs+,KsJh""#);
+        assert_eq!(serde_json::from_str::<Range>(&json).unwrap(), range);
+        assert!(serde_json::from_str::<Range>(r#""AKQ""#).is_err());
+    }
 }
@@ -0,0 +1,397 @@
+use crate::card::*;
+use crate::error::*;
+use crate::range::*;
+use std::cmp::Reverse;
+
+/// Range formats of third-party tools supported by [`Range::from_format`] and
+/// [`Range::to_format`].
+///
+/// Importing is strict: a hand that is specified more than once with different weights, a weight
+/// outside the valid range, and any unparsable text are errors rather than being silently ignored.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let range = Range::from_format("[50]AKs,QQ[/50],AA", RangeFormat::GtoPlus).unwrap();
+/// assert_eq!(range.to_format(RangeFormat::Pio), "AA,QQ:0.5,AKs:0.5");
+///
+/// let range = Range::from_format("AA,KK:0.25,AKs:0.5", RangeFormat::Pio).unwrap();
+/// assert_eq!(range.to_format(RangeFormat::GtoPlus), "AA,[50]AKs[/50],[25]KK[/25]");
+///
+/// let values = range.to_format(RangeFormat::Values);
+/// assert_eq!(values.split(' ').count(), 1326);
+/// assert_eq!(Range::from_format(&values, RangeFormat::Values).unwrap(), range);
+///
+/// // conflicting weights
+/// assert!(Range::from_format("AKs,AK:0.5", RangeFormat::Pio).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeFormat {
+    /// PioSOLVER syntax: comma-separated hand classes or combos with optional weights between `0`
+    /// and `1` (e.g., `"AA,KK:0.5,AKs,AsKh:0.25"`). Plus and dash ranges (e.g., `"QQ+"`,
+    /// `"A5s-A2s"`) are also accepted. This is the same syntax as GTO Wizard.
+    ///
+    /// The export lists every hand class separately.
+    Pio,
+
+    /// GTO+ syntax: hands with a weight other than 100% are grouped by brackets with the weight in
+    /// percent (e.g., `"AA,[50.0]KK,AKs[/50.0]"`).
+    GtoPlus,
+
+    /// Per-combo list: same syntax as [`Pio`], but the export lists every combo separately (e.g.,
+    /// `"AsAh,AsAd:0.5,..."`).
+    ///
+    /// [`Pio`]: #variant.Pio
+    Combos,
+
+    /// 1326 weights separated by whitespaces or commas, in the order of PioSOLVER: `2d2c`, `2h2c`,
+    /// `2h2d`, `2s2c`, `2s2d`, `2s2h`, `3c2c`, ..., `AsAh` (i.e., sorted by the higher card, then by
+    /// the lower card, where the cards are ordered `2c < 2d < 2h < 2s < 3c < ... < As`).
+    Values,
+}
+
+impl Range {
+    /// Attempts to parse a range in the specified format.
+    ///
+    /// See [`RangeFormat`] for the supported formats.
+    pub fn from_format(s: &str, format: RangeFormat) -> Result<Self, Error> {
+        match format {
+            RangeFormat::Pio | RangeFormat::Combos => parse_pio(s),
+            RangeFormat::GtoPlus => parse_gto_plus(s),
+            RangeFormat::Values => parse_values(s),
+        }
+        .map_err(Error::InvalidRange)
+    }
+
+    /// Converts the range into a string in the specified format.
+    ///
+    /// See [`RangeFormat`] for the supported formats.
+    pub fn to_format(&self, format: RangeFormat) -> String {
+        match format {
+            RangeFormat::Pio => join_groups(&self.hand_groups(false)),
+            RangeFormat::Combos => join_groups(&self.hand_groups(true)),
+            RangeFormat::GtoPlus => self.gto_plus_string(),
+            RangeFormat::Values => self.values_string(),
+        }
+    }
+
+    /// Returns the hand classes (or combos if the weights of a hand class are not uniform, or
+    /// `combos` is `true`) with positive weights.
+    fn hand_groups(&self, combos: bool) -> Vec<(String, f32)> {
+        let mut result = Vec::new();
+
+        let mut push = |name: String, indices: Vec<usize>| {
+            let weight = self.raw_data()[indices[0]];
+            if !combos && indices.iter().all(|&i| self.raw_data()[i] == weight) {
+                if weight > 0.0 {
+                    result.push((name, weight));
+                }
+                return;
+            }
+
+            // list the combos in descending order of the cards (e.g., AsAh, AsAd, ..., AdAc)
+            let mut pairs = indices
+                .iter()
+                .map(|&i| index_to_card_pair(i))
+                .collect::<Vec<_>>();
+            pairs.sort_unstable_by_key(|&(card1, card2)| Reverse((card2, card1)));
+            for (card1, card2) in pairs {
+                let weight = self.raw_data()[card_pair_to_index(card1, card2)];
+                if weight > 0.0 {
+                    result.push((hole_to_string((card1, card2)).unwrap(), weight));
+                }
+            }
+        };
+
+        for rank in (0..13).rev() {
+            let r = rank_to_char(rank).unwrap();
+            push(format!("{r}{r}"), pair_indices(rank));
+        }
+
+        for rank1 in (1..13).rev() {
+            let r1 = rank_to_char(rank1).unwrap();
+            for rank2 in (0..rank1).rev() {
+                let r2 = rank_to_char(rank2).unwrap();
+                push(format!("{r1}{r2}s"), suited_indices(rank1, rank2));
+                push(format!("{r1}{r2}o"), offsuit_indices(rank1, rank2));
+            }
+        }
+
+        result
+    }
+
+    fn gto_plus_string(&self) -> String {
+        let groups = self.hand_groups(false);
+
+        let mut weights = groups.iter().map(|&(_, w)| w).collect::<Vec<_>>();
+        weights.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap());
+        weights.dedup();
+
+        let mut result = Vec::new();
+        for weight in weights {
+            let names = groups
+                .iter()
+                .filter(|&&(_, w)| w == weight)
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            if weight == 1.0 {
+                result.push(names);
+            } else {
+                let percent = to_decimal(weight) * 100.0;
+                let percent = (percent * 1e6).round() / 1e6;
+                result.push(format!("[{percent}]{names}[/{percent}]"));
+            }
+        }
+
+        result.join(",")
+    }
+
+    fn values_string(&self) -> String {
+        pio_order()
+            .map(|index| self.raw_data()[index].to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Returns the indices of the combos in the order of PioSOLVER.
+#[inline]
+fn pio_order() -> impl Iterator<Item = usize> {
+    (1..52).flat_map(|high| (0..high).map(move |low| card_pair_to_index(low, high)))
+}
+
+#[inline]
+fn join_groups(groups: &[(String, f32)]) -> String {
+    groups
+        .iter()
+        .map(|(name, weight)| {
+            if *weight == 1.0 {
+                name.clone()
+            } else {
+                format!("{name}:{weight}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Builder of a range that rejects conflicting weights.
+struct StrictRange {
+    weights: Vec<Option<f32>>,
+}
+
+impl StrictRange {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            weights: vec![None; 52 * 51 / 2],
+        }
+    }
+
+    /// Sets the weight of the hands of a group (e.g., "AKs", "88+", "A5s-A2s").
+    fn set(&mut self, group: &str, weight: f32) -> Result<(), String> {
+        if !RANGE_REGEX.is_match(group) {
+            return Err(format!("Failed to parse range: {group}"));
+        }
+
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(format!("Invalid weight: {group}:{weight}"));
+        }
+
+        let mut mask = Range::new();
+        mask.update_with_group(group, 1.0)?;
+
+        for (index, _) in mask.raw_data().iter().enumerate().filter(|(_, &w)| w > 0.0) {
+            match self.weights[index] {
+                Some(prev) if prev != weight => {
+                    let hand = hole_to_string(index_to_card_pair(index)).unwrap();
+                    return Err(format!(
+                        "Conflicting weights of {hand} in {group}: {prev} and {weight}"
+                    ));
+                }
+                _ => self.weights[index] = Some(weight),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn build(self) -> Result<Range, String> {
+        let data = self
+            .weights
+            .iter()
+            .map(|w| w.unwrap_or(0.0))
+            .collect::<Vec<_>>();
+        Range::from_raw_data(&data).map_err(|e| e.to_string())
+    }
+}
+
+fn parse_pio(s: &str) -> Result<Range, String> {
+    let s = TRIM_REGEX.replace_all(s, "$1");
+    let mut result = StrictRange::new();
+
+    for entry in s.trim().split(',').filter(|e| !e.is_empty()) {
+        let (group, weight) = match entry.split_once(':') {
+            Some((group, weight)) => (group, parse_weight(weight, 1.0)?),
+            None => (entry, 1.0),
+        };
+        result.set(group, weight)?;
+    }
+
+    result.build()
+}
+
+fn parse_gto_plus(s: &str) -> Result<Range, String> {
+    let mut result = StrictRange::new();
+    let mut rest = s.trim();
+
+    while !rest.is_empty() {
+        let (text, weight) = if let Some(tail) = rest.strip_prefix('[') {
+            let (weight_str, tail) = tail
+                .split_once(']')
+                .ok_or_else(|| format!("Unclosed bracket: {rest}"))?;
+            let closing = format!("[/{weight_str}]");
+            let (text, tail) = tail
+                .split_once(&closing)
+                .ok_or_else(|| format!("Missing closing tag {closing}: {rest}"))?;
+            rest = tail;
+            (text, parse_weight(weight_str, 100.0)?)
+        } else {
+            let end = rest.find('[').unwrap_or(rest.len());
+            let text = &rest[..end];
+            rest = &rest[end..];
+            (text, 1.0)
+        };
+
+        if text.contains(['[', ']', ':']) {
+            return Err(format!("Invalid group: {text}"));
+        }
+
+        for group in text.split(',').map(str::trim).filter(|g| !g.is_empty()) {
+            result.set(group, weight)?;
+        }
+
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+
+    result.build()
+}
+
+fn parse_values(s: &str) -> Result<Range, String> {
+    let values = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+
+    if values.len() != 52 * 51 / 2 {
+        return Err(format!(
+            "Expected exactly {} values, but got {}",
+            52 * 51 / 2,
+            values.len()
+        ));
+    }
+
+    let mut data = vec![0.0; 52 * 51 / 2];
+    for (index, value) in pio_order().zip(values) {
+        data[index] = parse_weight(value, 1.0)?;
+    }
+
+    Range::from_raw_data(&data).map_err(|e| e.to_string())
+}
+
+/// Parses a weight in `[0, scale]` and divides it by `scale`.
+#[inline]
+fn parse_weight(s: &str, scale: f64) -> Result<f32, String> {
+    let s = s.trim();
+    let weight = s
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid weight: {s}"))?;
+    if !(0.0..=scale).contains(&weight) {
+        return Err(format!("Weight must be between 0 and {scale}: {s}"));
+    }
+    Ok((weight / scale) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pio_format() {
+        let range = Range::from_format("AA, KK:0.5, AKs, AhKd:.25, 22+:0.1", RangeFormat::Pio);
+        let range = range.unwrap_err();
+        assert!(matches!(range, Error::InvalidRange(_)));
+
+        let range = Range::from_format("AA,KK:0.5,AKs,AhKd:.25", RangeFormat::Pio).unwrap();
+        assert_eq!(range, "AA,KK:0.5,AKs,AhKd:0.25".parse().unwrap());
+        assert_eq!(range.to_format(RangeFormat::Pio), "AA,KK:0.5,AKs,AhKd:0.25");
+
+        let combos = range.to_format(RangeFormat::Combos);
+        assert_eq!(combos.split(',').count(), 6 + 6 + 4 + 1);
+        assert!(combos.starts_with("AsAh,AsAd,AsAc,AhAd,AhAc,AdAc,KsKh:0.5,"));
+        assert_eq!(
+            Range::from_format(&combos, RangeFormat::Combos).unwrap(),
+            range
+        );
+
+        let error_tests = ["AA:1.5", "AKQ", "AA:x", "AK,AKo:0.5", "AA,AsAh:0.5"];
+        for s in error_tests {
+            assert!(Range::from_format(s, RangeFormat::Pio).is_err(), "{s}");
+        }
+
+        // the same weight may be specified twice
+        assert!(Range::from_format("AK,AKs", RangeFormat::Pio).is_ok());
+    }
+
+    #[test]
+    fn gto_plus_format() {
+        let s = "[75.5]QQ, AKo[/75.5], AA, KK, [10]AsKs[/10]";
+        let range = Range::from_format(s, RangeFormat::GtoPlus).unwrap();
+        let expected = "KK+,QQ:0.755,AKo:0.755,AsKs:0.1".parse().unwrap();
+        assert_eq!(range, expected);
+
+        let s = range.to_format(RangeFormat::GtoPlus);
+        assert_eq!(s, "AA,KK,[75.5]QQ,AKo[/75.5],[10]AsKs[/10]");
+        assert_eq!(Range::from_format(&s, RangeFormat::GtoPlus).unwrap(), range);
+
+        let error_tests = [
+            "[50]AA",
+            "[50]AA[/25]",
+            "[150]AA[/150]",
+            "[x]AA[/x]",
+            "[50]AA:0.5[/50]",
+            "[50]AA[50]KK[/50][/50]",
+            "AA,[50]AA[/50]",
+        ];
+        for s in error_tests {
+            assert!(Range::from_format(s, RangeFormat::GtoPlus).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn values_format() {
+        let range = "AA,KK:0.5,AsKh:0.125,32o:0.1".parse::<Range>().unwrap();
+        let s = range.to_format(RangeFormat::Values);
+        assert_eq!(Range::from_format(&s, RangeFormat::Values).unwrap(), range);
+
+        let mut values = vec!["0"; 1326];
+        values[0] = "1"; // 2d2c
+        values[1] = "0.5"; // 2h2c
+        values[1325] = "0.25"; // AsAh
+        let range = Range::from_format(&values.join(", "), RangeFormat::Values).unwrap();
+        assert_eq!(range.get_weight_by_cards(0, 1), 1.0);
+        assert_eq!(range.get_weight_by_cards(0, 2), 0.5);
+        assert_eq!(range.get_weight_by_cards(50, 51), 0.25);
+        assert_eq!(
+            range.to_format(RangeFormat::Combos),
+            "AsAh:0.25,2h2c:0.5,2d2c"
+        );
+
+        assert!(Range::from_format(&values[1..].join(" "), RangeFormat::Values).is_err());
+        values[2] = "1.1";
+        assert!(Range::from_format(&values.join(" "), RangeFormat::Values).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::utils::*;
 use crate::weighted_average;
 use crate::Card;
+use crate::Error;
 use crate::PostFlopGame;
 use crate::SpecificResultData;
 
@@ -49,6 +50,7 @@ pub struct Spot {
     pub prev_player: Option<String>, // Nouveau champ
     pub pot: f64,
     pub stack: f64,
+    pub stacks: [f64; 2], // Stacks réels (OOP, IP)
     pub equity_oop: f64,
 }
 
@@ -119,8 +121,8 @@ impl GameState {
             }
 
             println!(
-                "  Pot: {:.2}, Stack: {:.2}, Equity OOP: {:.2}",
-                spot.pot, spot.stack, spot.equity_oop
+                "  Pot: {:.2}, Stack: {:.2}, Stacks: {:?}, Equity OOP: {:.2}",
+                spot.pot, spot.stack, spot.stacks, spot.equity_oop
             );
             println!("  Previous player: {:?}", spot.prev_player);
         } else {
@@ -168,8 +170,8 @@ impl GameState {
             }
 
             println!(
-                "  Pot: {:.2}, Stack: {:.2}, Equity OOP: {:.2}",
-                spot.pot, spot.stack, spot.equity_oop
+                "  Pot: {:.2}, Stack: {:.2}, Stacks: {:?}, Equity OOP: {:.2}",
+                spot.pot, spot.stack, spot.stacks, spot.equity_oop
             );
             println!("  Previous player: {:?}", spot.prev_player);
         }
@@ -202,7 +204,7 @@ pub fn select_spot(
     spot_index: usize,
     need_splice: bool,
     from_deal: bool,
-) -> Result<SpecificResultData, String> {
+) -> Result<SpecificResultData, Error> {
     // println!(
     //     "select_spot() - spot_index: {}, need_splice: {}, from_deal: {}",
     //     spot_index, need_splice, from_deal
@@ -568,7 +570,7 @@ fn splice_spots_terminal(
     game: &mut PostFlopGame,
     state: &mut GameState,
     spot_index: usize,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     // println!("splice_spots_terminal() - spot_index: {}", spot_index);
     let prev_spot = &state.spots[spot_index - 1];
     let prev_action = &prev_spot.actions[prev_spot.selected_index as usize];
@@ -609,6 +611,7 @@ fn splice_spots_terminal(
         actions: Vec::new(),
         pot: final_pot,
         stack: 0.0, // Le stack n'est pas utilisé dans les spots terminaux
+        stacks: [0.0; 2],
         equity_oop,
         prev_player: Some(prev_spot.player.clone()),
     };
@@ -624,7 +627,7 @@ fn splice_spots_chance(
     game: &mut PostFlopGame,
     state: &mut GameState,
     spot_index: usize,
-) -> Result<(i32, i32), String> {
+) -> Result<(i32, i32), Error> {
     // println!("splice_spots_chance() - spot_index: {}", spot_index);
     let prev_spot = &state.spots[spot_index - 1];
     let turn_spot = state
@@ -707,8 +710,9 @@ fn splice_spots_chance(
             })
             .collect(),
         actions: Vec::new(),
-        pot: game.tree_config().starting_pot as f64 + 2.0 * game.total_bet_amount()[0] as f64,
+        pot: game.current_pot() as f64,
         stack: game.tree_config().effective_stack as f64 - game.total_bet_amount()[0] as f64,
+        stacks: game.current_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: Some(prev_spot.player.clone()),
     };
@@ -743,6 +747,7 @@ fn splice_spots_chance(
         actions: player_actions,
         pot: new_chance_spot.pot,
         stack: new_chance_spot.stack,
+        stacks: new_chance_spot.stacks,
         equity_oop: 0.0,
         prev_player: Some(new_chance_spot.player.clone()),
     };
@@ -773,7 +778,7 @@ fn splice_spots_player(
     state: &mut GameState,
     spot_index: usize,
     actions_str: String,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     // println!("splice_spots_player() - spot_index: {}", spot_index);
     let prev_spot = &state.spots[spot_index - 1];
     let player = if prev_spot.player == "oop" {
@@ -814,6 +819,7 @@ fn splice_spots_player(
         actions: player_actions,
         pot: prev_spot.pot,     // Utiliser le même pot que le spot précédent
         stack: prev_spot.stack, // Utiliser le même stack que le spot précédent
+        stacks: prev_spot.stacks,
         equity_oop: 0.0,
         prev_player: Some(prev_spot.player.clone()),
     };
@@ -830,7 +836,7 @@ pub fn play(
     game: &mut PostFlopGame,
     state: &mut GameState,
     action_index: usize,
-) -> Result<SpecificResultData, String> {
+) -> Result<SpecificResultData, Error> {
     let spot_index = state.selected_spot_index as usize;
 
     // state.log_state("play()");
@@ -839,15 +845,20 @@ pub fn play(
 
     let spot: &mut Spot = match state.spots.get_mut(spot_index) {
         Some(s) => s,
-        None => return Err(format!("Spot à l'index {} non trouvé", spot_index)),
+        None => {
+            return Err(Error::InvalidState(format!(
+                "Spot not found at index {}",
+                spot_index
+            )))
+        }
     };
 
     // Vérifier que c'est bien un nœud de joueur
     if spot.spot_type != SpotType::Player {
-        return Err(format!(
-            "Le spot à l'index {} n'est pas un nœud joueur",
+        return Err(Error::InvalidState(format!(
+            "Spot at index {} is not a player node",
             spot_index
-        ));
+        )));
     }
 
     // Si une action est déjà sélectionnée, la désélectionner
@@ -861,7 +872,10 @@ pub fn play(
     if let Some(action) = spot.actions.get_mut(action_index) {
         action.is_selected = true;
     } else {
-        return Err(format!("Action à l'index {} non trouvée", action_index));
+        return Err(Error::InvalidAction(format!(
+            "Action not found at index {}",
+            action_index
+        )));
     }
 
     spot.selected_index = action_index as i32;
@@ -874,10 +888,12 @@ pub fn deal(
     game: &mut PostFlopGame,
     state: &mut GameState,
     card_index: usize,
-) -> Result<SpecificResultData, String> {
+) -> Result<SpecificResultData, Error> {
     // Check if there's a selected chance node
     if state.selected_chance_index == -1 {
-        return Err("Aucun nœud de chance sélectionné".to_string());
+        return Err(Error::InvalidState(
+            "No chance node is selected".to_string(),
+        ));
     }
 
     // Get the chance spot
@@ -886,15 +902,17 @@ pub fn deal(
 
     // Ensure it's a chance node
     if chance_spot.spot_type != SpotType::Chance {
-        return Err("Le spot sélectionné n'est pas un nœud de chance".to_string());
+        return Err(Error::InvalidState(
+            "Selected spot is not a chance node".to_string(),
+        ));
     }
 
     // Check if the card is dead/unavailable
     if card_index >= chance_spot.cards.len() || chance_spot.cards[card_index].is_dead {
-        return Err(format!(
-            "La carte à l'index {} est morte ou invalide",
+        return Err(Error::InvalidCard(format!(
+            "Card at index {} is dead or invalid",
             card_index
-        ));
+        )));
     }
 
     // Mark that we're in a dealing state
@@ -1018,7 +1036,7 @@ pub fn get_specific_chance_reports(
     append: &[usize],
     player: &str,
     num_actions: usize,
-) -> Result<SpecificChanceReportData, String> {
+) -> Result<SpecificChanceReportData, Error> {
     let buffer = get_chance_reports(game, append, num_actions);
     let mut offset = 0;
 
@@ -4,99 +4,409 @@ use crate::sliceop::*;
 use crate::utility::*;
 use std::io::{self, Write};
 use std::mem::MaybeUninit;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "custom-alloc")]
 use crate::alloc::*;
 
-struct DiscountParams {
-    alpha_t: f32,
-    beta_t: f32,
-    gamma_t: f32,
+/// Discount factors applied to the cumulative values at the beginning of an iteration.
+///
+/// Positive cumulative regrets are multiplied by `alpha_t`, negative cumulative regrets are
+/// multiplied by `beta_t`, and the cumulative strategy is multiplied by `gamma_t` before the
+/// values of the current iteration are added.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiscountParams {
+    pub alpha_t: f32,
+    pub beta_t: f32,
+    pub gamma_t: f32,
 }
 
 impl DiscountParams {
+    /// Creates the parameters used by the default [`DiscountedCfr`] algorithm.
+    #[inline]
     pub fn new(current_iteration: u32) -> Self {
+        DiscountedCfr::default().discount_params(current_iteration)
+    }
+}
+
+/// A trait that defines how the cumulative regrets and strategy are updated in each iteration.
+///
+/// The `solve_with` and `solve_step_with` functions accept any type implementing this trait.
+/// The following variants are provided by this crate:
+/// - [`VanillaCfr`]: the original CFR algorithm.
+/// - [`CfrPlus`]: CFR+ (regrets are floored at zero, linear averaging).
+/// - [`LinearCfr`]: Linear CFR (both regrets and strategy are weighted by the iteration).
+/// - [`DiscountedCfr`]: Discounted CFR with arbitrary (α, β, γ) parameters (default).
+/// - [`PredictiveCfrPlus`]: Predictive CFR+ (requires prediction storage, see
+///   [`PostFlopGame::allocate_prediction_memory`]).
+///
+/// [`PostFlopGame::allocate_prediction_memory`]: crate::PostFlopGame::allocate_prediction_memory
+pub trait CfrAlgorithm {
+    /// Returns the discount factors for the given iteration (0-indexed).
+    fn discount_params(&self, current_iteration: u32) -> DiscountParams;
+
+    /// Returns whether the cumulative regrets are floored at zero after each update.
+    #[inline]
+    fn floors_regrets(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the instantaneous regrets of the last iteration are used as a prediction
+    /// when computing the current strategy.
+    #[inline]
+    fn is_predictive(&self) -> bool {
+        false
+    }
+}
+
+/// Vanilla CFR: cumulative values are never discounted.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VanillaCfr;
+
+/// CFR+: cumulative regrets are floored at zero and the average strategy is weighted linearly.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CfrPlus;
+
+/// Linear CFR: both the regrets and the strategy of iteration `t` are weighted by `t`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LinearCfr;
+
+/// Discounted CFR with parameters (α, β, γ).
+///
+/// At iteration `t`, positive regrets are multiplied by `t^α / (t^α + 1)`, negative regrets by
+/// `t^β / (t^β + 1)`, and the cumulative strategy by `(t / (t + 1))^γ`. When
+/// `reset_strategy` is `true`, the cumulative strategy is reset when the number of iterations is
+/// a power of 4.
+///
+/// The default value (α = 1.5, β = 0, γ = 3, with resets) corresponds to the algorithm used by
+/// `solve` and `solve_step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiscountedCfr {
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub reset_strategy: bool,
+}
+
+/// Predictive CFR+: CFR+ whose strategy is computed from the cumulative regrets plus the
+/// instantaneous regrets of the last iteration. The average strategy is weighted quadratically.
+///
+/// This algorithm needs additional storage; call
+/// [`PostFlopGame::allocate_prediction_memory`] after allocating the memory.
+/// The compression feature is not supported.
+///
+/// [`PostFlopGame::allocate_prediction_memory`]: crate::PostFlopGame::allocate_prediction_memory
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PredictiveCfrPlus;
+
+impl Default for DiscountedCfr {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            alpha: 1.5,
+            beta: 0.0,
+            gamma: 3.0,
+            reset_strategy: true,
+        }
+    }
+}
+
+impl CfrAlgorithm for VanillaCfr {
+    #[inline]
+    fn discount_params(&self, _current_iteration: u32) -> DiscountParams {
+        DiscountParams {
+            alpha_t: 1.0,
+            beta_t: 1.0,
+            gamma_t: 1.0,
+        }
+    }
+}
+
+impl CfrAlgorithm for CfrPlus {
+    #[inline]
+    fn discount_params(&self, current_iteration: u32) -> DiscountParams {
+        let t = current_iteration as f64;
+        DiscountParams {
+            alpha_t: 1.0,
+            beta_t: 0.0,
+            gamma_t: (t / (t + 1.0)) as f32,
+        }
+    }
+
+    #[inline]
+    fn floors_regrets(&self) -> bool {
+        true
+    }
+}
+
+impl CfrAlgorithm for LinearCfr {
+    #[inline]
+    fn discount_params(&self, current_iteration: u32) -> DiscountParams {
+        let t = current_iteration as f64;
+        let coef = (t / (t + 1.0)) as f32;
+        DiscountParams {
+            alpha_t: coef,
+            beta_t: coef,
+            gamma_t: coef,
+        }
+    }
+}
+
+impl CfrAlgorithm for DiscountedCfr {
+    fn discount_params(&self, current_iteration: u32) -> DiscountParams {
         // 0, 1, 4, 16, 64, 256, ...
         let nearest_lower_power_of_4 = match current_iteration {
             0 => 0,
             x => 1 << ((x.leading_zeros() ^ 31) & !1),
         };
 
-        let t_alpha = (current_iteration as i32 - 1).max(0) as f64;
-        let t_gamma = (current_iteration - nearest_lower_power_of_4) as f64;
+        let t_regret = (current_iteration as i32 - 1).max(0) as f64;
+        let t_gamma = if self.reset_strategy {
+            (current_iteration - nearest_lower_power_of_4) as f64
+        } else {
+            current_iteration as f64
+        };
 
-        let pow_alpha = t_alpha * t_alpha.sqrt();
-        let pow_gamma = (t_gamma / (t_gamma + 1.0)).powi(3);
+        let pow_alpha = t_regret.powf(self.alpha);
+        let pow_beta = t_regret.powf(self.beta);
+        let pow_gamma = (t_gamma / (t_gamma + 1.0)).powf(self.gamma);
 
-        Self {
+        DiscountParams {
             alpha_t: (pow_alpha / (pow_alpha + 1.0)) as f32,
-            beta_t: 0.5,
+            beta_t: (pow_beta / (pow_beta + 1.0)) as f32,
             gamma_t: pow_gamma as f32,
         }
     }
 }
 
+impl CfrAlgorithm for PredictiveCfrPlus {
+    #[inline]
+    fn discount_params(&self, current_iteration: u32) -> DiscountParams {
+        let t = current_iteration as f64;
+        DiscountParams {
+            alpha_t: 1.0,
+            beta_t: 0.0,
+            gamma_t: (t / (t + 1.0)).powi(2) as f32,
+        }
+    }
+
+    #[inline]
+    fn floors_regrets(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn is_predictive(&self) -> bool {
+        true
+    }
+}
+
+/// Parameters passed to `solve_recursive`.
+#[derive(Clone, Copy)]
+struct StepParams {
+    discount: DiscountParams,
+    floors_regrets: bool,
+    is_predictive: bool,
+}
+
+impl StepParams {
+    #[inline]
+    fn new<A: CfrAlgorithm + ?Sized>(algorithm: &A, current_iteration: u32) -> Self {
+        Self {
+            discount: algorithm.discount_params(current_iteration),
+            floors_regrets: algorithm.floors_regrets(),
+            is_predictive: algorithm.is_predictive(),
+        }
+    }
+}
+
 /// Performs Discounted CFR algorithm until the given number of iterations or exploitability is
 /// satisfied.
 ///
 /// This method returns the exploitability of the obtained strategy.
+#[inline]
 pub fn solve<T: Game>(
     game: &mut T,
     max_num_iterations: u32,
     target_exploitability: f32,
     print_progress: bool,
 ) -> f32 {
-    if game.is_solved() {
-        panic!("Game is already solved");
-    }
+    solve_with(
+        game,
+        &DiscountedCfr::default(),
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    )
+}
 
-    if !game.is_ready() {
-        panic!("Game is not ready");
+/// Performs the given CFR algorithm until the given number of iterations or exploitability is
+/// satisfied.
+///
+/// This method returns the exploitability of the obtained strategy.
+pub fn solve_with<T: Game, A: CfrAlgorithm + ?Sized>(
+    game: &mut T,
+    algorithm: &A,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    let mut last_exploitability = 0.0;
+    let exploitability = solve_with_callback(
+        game,
+        algorithm,
+        max_num_iterations,
+        target_exploitability,
+        |_, progress| {
+            if print_progress {
+                if let Some(exploitability) = progress.exploitability {
+                    last_exploitability = exploitability;
+                }
+                print!(
+                    "\riteration: {} / {} ",
+                    progress.iteration, max_num_iterations
+                );
+                print!("(exploitability = {last_exploitability:.4e})");
+                io::stdout().flush().unwrap();
+            }
+            SolveControl::Continue
+        },
+    );
+
+    if print_progress {
+        println!();
+        io::stdout().flush().unwrap();
     }
 
+    exploitability
+}
+
+/// Progress information passed to the callback of [`solve_with_callback`].
+///
+/// `iteration` and `max_num_iterations` count the iterations from the very beginning, even when
+/// the solving process is resumed by [`resume_with_callback`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveProgress {
+    /// The number of completed iterations.
+    pub iteration: u32,
+
+    /// The maximum number of iterations.
+    pub max_num_iterations: u32,
+
+    /// The exploitability of the current strategy, if it was computed at this iteration.
+    ///
+    /// The exploitability is computed at the beginning, every 10 iterations, and at the last
+    /// iteration.
+    pub exploitability: Option<f32>,
+
+    /// The elapsed time since the solving process started.
+    pub elapsed: Duration,
+
+    /// The memory allocated by the game in bytes.
+    pub memory_usage: u64,
+}
+
+/// The return value of the callback of [`solve_with_callback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveControl {
+    /// Continues the solving process.
+    Continue,
+
+    /// Stops the solving process after the current iteration.
+    Stop,
+}
+
+/// Performs the given CFR algorithm until the given number of iterations or exploitability is
+/// satisfied, or until the `callback` returns [`SolveControl::Stop`].
+///
+/// The `callback` receives the game and the progress information. It is called once before the
+/// first iteration and then after every iteration.
+/// The obtained strategy is finalized even when the solving process is stopped by the `callback`.
+///
+/// This method returns the exploitability of the obtained strategy.
+#[inline]
+pub fn solve_with_callback<T, A, F>(
+    game: &mut T,
+    algorithm: &A,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    callback: F,
+) -> f32
+where
+    T: Game,
+    A: CfrAlgorithm + ?Sized,
+    F: FnMut(&T, &SolveProgress) -> SolveControl,
+{
+    resume_with_callback(
+        game,
+        algorithm,
+        0,
+        max_num_iterations,
+        target_exploitability,
+        callback,
+    )
+}
+
+/// Same as [`solve_with_callback`], but starts from the `start_iteration`-th iteration.
+///
+/// This is used to resume the solving process restored from a checkpoint with the same
+/// discounting schedule. `max_num_iterations` is the total number of iterations including the
+/// ones already performed.
+pub fn resume_with_callback<T, A, F>(
+    game: &mut T,
+    algorithm: &A,
+    start_iteration: u32,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    mut callback: F,
+) -> f32
+where
+    T: Game,
+    A: CfrAlgorithm + ?Sized,
+    F: FnMut(&T, &SolveProgress) -> SolveControl,
+{
+    check_solvable(game, algorithm);
+
+    let start_time = Instant::now();
+    let memory_usage = game.allocated_memory();
+
     let mut root = game.root();
     let mut exploitability = compute_exploitability(game);
+    let mut is_exploitability_updated = true;
 
-    if print_progress {
-        print!("iteration: 0 / {max_num_iterations} ");
-        print!("(exploitability = {exploitability:.4e})");
-        io::stdout().flush().unwrap();
-    }
+    let mut progress = SolveProgress {
+        iteration: start_iteration,
+        max_num_iterations,
+        exploitability: Some(exploitability),
+        elapsed: start_time.elapsed(),
+        memory_usage,
+    };
+
+    let mut control = callback(game, &progress);
 
-    for t in 0..max_num_iterations {
-        if exploitability <= target_exploitability {
+    for t in start_iteration..max_num_iterations {
+        if control == SolveControl::Stop || exploitability <= target_exploitability {
             break;
         }
 
-        let params = DiscountParams::new(t);
+        let params = StepParams::new(algorithm, t);
+        solve_iteration(game, &mut root, &params);
 
-        // alternating updates
-        for player in 0..2 {
-            let mut result = Vec::with_capacity(game.num_private_hands(player));
-            solve_recursive(
-                result.spare_capacity_mut(),
-                game,
-                &mut root,
-                player,
-                game.initial_weights(player ^ 1),
-                &params,
-            );
-        }
-
-        if (t + 1) % 10 == 0 || t + 1 == max_num_iterations {
+        is_exploitability_updated = (t + 1) % 10 == 0 || t + 1 == max_num_iterations;
+        if is_exploitability_updated {
             exploitability = compute_exploitability(game);
         }
 
-        if print_progress {
-            print!("\riteration: {} / {} ", t + 1, max_num_iterations);
-            print!("(exploitability = {exploitability:.4e})");
-            io::stdout().flush().unwrap();
-        }
+        progress.iteration = t + 1;
+        progress.exploitability = is_exploitability_updated.then_some(exploitability);
+        progress.elapsed = start_time.elapsed();
+
+        control = callback(game, &progress);
     }
 
-    if print_progress {
-        println!();
-        io::stdout().flush().unwrap();
+    // make sure that the returned value reflects the final strategy
+    if !is_exploitability_updated {
+        exploitability = compute_exploitability(game);
     }
 
     finalize(game);
@@ -107,28 +417,107 @@ pub fn solve<T: Game>(
 /// Proceeds Discounted CFR algorithm for one iteration.
 #[inline]
 pub fn solve_step<T: Game>(game: &T, current_iteration: u32) {
-    if game.is_solved() {
-        panic!("Game is already solved");
-    }
+    solve_step_with(game, &DiscountedCfr::default(), current_iteration);
+}
 
-    if !game.is_ready() {
-        panic!("Game is not ready");
-    }
+/// Proceeds the given CFR algorithm for one iteration.
+pub fn solve_step_with<T: Game, A: CfrAlgorithm + ?Sized>(
+    game: &T,
+    algorithm: &A,
+    current_iteration: u32,
+) {
+    check_solvable(game, algorithm);
 
     let mut root = game.root();
-    let params = DiscountParams::new(current_iteration);
+    let params = StepParams::new(algorithm, current_iteration);
+    solve_iteration(game, &mut root, &params);
+}
+
+/// Performs one iteration with alternating updates.
+fn solve_iteration<T: Game>(game: &T, root: &mut T::Node, params: &StepParams) {
+    let gadget_player = game.gadget_player();
 
-    // alternating updates
     for player in 0..2 {
         let mut result = Vec::with_capacity(game.num_private_hands(player));
+
+        // the reach probabilities of the gadget player are scaled by the probability of following
+        let gadget_reach;
+        let cfreach = if gadget_player == Some(player ^ 1) {
+            gadget_reach =
+                gadget_follow_reach(&game.gadget_regrets(), game.initial_weights(player ^ 1));
+            &gadget_reach
+        } else {
+            game.initial_weights(player ^ 1)
+        };
+
         solve_recursive(
             result.spare_capacity_mut(),
             game,
-            &mut root,
+            root,
             player,
-            game.initial_weights(player ^ 1),
-            &params,
+            cfreach,
+            params,
         );
+
+        if gadget_player == Some(player) {
+            unsafe { result.set_len(game.num_private_hands(player)) };
+            update_gadget_regrets(&mut game.gadget_regrets(), game.gadget_values(), &result);
+        }
+    }
+}
+
+/// Computes the reach probabilities of the gadget player after choosing to follow.
+#[inline]
+fn gadget_follow_reach(regrets: &[f32], weights: &[f32]) -> Vec<f32> {
+    let (terminate, follow) = regrets.split_at(weights.len());
+    weights
+        .iter()
+        .zip(terminate.iter().zip(follow))
+        .map(|(&w, (&t, &f))| {
+            let (t, f) = (max(t, 0.0), max(f, 0.0));
+            let denom = t + f;
+            w * if denom > 0.0 { f / denom } else { 0.5 }
+        })
+        .collect()
+}
+
+/// Updates the cumulative regrets of the re-solving gadget by CFR+.
+#[inline]
+fn update_gadget_regrets(regrets: &mut [f32], values: &[f32], cfvalues: &[f32]) {
+    let (terminate, follow) = regrets.split_at_mut(values.len());
+    terminate
+        .iter_mut()
+        .zip(follow)
+        .zip(values.iter().zip(cfvalues))
+        .for_each(|((t, f), (&v, &cfv))| {
+            let (pt, pf) = (max(*t, 0.0), max(*f, 0.0));
+            let denom = pt + pf;
+            let prob_follow = if denom > 0.0 { pf / denom } else { 0.5 };
+            let ev = v * (1.0 - prob_follow) + cfv * prob_follow;
+            *t = max(*t + v - ev, 0.0);
+            *f = max(*f + cfv - ev, 0.0);
+        });
+}
+
+/// Panics if the game cannot be solved by the given algorithm.
+#[inline]
+fn check_solvable<T: Game, A: CfrAlgorithm + ?Sized>(game: &T, algorithm: &A) {
+    if game.is_solved() {
+        panic!("Game is already solved");
+    }
+
+    if !game.is_ready() {
+        panic!("Game is not ready");
+    }
+
+    if algorithm.is_predictive() {
+        if game.is_compression_enabled() {
+            panic!("Predictive algorithms do not support compression");
+        }
+
+        if !game.is_prediction_enabled() {
+            panic!("Prediction storage is not allocated");
+        }
     }
 }
 
@@ -139,7 +528,7 @@ fn solve_recursive<T: Game>(
     node: &mut T::Node,
     player: usize,
     cfreach: &[f32],
-    params: &DiscountParams,
+    params: &StepParams,
 ) {
     // return the counterfactual values when the `node` is terminal
     if node.is_terminal() {
@@ -239,6 +628,8 @@ fn solve_recursive<T: Game>(
         // compute the strategy by regret-maching algorithm
         let mut strategy = if game.is_compression_enabled() {
             regret_matching_compressed(node.regrets_compressed(), num_actions)
+        } else if params.is_predictive {
+            regret_matching_predictive(node.regrets(), node.predictions(), num_actions)
         } else {
             regret_matching(node.regrets(), num_actions)
         };
@@ -255,7 +646,7 @@ fn solve_recursive<T: Game>(
         if game.is_compression_enabled() {
             // update the cumulative strategy
             let scale = node.strategy_scale();
-            let decoder = params.gamma_t * scale / u16::MAX as f32;
+            let decoder = params.discount.gamma_t * scale / u16::MAX as f32;
             let cum_strategy = node.strategy_compressed_mut();
 
             strategy.iter_mut().zip(&*cum_strategy).for_each(|(x, y)| {
@@ -275,8 +666,8 @@ fn solve_recursive<T: Game>(
 
             // update the cumulative regret
             let scale = node.regret_scale();
-            let alpha_decoder = params.alpha_t * scale / i16::MAX as f32;
-            let beta_decoder = params.beta_t * scale / i16::MAX as f32;
+            let alpha_decoder = params.discount.alpha_t * scale / i16::MAX as f32;
+            let beta_decoder = params.discount.beta_t * scale / i16::MAX as f32;
             let cum_regret = node.regrets_compressed_mut();
 
             cfv_actions.iter_mut().zip(&*cum_regret).for_each(|(x, y)| {
@@ -295,18 +686,22 @@ fn solve_recursive<T: Game>(
                 })
             }
 
+            if params.floors_regrets {
+                cfv_actions.iter_mut().for_each(|x| *x = max(*x, 0.0));
+            }
+
             let new_scale = encode_signed_slice(cum_regret, &cfv_actions);
             node.set_regret_scale(new_scale);
         } else {
             // update the cumulative strategy
-            let gamma = params.gamma_t;
+            let gamma = params.discount.gamma_t;
             let cum_strategy = node.strategy_mut();
             cum_strategy.iter_mut().zip(&strategy).for_each(|(x, y)| {
                 *x = *x * gamma + *y;
             });
 
             // update the cumulative regret
-            let (alpha, beta) = (params.alpha_t, params.beta_t);
+            let (alpha, beta) = (params.discount.alpha_t, params.discount.beta_t);
             let cum_regret = node.regrets_mut();
             cum_regret.iter_mut().zip(&*cfv_actions).for_each(|(x, y)| {
                 let coef = if x.is_sign_positive() { alpha } else { beta };
@@ -315,6 +710,19 @@ fn solve_recursive<T: Game>(
             cum_regret.chunks_exact_mut(num_hands).for_each(|row| {
                 sub_slice(row, result);
             });
+
+            if params.floors_regrets {
+                cum_regret.iter_mut().for_each(|x| *x = max(*x, 0.0));
+            }
+
+            // store the instantaneous regrets as the prediction of the next iteration
+            if params.is_predictive {
+                let prediction = node.predictions_mut();
+                prediction.copy_from_slice(&cfv_actions);
+                prediction.chunks_exact_mut(num_hands).for_each(|row| {
+                    sub_slice(row, result);
+                });
+            }
         }
     }
     // if the current player is not `player`
@@ -322,6 +730,8 @@ fn solve_recursive<T: Game>(
         // compute the strategy by regret-matching algorithm
         let mut cfreach_actions = if game.is_compression_enabled() {
             regret_matching_compressed(node.regrets_compressed(), num_actions)
+        } else if params.is_predictive {
+            regret_matching_predictive(node.regrets(), node.predictions(), num_actions)
         } else {
             regret_matching(node.regrets(), num_actions)
         };
@@ -442,3 +852,25 @@ fn regret_matching_compressed(regret: &[i16], num_actions: usize) -> Vec<f32> {
 
     strategy
 }
+
+/// Computes the strategy by regret-matching algorithm using the predicted regrets.
+#[cfg(feature = "custom-alloc")]
+#[inline]
+fn regret_matching_predictive(
+    regret: &[f32],
+    prediction: &[f32],
+    num_actions: usize,
+) -> Vec<f32, StackAlloc> {
+    let mut predicted = Vec::with_capacity_in(regret.len(), StackAlloc);
+    predicted.extend(regret.iter().zip(prediction).map(|(r, p)| r + p));
+    regret_matching(&predicted, num_actions)
+}
+
+/// Computes the strategy by regret-matching algorithm using the predicted regrets.
+#[cfg(not(feature = "custom-alloc"))]
+#[inline]
+fn regret_matching_predictive(regret: &[f32], prediction: &[f32], num_actions: usize) -> Vec<f32> {
+    let mut predicted = Vec::with_capacity(regret.len());
+    predicted.extend(regret.iter().zip(prediction).map(|(r, p)| r + p));
+    regret_matching(&predicted, num_actions)
+}
@@ -0,0 +1,447 @@
+use super::*;
+use crate::bet_size::*;
+use crate::error::*;
+
+/// A struct containing the game tree configuration of the three-player game.
+///
+/// The game starts at the river, and `stacks` are the remaining stacks of the players at the
+/// beginning of the river. The starting pot is treated as being contributed equally by the three
+/// players.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let bet_sizes = BetSizeOptions::try_from(("50%, a", "3x")).unwrap();
+///
+/// let tree_config = ThreeWayTreeConfig {
+///     starting_pot: 300,
+///     stacks: [900, 900, 500],
+///     rake_rate: 0.0,
+///     rake_cap: 0.0,
+///     river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes],
+///     add_allin_threshold: 1.5,
+///     force_allin_threshold: 0.15,
+///     merging_threshold: 0.1,
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ThreeWayTreeConfig {
+    /// Starting pot size. Must be greater than `0`.
+    pub starting_pot: i32,
+
+    /// Initial stack of each player. Must be greater than `0`.
+    pub stacks: [i32; 3],
+
+    /// Rake rate. Must be between `0.0` and `1.0`, inclusive.
+    pub rake_rate: f64,
+
+    /// Rake cap. Must be non-negative.
+    pub rake_cap: f64,
+
+    /// Bet size options of each player for the river.
+    pub river_bet_sizes: [BetSizeOptions; 3],
+
+    /// Add all-in action if the ratio of maximum bet size to the pot is below or equal to this
+    /// value (set `0.0` to disable).
+    pub add_allin_threshold: f64,
+
+    /// Force all-in action if the SPR (stack/pot) after the call is below or equal to this value
+    /// (set `0.0` to disable).
+    pub force_allin_threshold: f64,
+
+    /// Merge bet actions if there are bet actions with "close" values (set `0.0` to disable).
+    ///
+    /// See [`TreeConfig::merging_threshold`] for the details.
+    ///
+    /// [`TreeConfig::merging_threshold`]: crate::TreeConfig::merging_threshold
+    pub merging_threshold: f64,
+}
+
+/// A struct representing an action tree of the three-player game.
+#[derive(Debug, Default)]
+pub struct ThreeWayActionTree {
+    config: ThreeWayTreeConfig,
+    root: ThreeWayNode,
+}
+
+/// A struct representing a node of the three-player game tree.
+#[derive(Debug, Default)]
+pub(crate) struct ThreeWayNode {
+    /// Acting player, or `PLAYER_TERMINAL_FLAG` if the node is terminal.
+    pub(crate) player: u8,
+
+    /// Amount committed by each player on the river.
+    pub(crate) amounts: [i32; 3],
+
+    /// Whether each player has folded.
+    pub(crate) folded: [bool; 3],
+
+    pub(crate) actions: Vec<Action>,
+    pub(crate) children: Vec<ThreeWayNode>,
+
+    /// Cumulative strategy: `strategy[action * num_hands + hand]`.
+    pub(crate) strategy: Vec<f32>,
+
+    /// Cumulative regrets: `regrets[action * num_hands + hand]`.
+    pub(crate) regrets: Vec<f32>,
+}
+
+/// Betting state used while building the tree.
+#[derive(Clone, Copy)]
+struct BuildTreeInfo {
+    amounts: [i32; 3],
+    folded: [bool; 3],
+    acted: [bool; 3],
+    num_bets: i32,
+    last_raise: i32,
+}
+
+impl ThreeWayActionTree {
+    /// Creates a new [`ThreeWayActionTree`] with the specified configuration.
+    #[inline]
+    pub fn new(config: ThreeWayTreeConfig) -> Result<Self, Error> {
+        Self::check_config(&config)?;
+        let mut ret = Self {
+            config,
+            root: ThreeWayNode::default(),
+        };
+        ret.build_tree();
+        Ok(ret)
+    }
+
+    /// Obtains the configuration of the game tree.
+    #[inline]
+    pub fn config(&self) -> &ThreeWayTreeConfig {
+        &self.config
+    }
+
+    /// Returns the number of player nodes (i.e., non-terminal nodes) of the tree.
+    #[inline]
+    pub fn num_action_nodes(&self) -> u64 {
+        self.root.num_action_nodes()
+    }
+
+    /// Ejects the fields.
+    #[inline]
+    pub(super) fn eject(self) -> (ThreeWayTreeConfig, ThreeWayNode) {
+        (self.config, self.root)
+    }
+
+    /// Checks the configuration.
+    #[inline]
+    fn check_config(config: &ThreeWayTreeConfig) -> Result<(), Error> {
+        if config.starting_pot <= 0 {
+            return Err(Error::InvalidConfig(format!(
+                "Starting pot must be positive: {}",
+                config.starting_pot
+            )));
+        }
+
+        if let Some(stack) = config.stacks.iter().find(|&&stack| stack <= 0) {
+            return Err(Error::InvalidConfig(format!(
+                "Stack must be positive: {stack}"
+            )));
+        }
+
+        if config.rake_rate < 0.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Rake rate must be non-negative: {}",
+                config.rake_rate
+            )));
+        }
+
+        if config.rake_rate > 1.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Rake rate must be less than or equal to 1.0: {}",
+                config.rake_rate
+            )));
+        }
+
+        if config.rake_cap < 0.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Rake cap must be non-negative: {}",
+                config.rake_cap
+            )));
+        }
+
+        if config.add_allin_threshold < 0.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Add all-in threshold must be non-negative: {}",
+                config.add_allin_threshold
+            )));
+        }
+
+        if config.force_allin_threshold < 0.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Force all-in threshold must be non-negative: {}",
+                config.force_allin_threshold
+            )));
+        }
+
+        if config.merging_threshold < 0.0 {
+            return Err(Error::InvalidConfig(format!(
+                "Merging threshold must be non-negative: {}",
+                config.merging_threshold
+            )));
+        }
+
+        let is_big_blind = |size: &BetSize| matches!(size, BetSize::BigBlindRelative(_));
+        if config.river_bet_sizes.iter().any(|options| {
+            options.bet.iter().any(is_big_blind)
+                || options.raise.iter().any(is_big_blind)
+                || options
+                    .rules
+                    .iter()
+                    .any(|rule| rule.sizes.iter().any(is_big_blind))
+        }) {
+            return Err(Error::InvalidBetSize(
+                "\"bb\" bet sizes are not supported in the three-player game".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Builds the action tree.
+    #[inline]
+    fn build_tree(&mut self) {
+        let info = BuildTreeInfo {
+            amounts: [0; 3],
+            folded: [false; 3],
+            acted: [false; 3],
+            num_bets: 0,
+            last_raise: 0,
+        };
+
+        let mut root = ThreeWayNode::default();
+        self.build_tree_recursive(&mut root, info, 0);
+        self.root = root;
+    }
+
+    /// Recursively builds the action tree.
+    fn build_tree_recursive(&self, node: &mut ThreeWayNode, info: BuildTreeInfo, player: u8) {
+        node.amounts = info.amounts;
+        node.folded = info.folded;
+
+        let num_remaining = info.folded.iter().filter(|&&f| !f).count();
+        let next_player = (0..3)
+            .map(|i| (player + i) % 3)
+            .find(|&p| self.needs_action(&info, p as usize));
+
+        let Some(player) = next_player.filter(|_| num_remaining > 1) else {
+            node.player = PLAYER_TERMINAL_FLAG;
+            return;
+        };
+
+        node.player = player;
+        self.push_actions(node, &info);
+
+        for (action, child) in node.actions.iter().zip(node.children.iter_mut()) {
+            let next_info = self.create_next(&info, player as usize, *action);
+            self.build_tree_recursive(child, next_info, (player + 1) % 3);
+        }
+    }
+
+    /// Returns whether the given player still needs to act in the current betting round.
+    #[inline]
+    fn needs_action(&self, info: &BuildTreeInfo, player: usize) -> bool {
+        let max_amount = *info.amounts.iter().max().unwrap();
+        !info.folded[player]
+            && info.amounts[player] < self.config.stacks[player]
+            && (!info.acted[player] || info.amounts[player] < max_amount)
+    }
+
+    /// Pushes all possible actions to the given node.
+    fn push_actions(&self, node: &mut ThreeWayNode, info: &BuildTreeInfo) {
+        let player = node.player as usize;
+        let stacks = &self.config.stacks;
+
+        let prev_amount = *info.amounts.iter().max().unwrap();
+        let to_call = prev_amount - info.amounts[player];
+        let pot = self.config.starting_pot + info.amounts.iter().sum::<i32>() + to_call;
+
+        // raising is meaningful only if another player can respond
+        let max_opponent_stack = (0..3)
+            .filter(|&p| p != player && !info.folded[p] && info.amounts[p] < stacks[p])
+            .map(|p| stacks[p])
+            .max();
+        let max_amount = match max_opponent_stack {
+            Some(stack) => stacks[player].min(stack.max(prev_amount)),
+            None => prev_amount.min(stacks[player]),
+        };
+        let can_raise = max_amount > prev_amount;
+        let min_amount = (prev_amount + info.last_raise.max(1)).clamp(1, max_amount);
+
+        let spr_after_call = (max_amount - prev_amount) as f64 / pot as f64;
+        let compute_geometric = |num_streets: i32, max_ratio: f64| {
+            let ratio = ((2.0 * spr_after_call + 1.0).powf(1.0 / num_streets as f64) - 1.0) / 2.0;
+            (pot as f64 * ratio.min(max_ratio)).round() as i32
+        };
+
+        let bet_options = &self.config.river_bet_sizes[player];
+        let mut actions = Vec::new();
+
+        if to_call == 0 {
+            // check
+            actions.push(Action::Check);
+
+            if can_raise {
+                // bet
+                for &bet_size in bet_options.sizes(0, spr_after_call) {
+                    match bet_size {
+                        BetSize::PotRelative(ratio) => {
+                            let amount = (pot as f64 * ratio).round() as i32;
+                            actions.push(Action::Bet(amount));
+                        }
+                        BetSize::PrevBetRelative(_) => panic!("Unexpected `PrevBetRelative`"),
+                        BetSize::Additive(adder, _) => actions.push(Action::Bet(adder)),
+                        BetSize::BigBlindRelative(_) => panic!("Unexpected `BigBlindRelative`"),
+                        BetSize::Geometric(num_streets, max_ratio) => {
+                            let amount = compute_geometric(num_streets.max(1), max_ratio);
+                            actions.push(Action::Bet(amount));
+                        }
+                        BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
+                    }
+                }
+
+                // all-in
+                if max_amount <= (pot as f64 * self.config.add_allin_threshold).round() as i32 {
+                    actions.push(Action::AllIn(max_amount));
+                }
+            }
+        } else {
+            // fold
+            actions.push(Action::Fold);
+
+            // call
+            actions.push(Action::Call);
+
+            if can_raise {
+                // raise
+                for &bet_size in bet_options.sizes(info.num_bets, spr_after_call) {
+                    match bet_size {
+                        BetSize::PotRelative(ratio) => {
+                            let amount = prev_amount + (pot as f64 * ratio).round() as i32;
+                            actions.push(Action::Raise(amount));
+                        }
+                        BetSize::PrevBetRelative(ratio) => {
+                            let amount = (prev_amount as f64 * ratio).round() as i32;
+                            actions.push(Action::Raise(amount));
+                        }
+                        BetSize::Additive(adder, raise_cap) => {
+                            if raise_cap == 0 || info.num_bets <= raise_cap {
+                                actions.push(Action::Raise(prev_amount + adder));
+                            }
+                        }
+                        BetSize::BigBlindRelative(_) => panic!("Unexpected `BigBlindRelative`"),
+                        BetSize::Geometric(num_streets, max_ratio) => {
+                            let amount = compute_geometric(num_streets.max(1), max_ratio);
+                            actions.push(Action::Raise(prev_amount + amount));
+                        }
+                        BetSize::AllIn => actions.push(Action::AllIn(max_amount)),
+                    }
+                }
+
+                // all-in
+                let allin_threshold = pot as f64 * self.config.add_allin_threshold;
+                if max_amount <= prev_amount + allin_threshold.round() as i32 {
+                    actions.push(Action::AllIn(max_amount));
+                }
+            }
+        }
+
+        // the pot after every live opponent calls `amount` (as far as their stacks allow)
+        let is_above_threshold = |amount: i32| {
+            let new_pot = self.config.starting_pot
+                + (0..3)
+                    .map(|p| match p {
+                        _ if p == player => amount,
+                        _ if info.folded[p] => info.amounts[p],
+                        _ => amount.min(stacks[p]).max(info.amounts[p]),
+                    })
+                    .sum::<i32>();
+            let threshold = (new_pot as f64 * self.config.force_allin_threshold).round() as i32;
+            max_amount <= amount + threshold
+        };
+
+        // clamp bet amounts
+        for action in actions.iter_mut() {
+            match *action {
+                Action::Bet(amount) => {
+                    let clamped = amount.clamp(min_amount, max_amount);
+                    if is_above_threshold(clamped) {
+                        *action = Action::AllIn(max_amount);
+                    } else if clamped != amount {
+                        *action = Action::Bet(clamped);
+                    }
+                }
+                Action::Raise(amount) => {
+                    let clamped = amount.clamp(min_amount, max_amount);
+                    if is_above_threshold(clamped) {
+                        *action = Action::AllIn(max_amount);
+                    } else if clamped != amount {
+                        *action = Action::Raise(clamped);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // remove duplicates
+        actions.sort_unstable();
+        actions.dedup();
+
+        // merge bet actions with close amounts
+        actions = merge_bet_actions(actions, pot, prev_amount, self.config.merging_threshold);
+
+        node.children = actions.iter().map(|_| ThreeWayNode::default()).collect();
+        node.actions = actions;
+    }
+
+    /// Computes the betting state after the given action.
+    #[inline]
+    fn create_next(&self, info: &BuildTreeInfo, player: usize, action: Action) -> BuildTreeInfo {
+        let mut next = *info;
+        let prev_amount = *info.amounts.iter().max().unwrap();
+
+        match action {
+            Action::Fold => next.folded[player] = true,
+            Action::Call => next.amounts[player] = prev_amount.min(self.config.stacks[player]),
+            Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => {
+                next.num_bets += 1;
+                next.last_raise = next.last_raise.max(amount - prev_amount);
+                next.amounts[player] = amount;
+                next.acted = [false; 3];
+            }
+            _ => {}
+        }
+
+        next.acted[player] = true;
+        next
+    }
+}
+
+impl ThreeWayNode {
+    #[inline]
+    pub(crate) fn is_terminal(&self) -> bool {
+        self.player & PLAYER_TERMINAL_FLAG != 0
+    }
+
+    #[inline]
+    pub(crate) fn num_actions(&self) -> usize {
+        self.actions.len()
+    }
+
+    fn num_action_nodes(&self) -> u64 {
+        if self.is_terminal() {
+            0
+        } else {
+            1 + self
+                .children
+                .iter()
+                .map(|child| child.num_action_nodes())
+                .sum::<u64>()
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use super::*;
+use crate::error::*;
+use crate::hand::*;
+use crate::utility::*;
+use std::mem;
+
+impl ThreeWayGame {
+    /// Creates a new empty [`ThreeWayGame`] (needs `update_config()` before solving).
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`ThreeWayGame`] with the specified configuration.
+    #[inline]
+    pub fn with_config(
+        card_config: ThreeWayCardConfig,
+        action_tree: ThreeWayActionTree,
+    ) -> Result<Self, Error> {
+        let mut game = Self::new();
+        game.update_config(card_config, action_tree)?;
+        Ok(game)
+    }
+
+    /// Updates the game configuration. The solved result will be lost.
+    #[inline]
+    pub fn update_config(
+        &mut self,
+        card_config: ThreeWayCardConfig,
+        action_tree: ThreeWayActionTree,
+    ) -> Result<(), Error> {
+        self.is_memory_allocated = false;
+        self.is_solved = false;
+        self.card_config = card_config;
+        (self.tree_config, self.root) = action_tree.eject();
+
+        self.check_card_config()?;
+        self.init_hands();
+        self.back_to_root();
+
+        if self.num_combinations == 0.0 {
+            return Err(Error::InvalidRange(
+                "Valid card assignment does not exist".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Obtains the card configuration.
+    #[inline]
+    pub fn card_config(&self) -> &ThreeWayCardConfig {
+        &self.card_config
+    }
+
+    /// Obtains the tree configuration.
+    #[inline]
+    pub fn tree_config(&self) -> &ThreeWayTreeConfig {
+        &self.tree_config
+    }
+
+    /// Returns the number of private hands of the given player.
+    #[inline]
+    pub fn num_private_hands(&self, player: usize) -> usize {
+        self.private_cards[player].len()
+    }
+
+    /// Returns the private hands of the given player.
+    #[inline]
+    pub fn private_cards(&self, player: usize) -> &[(Card, Card)] {
+        &self.private_cards[player]
+    }
+
+    /// Returns the initial weights of the private hands of the given player.
+    #[inline]
+    pub fn initial_weights(&self, player: usize) -> &[f32] {
+        &self.initial_weights[player]
+    }
+
+    /// Returns whether the memory is allocated.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.is_memory_allocated
+    }
+
+    /// Returns whether the game is solved.
+    #[inline]
+    pub fn is_solved(&self) -> bool {
+        self.is_solved
+    }
+
+    /// Returns the memory usage in bytes when the memory is allocated.
+    #[inline]
+    pub fn memory_usage(&self) -> u64 {
+        self.memory_usage_recursive(&self.root)
+    }
+
+    /// Allocates the memory for the cumulative strategy and regrets.
+    #[inline]
+    pub fn allocate_memory(&mut self) {
+        let num_hands = [0, 1, 2].map(|player| self.num_private_hands(player));
+        Self::allocate_memory_recursive(&mut self.root, &num_hands);
+        self.is_memory_allocated = true;
+        self.is_solved = false;
+    }
+
+    /// Moves the current node back to the root node.
+    #[inline]
+    pub fn back_to_root(&mut self) {
+        self.action_history.clear();
+    }
+
+    /// Returns the history of the current node (indices of the played actions).
+    #[inline]
+    pub fn history(&self) -> &[usize] {
+        &self.action_history
+    }
+
+    /// Plays the given action. The `action` is an index of [`available_actions`].
+    ///
+    /// [`available_actions`]: #method.available_actions
+    #[inline]
+    pub fn play(&mut self, action: usize) {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+
+        if action >= self.node().num_actions() {
+            panic!("Invalid action");
+        }
+
+        self.action_history.push(action);
+    }
+
+    /// Returns whether the current node is a terminal node.
+    #[inline]
+    pub fn is_terminal_node(&self) -> bool {
+        self.node().is_terminal()
+    }
+
+    /// Returns the current player (`0`, `1`, or `2`).
+    ///
+    /// Panics if the current node is a terminal node.
+    #[inline]
+    pub fn current_player(&self) -> usize {
+        if self.is_terminal_node() {
+            panic!("Terminal node is not allowed");
+        }
+        self.node().player as usize
+    }
+
+    /// Returns the available actions of the current node.
+    #[inline]
+    pub fn available_actions(&self) -> &[Action] {
+        &self.node().actions
+    }
+
+    /// Returns the amount committed by each player on the river at the current node.
+    #[inline]
+    pub fn total_bet_amount(&self) -> [i32; 3] {
+        self.node().amounts
+    }
+
+    /// Returns the average strategy of the current node.
+    ///
+    /// The return value is a vector of the length of `#(actions) * #(private hands)`. The
+    /// probability of the `i`-th action with the `j`-th private hand is stored in the
+    /// `i * #(private hands) + j`-th element.
+    ///
+    /// Panics if the memory is not allocated or the current node is a terminal node.
+    #[inline]
+    pub fn strategy(&self) -> Vec<f32> {
+        if !self.is_memory_allocated {
+            panic!("Memory is not allocated");
+        }
+
+        let node = self.node();
+        if node.is_terminal() {
+            panic!("Terminal node is not allowed");
+        }
+
+        normalized_strategy(&node.strategy, node.num_actions())
+    }
+
+    /// Returns the reference to the root node.
+    #[inline]
+    pub(super) fn root(&self) -> &ThreeWayNode {
+        &self.root
+    }
+
+    /// Returns the reference to the current node.
+    #[inline]
+    fn node(&self) -> &ThreeWayNode {
+        self.action_history
+            .iter()
+            .fold(&self.root, |node, &action| &node.children[action])
+    }
+
+    /// Checks the card configuration.
+    fn check_card_config(&self) -> Result<(), Error> {
+        let config = &self.card_config;
+        let board = [
+            config.flop[0],
+            config.flop[1],
+            config.flop[2],
+            config.turn,
+            config.river,
+        ];
+
+        if board.contains(&NOT_DEALT) {
+            return Err(Error::InvalidCard(
+                "Board cards not initialized".to_string(),
+            ));
+        }
+
+        if board.iter().any(|&c| 52 <= c) {
+            return Err(Error::InvalidCard(format!(
+                "Board cards must be in [0, 52): board = {board:?}"
+            )));
+        }
+
+        for i in 0..5 {
+            if board[i + 1..].contains(&board[i]) {
+                return Err(Error::InvalidCard(format!(
+                    "Board cards must be unique: board = {board:?}"
+                )));
+            }
+        }
+
+        for (player, range) in config.range.iter().enumerate() {
+            if !range.is_valid() {
+                return Err(Error::InvalidRange(format!(
+                    "Invalid range (player = {player})"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Initializes fields related to private hands.
+    fn init_hands(&mut self) {
+        let config = &self.card_config;
+        let board = [
+            config.flop[0],
+            config.flop[1],
+            config.flop[2],
+            config.turn,
+            config.river,
+        ];
+
+        let board_mask = board.iter().fold(0u64, |mask, &c| mask | (1 << c));
+        let board_hand = board
+            .iter()
+            .fold(Hand::new(), |hand, &c| hand.add_card(c as usize));
+
+        for player in 0..3 {
+            let (hands, weights) = config.range[player].get_hands_weights(board_mask);
+            self.hand_strength[player] = hands
+                .iter()
+                .map(|&(c1, c2)| {
+                    let hand = board_hand.add_card(c1 as usize).add_card(c2 as usize);
+                    hand.evaluate()
+                })
+                .collect();
+            self.private_cards[player] = hands;
+            self.initial_weights[player] = weights;
+        }
+
+        // count the number of valid card assignments
+        let mut num_combinations = 0.0;
+        for (&(a1, a2), &w0) in self.private_cards[0].iter().zip(&self.initial_weights[0]) {
+            let mask0: u64 = (1 << a1) | (1 << a2);
+            for (&(b1, b2), &w1) in self.private_cards[1].iter().zip(&self.initial_weights[1]) {
+                let mask1: u64 = (1 << b1) | (1 << b2);
+                if mask0 & mask1 != 0 {
+                    continue;
+                }
+                let w01 = w0 as f64 * w1 as f64;
+                for (&(c1, c2), &w2) in self.private_cards[2].iter().zip(&self.initial_weights[2]) {
+                    let mask2: u64 = (1 << c1) | (1 << c2);
+                    if (mask0 | mask1) & mask2 == 0 {
+                        num_combinations += w01 * w2 as f64;
+                    }
+                }
+            }
+        }
+
+        self.num_combinations = num_combinations;
+    }
+
+    fn allocate_memory_recursive(node: &mut ThreeWayNode, num_hands: &[usize; 3]) {
+        if node.is_terminal() {
+            return;
+        }
+
+        let num_elements = node.num_actions() * num_hands[node.player as usize];
+        node.strategy = vec![0.0; num_elements];
+        node.regrets = vec![0.0; num_elements];
+
+        for child in &mut node.children {
+            Self::allocate_memory_recursive(child, num_hands);
+        }
+    }
+
+    fn memory_usage_recursive(&self, node: &ThreeWayNode) -> u64 {
+        if node.is_terminal() {
+            return 0;
+        }
+
+        let num_hands = self.num_private_hands(node.player as usize);
+        let storage = 2 * (node.num_actions() * num_hands * mem::size_of::<f32>()) as u64;
+        storage
+            + node
+                .children
+                .iter()
+                .map(|child| self.memory_usage_recursive(child))
+                .sum::<u64>()
+    }
+}
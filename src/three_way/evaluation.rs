@@ -0,0 +1,137 @@
+use super::*;
+
+/// Returns `0` if `x < y`, `1` if `x == y`, and `2` if `x > y`.
+#[inline]
+fn compare_index(x: u16, y: u16) -> usize {
+    (x > y) as usize + (x >= y) as usize
+}
+
+/// Returns the index of the payoff table for the given hand strengths.
+#[inline]
+fn outcome_index(player: u16, opponent1: u16, opponent2: u16) -> usize {
+    compare_index(player, opponent1) * 9
+        + compare_index(player, opponent2) * 3
+        + compare_index(opponent1, opponent2)
+}
+
+impl ThreeWayGame {
+    /// Computes the counterfactual values of `player` at the terminal `node`.
+    ///
+    /// `cfreach[p]` is the reach probabilities of player `p` (the entry of `player` is ignored).
+    pub(super) fn evaluate(
+        &self,
+        result: &mut [f32],
+        node: &ThreeWayNode,
+        player: usize,
+        cfreach: [&[f32]; 3],
+    ) {
+        let opponents = [(player + 1) % 3, (player + 2) % 3];
+
+        // payoffs of `player` indexed by the outcome of the showdown
+        let mut payoff_table = [0.0; 27];
+        for i in 0..27 {
+            let mut strength = [0; 3];
+            strength[player] = (i / 9) as u16;
+            strength[opponents[0]] = (i / 3 % 3) as u16;
+            strength[opponents[1]] = (i % 3) as u16;
+            let index = outcome_index(
+                strength[player],
+                strength[opponents[0]],
+                strength[opponents[1]],
+            );
+            payoff_table[index] = self.payoffs(node, strength)[player] / self.num_combinations;
+        }
+
+        // collect the opponent hands that can reach this node
+        let opponent_hands = opponents.map(|opponent| {
+            self.private_cards[opponent]
+                .iter()
+                .zip(&self.hand_strength[opponent])
+                .zip(cfreach[opponent])
+                .filter(|(_, &reach)| reach != 0.0)
+                .map(|((&(c1, c2), &strength), &reach)| {
+                    let mask: u64 = (1 << c1) | (1 << c2);
+                    (mask, strength, reach as f64)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let player_cards = &self.private_cards[player];
+        let player_strength = &self.hand_strength[player];
+
+        for (i, r) in result.iter_mut().enumerate() {
+            let (c1, c2) = player_cards[i];
+            let mask: u64 = (1 << c1) | (1 << c2);
+            let strength = player_strength[i];
+
+            let mut cfvalue = 0.0;
+            for &(mask1, strength1, reach1) in &opponent_hands[0] {
+                if mask & mask1 != 0 {
+                    continue;
+                }
+
+                let mask01 = mask | mask1;
+                let mut sum = 0.0;
+                for &(mask2, strength2, reach2) in &opponent_hands[1] {
+                    if mask01 & mask2 == 0 {
+                        sum += reach2 * payoff_table[outcome_index(strength, strength1, strength2)];
+                    }
+                }
+
+                cfvalue += reach1 * sum;
+            }
+
+            *r = cfvalue as f32;
+        }
+    }
+
+    /// Computes the payoffs of all players at the terminal `node` with the given hand strengths.
+    ///
+    /// The starting pot is treated as being contributed equally by the three players, and the
+    /// pot is split into the main pot and side pots according to the contributions.
+    pub(super) fn payoffs(&self, node: &ThreeWayNode, strength: [u16; 3]) -> [f64; 3] {
+        let dead_money = self.tree_config.starting_pot as f64 / 3.0;
+        let contribution = node.amounts.map(|amount| dead_money + amount as f64);
+
+        let pot = contribution.iter().sum::<f64>();
+        let rake = (pot * self.tree_config.rake_rate).min(self.tree_config.rake_cap);
+        let rake_coef = 1.0 - rake / pot;
+
+        let mut levels = (0..3)
+            .filter(|&p| !node.folded[p])
+            .map(|p| node.amounts[p])
+            .collect::<Vec<_>>();
+        levels.sort_unstable();
+        levels.dedup();
+
+        let mut winnings = [0.0; 3];
+        let mut prev_level = 0.0;
+
+        for level in levels {
+            let level = dead_money + level as f64;
+
+            // the size of the (side) pot between `prev_level` and `level`
+            let layer = contribution
+                .iter()
+                .map(|&c| c.min(level) - c.min(prev_level))
+                .sum::<f64>();
+
+            let eligible = (0..3)
+                .filter(|&p| !node.folded[p] && contribution[p] >= level)
+                .collect::<Vec<_>>();
+            let best = eligible.iter().map(|&p| strength[p]).max().unwrap();
+            let winners = eligible
+                .into_iter()
+                .filter(|&p| strength[p] == best)
+                .collect::<Vec<_>>();
+
+            for &p in &winners {
+                winnings[p] += layer / winners.len() as f64;
+            }
+
+            prev_level = level;
+        }
+
+        [0, 1, 2].map(|p| winnings[p] * rake_coef - contribution[p])
+    }
+}
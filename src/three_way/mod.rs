@@ -0,0 +1,84 @@
+//! Three-player postflop game.
+//!
+//! This module provides a river-only game with three acting positions. Unlike [`PostFlopGame`],
+//! the game tree is built and solved without isomorphism, compression, or node locking, and the
+//! terminal evaluation enumerates all combinations of the three hands. Therefore, the ranges
+//! should be kept reasonably small.
+//!
+//! Players are indexed by their acting order: player `0` acts first and player `2` acts last.
+//! Each player has its own stack, and side pots are created when a player is all-in for less.
+//!
+//! The number of players is fixed at three: the tree builder, the evaluator, and the solver are
+//! specialized for three players and do not generalize to other player counts. Use
+//! [`PostFlopGame`] for heads-up games.
+//!
+//! [`PostFlopGame`]: crate::PostFlopGame
+
+mod action_tree;
+mod base;
+mod evaluation;
+mod solver;
+
+#[cfg(test)]
+mod tests;
+
+pub use action_tree::*;
+pub use solver::*;
+
+use crate::action_tree::*;
+use crate::card::*;
+use crate::range::*;
+
+/// A struct containing the card configuration of the three-player game.
+///
+/// Currently, the whole board (i.e., the flop, turn, and river cards) must be dealt.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let card_config = ThreeWayCardConfig {
+///     range: [
+///         "AA,KK,AKs".parse().unwrap(),
+///         "QQ,JJ,AQs".parse().unwrap(),
+///         "TT,99,KQs".parse().unwrap(),
+///     ],
+///     flop: flop_from_str("Td9d6h").unwrap(),
+///     turn: card_from_str("Qc").unwrap(),
+///     river: card_from_str("2s").unwrap(),
+/// };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ThreeWayCardConfig {
+    /// Initial range of each player.
+    pub range: [Range; 3],
+
+    /// Flop cards: each card must be unique.
+    pub flop: [Card; 3],
+
+    /// Turn card: must be in range [`0`, `52`).
+    pub turn: Card,
+
+    /// River card: must be in range [`0`, `52`).
+    pub river: Card,
+}
+
+/// A struct representing a three-player postflop game.
+#[derive(Default)]
+pub struct ThreeWayGame {
+    // configuration
+    card_config: ThreeWayCardConfig,
+    tree_config: ThreeWayTreeConfig,
+    root: ThreeWayNode,
+    is_memory_allocated: bool,
+    is_solved: bool,
+
+    // hand information
+    private_cards: [Vec<(Card, Card)>; 3],
+    initial_weights: [Vec<f32>; 3],
+    hand_strength: [Vec<u16>; 3],
+    num_combinations: f64,
+
+    // interpreter
+    action_history: Vec<usize>,
+}
@@ -0,0 +1,337 @@
+use super::*;
+use crate::sliceop::*;
+use crate::solver::*;
+use crate::utility::*;
+use std::io::{self, Write};
+
+/// Performs Discounted CFR algorithm on the three-player game until the given number of
+/// iterations or exploitability is satisfied.
+///
+/// This method returns the exploitability of the obtained strategy (see
+/// [`compute_three_way_exploitability`]).
+#[inline]
+pub fn solve_three_way(
+    game: &mut ThreeWayGame,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    solve_three_way_with(
+        game,
+        &DiscountedCfr::default(),
+        max_num_iterations,
+        target_exploitability,
+        print_progress,
+    )
+}
+
+/// Performs the given CFR algorithm on the three-player game until the given number of
+/// iterations or exploitability is satisfied.
+///
+/// The three players are updated alternately in each iteration. Predictive algorithms are not
+/// supported.
+///
+/// This method returns the exploitability of the obtained strategy (see
+/// [`compute_three_way_exploitability`]).
+pub fn solve_three_way_with<A: CfrAlgorithm + ?Sized>(
+    game: &mut ThreeWayGame,
+    algorithm: &A,
+    max_num_iterations: u32,
+    target_exploitability: f32,
+    print_progress: bool,
+) -> f32 {
+    if game.is_solved() {
+        panic!("Game is already solved");
+    }
+
+    if !game.is_ready() {
+        panic!("Game is not ready");
+    }
+
+    if algorithm.is_predictive() {
+        panic!("Predictive algorithms are not supported");
+    }
+
+    let mut root = std::mem::take(&mut game.root);
+    let mut exploitability = compute_exploitability_internal(game, &root);
+
+    if print_progress {
+        print!("iteration: 0 / {max_num_iterations} ");
+        print!("(exploitability = {exploitability:.4e})");
+        io::stdout().flush().unwrap();
+    }
+
+    for t in 0..max_num_iterations {
+        if exploitability <= target_exploitability {
+            break;
+        }
+
+        let discount = algorithm.discount_params(t);
+        let floors_regrets = algorithm.floors_regrets();
+
+        // alternating updates
+        for player in 0..3 {
+            let cfreach = [0, 1, 2].map(|p| game.initial_weights(p));
+            let mut result = vec![0.0; game.num_private_hands(player)];
+            solve_recursive(
+                &mut result,
+                game,
+                &mut root,
+                player,
+                cfreach,
+                &discount,
+                floors_regrets,
+            );
+        }
+
+        if (t + 1) % 10 == 0 || t + 1 == max_num_iterations {
+            exploitability = compute_exploitability_internal(game, &root);
+        }
+
+        if print_progress {
+            print!("\riteration: {} / {} ", t + 1, max_num_iterations);
+            print!("(exploitability = {exploitability:.4e})");
+            io::stdout().flush().unwrap();
+        }
+    }
+
+    if print_progress {
+        println!();
+        io::stdout().flush().unwrap();
+    }
+
+    game.root = root;
+    game.is_solved = true;
+
+    compute_three_way_exploitability(game)
+}
+
+/// Computes the exploitability of the average strategy of the three-player game.
+///
+/// The exploitability is defined as the average of the gains that each player obtains by
+/// switching to the best response against the average strategies of the other two players.
+#[inline]
+pub fn compute_three_way_exploitability(game: &ThreeWayGame) -> f32 {
+    if !game.is_ready() {
+        panic!("Game is not ready");
+    }
+
+    compute_exploitability_internal(game, game.root())
+}
+
+/// Computes the expected values of the average strategy of each player.
+///
+/// The starting pot is treated as being contributed equally by the three players, so the sum of
+/// the return values is zero when not raked.
+#[inline]
+pub fn compute_three_way_ev(game: &ThreeWayGame) -> [f32; 3] {
+    if !game.is_ready() {
+        panic!("Game is not ready");
+    }
+
+    [0, 1, 2].map(|player| compute_value(game, game.root(), player, false))
+}
+
+fn compute_exploitability_internal(game: &ThreeWayGame, root: &ThreeWayNode) -> f32 {
+    let gain = (0..3)
+        .map(|player| {
+            compute_value(game, root, player, true) - compute_value(game, root, player, false)
+        })
+        .sum::<f32>();
+    gain / 3.0
+}
+
+/// Computes the expected value of `player` at the root (optionally with the best response).
+fn compute_value(
+    game: &ThreeWayGame,
+    root: &ThreeWayNode,
+    player: usize,
+    best_response: bool,
+) -> f32 {
+    let cfreach = [0, 1, 2].map(|p| game.initial_weights(p));
+    let mut result = vec![0.0; game.num_private_hands(player)];
+    compute_value_recursive(&mut result, game, root, player, cfreach, best_response);
+    result
+        .iter()
+        .zip(game.initial_weights(player))
+        .map(|(&v, &w)| v as f64 * w as f64)
+        .sum::<f64>() as f32
+}
+
+/// Recursively solves the counterfactual values.
+fn solve_recursive(
+    result: &mut [f32],
+    game: &ThreeWayGame,
+    node: &mut ThreeWayNode,
+    player: usize,
+    cfreach: [&[f32]; 3],
+    discount: &DiscountParams,
+    floors_regrets: bool,
+) {
+    if node.is_terminal() {
+        game.evaluate(result, node, player, cfreach);
+        return;
+    }
+
+    let num_actions = node.num_actions();
+    let num_hands = result.len();
+    let node_player = node.player as usize;
+    let strategy = regret_matching(&node.regrets, num_actions);
+
+    if node_player == player {
+        // compute the counterfactual values of each action
+        let mut cfv_actions = vec![0.0; num_actions * num_hands];
+        for (action, child) in node.children.iter_mut().enumerate() {
+            let row = &mut cfv_actions[action * num_hands..(action + 1) * num_hands];
+            solve_recursive(row, game, child, player, cfreach, discount, floors_regrets);
+        }
+
+        // sum up the counterfactual values
+        result.fill(0.0);
+        for (s, c) in strategy
+            .chunks_exact(num_hands)
+            .zip(cfv_actions.chunks_exact(num_hands))
+        {
+            result.iter_mut().zip(s).zip(c).for_each(|((r, &s), &c)| {
+                *r += s * c;
+            });
+        }
+
+        // update the cumulative strategy
+        let gamma = discount.gamma_t;
+        node.strategy
+            .iter_mut()
+            .zip(&strategy)
+            .for_each(|(x, &y)| *x = *x * gamma + y);
+
+        // update the cumulative regret
+        let (alpha, beta) = (discount.alpha_t, discount.beta_t);
+        for (regrets, cfv) in node
+            .regrets
+            .chunks_exact_mut(num_hands)
+            .zip(cfv_actions.chunks_exact(num_hands))
+        {
+            regrets
+                .iter_mut()
+                .zip(cfv)
+                .zip(&*result)
+                .for_each(|((x, &c), &r)| {
+                    let coef = if x.is_sign_positive() { alpha } else { beta };
+                    *x = *x * coef + c - r;
+                    if floors_regrets {
+                        *x = max(*x, 0.0);
+                    }
+                });
+        }
+    } else {
+        result.fill(0.0);
+        let mut cfv = vec![0.0; num_hands];
+
+        // only the reach probabilities of the acting player change, so a single buffer is reused
+        // for all actions and the others are shared with the parent
+        let mut cfreach_actor = vec![0.0; cfreach[node_player].len()];
+
+        for (action, child) in node.children.iter_mut().enumerate() {
+            // update the reach probabilities of the acting player
+            let row = row(&strategy, action, cfreach_actor.len());
+            cfreach_actor
+                .iter_mut()
+                .zip(cfreach[node_player])
+                .zip(row)
+                .for_each(|((u, &r), &s)| *u = r * s);
+
+            let mut cfreach_updated = cfreach;
+            cfreach_updated[node_player] = &cfreach_actor;
+
+            solve_recursive(
+                &mut cfv,
+                game,
+                child,
+                player,
+                cfreach_updated,
+                discount,
+                floors_regrets,
+            );
+            result.iter_mut().zip(&cfv).for_each(|(r, &v)| *r += v);
+        }
+    }
+}
+
+/// Recursively computes the counterfactual values of the average strategy (or the best
+/// response of `player` if `best_response` is `true`).
+fn compute_value_recursive(
+    result: &mut [f32],
+    game: &ThreeWayGame,
+    node: &ThreeWayNode,
+    player: usize,
+    cfreach: [&[f32]; 3],
+    best_response: bool,
+) {
+    if node.is_terminal() {
+        game.evaluate(result, node, player, cfreach);
+        return;
+    }
+
+    let num_actions = node.num_actions();
+    let num_hands = result.len();
+    let node_player = node.player as usize;
+    let strategy = normalized_strategy(&node.strategy, num_actions);
+
+    if node_player == player {
+        let mut cfv_actions = vec![0.0; num_actions * num_hands];
+        for (action, child) in node.children.iter().enumerate() {
+            let row = &mut cfv_actions[action * num_hands..(action + 1) * num_hands];
+            compute_value_recursive(row, game, child, player, cfreach, best_response);
+        }
+
+        if best_response {
+            result.fill(f32::MIN);
+            for c in cfv_actions.chunks_exact(num_hands) {
+                result.iter_mut().zip(c).for_each(|(r, &c)| *r = max(*r, c));
+            }
+        } else {
+            result.fill(0.0);
+            for (s, c) in strategy
+                .chunks_exact(num_hands)
+                .zip(cfv_actions.chunks_exact(num_hands))
+            {
+                result.iter_mut().zip(s).zip(c).for_each(|((r, &s), &c)| {
+                    *r += s * c;
+                });
+            }
+        }
+    } else {
+        result.fill(0.0);
+        let mut cfv = vec![0.0; num_hands];
+        let mut cfreach_actor = vec![0.0; cfreach[node_player].len()];
+
+        for (action, child) in node.children.iter().enumerate() {
+            let row = row(&strategy, action, cfreach_actor.len());
+            cfreach_actor
+                .iter_mut()
+                .zip(cfreach[node_player])
+                .zip(row)
+                .for_each(|((u, &r), &s)| *u = r * s);
+
+            let mut cfreach_updated = cfreach;
+            cfreach_updated[node_player] = &cfreach_actor;
+
+            compute_value_recursive(
+                &mut cfv,
+                game,
+                child,
+                player,
+                cfreach_updated,
+                best_response,
+            );
+            result.iter_mut().zip(&cfv).for_each(|(r, &v)| *r += v);
+        }
+    }
+}
+
+/// Computes the strategy by regret-matching algorithm.
+#[inline]
+fn regret_matching(regrets: &[f32], num_actions: usize) -> Vec<f32> {
+    let positive = regrets.iter().map(|&r| max(r, 0.0)).collect::<Vec<_>>();
+    normalized_strategy(&positive, num_actions)
+}
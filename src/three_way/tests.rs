@@ -0,0 +1,150 @@
+use super::*;
+use crate::bet_size::*;
+
+fn card_config() -> ThreeWayCardConfig {
+    ThreeWayCardConfig {
+        range: [
+            "AA,KK,AKs,87s".parse().unwrap(),
+            "QQ,JJ,AQs,65s".parse().unwrap(),
+            "TT,99,KQs,54s".parse().unwrap(),
+        ],
+        flop: flop_from_str("Td9d6h").unwrap(),
+        turn: card_from_str("Qc").unwrap(),
+        river: card_from_str("2s").unwrap(),
+    }
+}
+
+fn tree_config(stacks: [i32; 3]) -> ThreeWayTreeConfig {
+    let bet_sizes: BetSizeOptions = ("50%", "a").try_into().unwrap();
+    ThreeWayTreeConfig {
+        starting_pot: 300,
+        stacks,
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn action_tree() {
+    let action_tree = ThreeWayActionTree::new(tree_config([900, 900, 900])).unwrap();
+    let card_config = card_config();
+    let mut game = ThreeWayGame::with_config(card_config, action_tree).unwrap();
+
+    // check - check - check
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(game.available_actions(), [Action::Check, Action::Bet(150)]);
+    game.play(0);
+    assert_eq!(game.current_player(), 1);
+    game.play(0);
+    assert_eq!(game.current_player(), 2);
+    game.play(0);
+    assert!(game.is_terminal_node());
+
+    // bet - fold - call
+    game.back_to_root();
+    game.play(1);
+    assert_eq!(game.current_player(), 1);
+    assert_eq!(
+        game.available_actions(),
+        [Action::Fold, Action::Call, Action::AllIn(900)]
+    );
+    game.play(0);
+    assert_eq!(game.current_player(), 2);
+    game.play(1);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.total_bet_amount(), [150, 0, 150]);
+
+    // check - bet - all-in - fold - call
+    game.back_to_root();
+    game.play(0);
+    game.play(1);
+    assert_eq!(game.current_player(), 2);
+    game.play(2);
+    assert_eq!(game.current_player(), 0);
+    assert_eq!(game.available_actions(), [Action::Fold, Action::Call]);
+    game.play(0);
+    assert_eq!(game.current_player(), 1);
+    game.play(1);
+    assert!(game.is_terminal_node());
+    assert_eq!(game.total_bet_amount(), [0, 900, 900]);
+
+    assert!(ThreeWayActionTree::new(tree_config([900, 0, 900])).is_err());
+}
+
+#[test]
+fn force_allin_threshold() {
+    let bet_sizes: BetSizeOptions = ("50%", "50%").try_into().unwrap();
+    let config = ThreeWayTreeConfig {
+        starting_pot: 300,
+        stacks: [1000, 1000, 1000],
+        river_bet_sizes: [bet_sizes.clone(), bet_sizes.clone(), bet_sizes],
+        add_allin_threshold: 0.0,
+        force_allin_threshold: 0.4,
+        ..Default::default()
+    };
+
+    let action_tree = ThreeWayActionTree::new(config).unwrap();
+    let mut game = ThreeWayGame::with_config(card_config(), action_tree).unwrap();
+
+    // bet 150; a raise to 450 leaves 550 behind, which is within 40% of the pot of 1650 after
+    // both opponents call (but not within 40% of the heads-up pot of 1200)
+    game.play(1);
+    assert_eq!(
+        game.available_actions(),
+        [Action::Fold, Action::Call, Action::AllIn(1000)]
+    );
+}
+
+#[test]
+fn side_pots() {
+    let action_tree = ThreeWayActionTree::new(tree_config([100, 300, 300])).unwrap();
+    let game = ThreeWayGame::with_config(card_config(), action_tree).unwrap();
+
+    let node = ThreeWayNode {
+        player: PLAYER_TERMINAL_FLAG,
+        amounts: [100, 300, 300],
+        ..Default::default()
+    };
+
+    // player 0 wins the main pot, and player 1 wins the side pot
+    let payoffs = game.payoffs(&node, [3, 2, 1]);
+    assert_eq!(payoffs, [400.0, 0.0, -400.0]);
+
+    // player 2 wins both pots
+    let payoffs = game.payoffs(&node, [2, 1, 3]);
+    assert_eq!(payoffs, [-200.0, -400.0, 600.0]);
+
+    // player 0 and 1 split the main pot, and player 1 wins the side pot
+    let payoffs = game.payoffs(&node, [3, 3, 1]);
+    assert_eq!(payoffs, [100.0, 300.0, -400.0]);
+
+    // player 1 folded
+    let node = ThreeWayNode {
+        folded: [false, true, false],
+        ..node
+    };
+    let payoffs = game.payoffs(&node, [3, 2, 1]);
+    assert_eq!(payoffs, [400.0, -400.0, 0.0]);
+}
+
+#[test]
+fn solve() {
+    let action_tree = ThreeWayActionTree::new(tree_config([300, 900, 900])).unwrap();
+    let mut game = ThreeWayGame::with_config(card_config(), action_tree).unwrap();
+    game.allocate_memory();
+    assert!(compute_three_way_exploitability(&game) > 1.0);
+
+    let exploitability = solve_three_way(&mut game, 300, 1.0, false);
+    assert!(exploitability <= 1.0);
+    assert!(game.is_solved());
+
+    let ev = compute_three_way_ev(&game);
+    assert!((ev[0] + ev[1] + ev[2]).abs() < 1e-2);
+
+    let num_hands = game.num_private_hands(0);
+    let strategy = game.strategy();
+    for hand in 0..num_hands {
+        let sum = strategy.iter().skip(hand).step_by(num_hands).sum::<f32>();
+        assert!((sum - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::action_tree::*;
+use crate::game::*;
+use std::fmt;
+use std::mem;
+
+/// A struct representing the structural differences between two action trees.
+///
+/// Each line is an action sequence from the root node in the same format as
+/// [`ActionTree::add_line`], i.e., chance actions are omitted. When a subtree exists only in one of
+/// the trees, only the line to the root of the subtree is listed. Unlike
+/// [`ActionTree::added_lines`] and [`ActionTree::removed_lines`], the differences caused by the
+/// configuration (e.g., the merging threshold and the all-in thresholds) are also detected.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 1000,
+///     river_bet_sizes: [("50%, 60%", "").try_into().unwrap(), Default::default()],
+///     ..Default::default()
+/// };
+///
+/// let left = ActionTree::new(tree_config.clone()).unwrap();
+/// let right = ActionTree::new(TreeConfig {
+///     merging_threshold: 0.1,
+///     ..tree_config.clone()
+/// })
+/// .unwrap();
+///
+/// // the 50% bet is merged into the 60% bet
+/// let diff = ActionTreeDiff::new(&left, &right);
+/// assert_eq!(diff.only_in_left, [vec![Action::Bet(50)]]);
+/// assert!(diff.only_in_right.is_empty());
+/// assert!(diff.amount_mismatches.is_empty());
+///
+/// // the pot is twice as large in the right tree
+/// let right = ActionTree::new(TreeConfig {
+///     starting_pot: 200,
+///     ..tree_config
+/// })
+/// .unwrap();
+///
+/// let diff = ActionTreeDiff::new(&left, &right);
+/// assert_eq!(
+///     diff.amount_mismatches,
+///     [
+///         (vec![], Action::Bet(50), Action::Bet(100)),
+///         (vec![], Action::Bet(60), Action::Bet(120)),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionTreeDiff {
+    /// Lines that exist only in the first (left) tree.
+    pub only_in_left: Vec<Vec<Action>>,
+
+    /// Lines that exist only in the second (right) tree.
+    pub only_in_right: Vec<Vec<Action>>,
+
+    /// Bet actions of the same kind (bet, raise, or all-in) that exist at the same node but have
+    /// different amounts.
+    ///
+    /// Each element is `(line, left_action, right_action)`, where `line` leads to the node. The
+    /// unmatched bet actions of each node are paired in ascending order of amounts, and the
+    /// subtrees below the paired actions are not compared further.
+    pub amount_mismatches: Vec<(Vec<Action>, Action, Action)>,
+}
+
+impl ActionTreeDiff {
+    /// Compares two action trees.
+    #[inline]
+    pub fn new(left: &ActionTree, right: &ActionTree) -> Self {
+        Self::from_roots(&left.root(), &right.root())
+    }
+
+    /// Compares the action trees of two games.
+    ///
+    /// Lines removed by [`PostFlopGame::remove_lines`] are not taken into account.
+    #[inline]
+    pub fn from_games(left: &PostFlopGame, right: &PostFlopGame) -> Self {
+        Self::from_roots(&left.action_root(), &right.action_root())
+    }
+
+    /// Returns whether the two trees are structurally identical.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.only_in_left.is_empty()
+            && self.only_in_right.is_empty()
+            && self.amount_mismatches.is_empty()
+    }
+
+    #[inline]
+    fn from_roots(left: &ActionTreeNode, right: &ActionTreeNode) -> Self {
+        let mut ret = Self::default();
+        ret.diff_recursive(left, right, &mut Vec::new());
+        ret
+    }
+
+    fn diff_recursive(
+        &mut self,
+        left: &ActionTreeNode,
+        right: &ActionTreeNode,
+        line: &mut Vec<Action>,
+    ) {
+        if left.is_chance() && !left.is_terminal() {
+            return self.diff_recursive(&left.children[0].lock(), right, line);
+        }
+
+        if right.is_chance() && !right.is_terminal() {
+            return self.diff_recursive(left, &right.children[0].lock(), line);
+        }
+
+        let mut left_only = Vec::new();
+        for (&action, child) in left.actions.iter().zip(&left.children) {
+            match right.actions.iter().position(|&a| a == action) {
+                Some(index) => {
+                    line.push(action);
+                    self.diff_recursive(&child.lock(), &right.children[index].lock(), line);
+                    line.pop();
+                }
+                None => left_only.push(action),
+            }
+        }
+
+        let mut right_only = right
+            .actions
+            .iter()
+            .filter(|a| !left.actions.contains(a))
+            .copied()
+            .collect::<Vec<_>>();
+
+        // pair the bet actions of the same kind in ascending order of amounts
+        for action in left_only {
+            let is_bet = matches!(action, Action::Bet(_) | Action::Raise(_) | Action::AllIn(_));
+            let same_kind = |a: &Action| mem::discriminant(a) == mem::discriminant(&action);
+            match right_only.iter().position(same_kind) {
+                Some(index) if is_bet => {
+                    let right_action = right_only.remove(index);
+                    self.amount_mismatches
+                        .push((line.clone(), action, right_action));
+                }
+                _ => {
+                    line.push(action);
+                    self.only_in_left.push(line.clone());
+                    line.pop();
+                }
+            }
+        }
+
+        for action in right_only {
+            line.push(action);
+            self.only_in_right.push(line.clone());
+            line.pop();
+        }
+    }
+}
+
+impl fmt::Display for ActionTreeDiff {
+    /// Formats the differences in a unified-diff-like style: `-` for the lines only in the left
+    /// tree, `+` for the lines only in the right tree, and `~` for the amount mismatches.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.only_in_left {
+            writeln!(f, "- {line:?}")?;
+        }
+        for line in &self.only_in_right {
+            writeln!(f, "+ {line:?}")?;
+        }
+        for (line, left, right) in &self.amount_mismatches {
+            writeln!(f, "~ {line:?}: {left:?} -> {right:?}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+    use crate::card::*;
+    use crate::range::*;
+
+    #[test]
+    fn diff_trees() {
+        let bet_sizes = BetSizeOptions::try_from(("50%, 100%", "3x")).unwrap();
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 500,
+            turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            ..Default::default()
+        };
+
+        let left = ActionTree::new(tree_config.clone()).unwrap();
+        assert!(ActionTreeDiff::new(&left, &left).is_empty());
+
+        // a removed line and an added line
+        let mut right = ActionTree::new(tree_config.clone()).unwrap();
+        right.remove_line(&[Action::Bet(100)]).unwrap();
+        right.add_line(&[Action::Check, Action::Bet(75)]).unwrap();
+
+        let diff = ActionTreeDiff::new(&left, &right);
+        assert_eq!(diff.only_in_left, [vec![Action::Bet(100)]]);
+        assert_eq!(diff.only_in_right, [vec![Action::Check, Action::Bet(75)]]);
+        assert!(diff.amount_mismatches.is_empty());
+        assert_eq!(diff.to_string(), "- [Bet(100)]\n+ [Check, Bet(75)]\n");
+
+        // a larger stack: the all-in amounts differ, and the river lines are compared through the
+        // chance nodes
+        let right = ActionTree::new(TreeConfig {
+            effective_stack: 600,
+            ..tree_config
+        })
+        .unwrap();
+
+        let diff = ActionTreeDiff::new(&left, &right);
+        assert!(diff.amount_mismatches.contains(&(
+            vec![Action::Bet(100), Action::Raise(300)],
+            Action::AllIn(500),
+            Action::AllIn(600),
+        )));
+        assert!(diff.amount_mismatches.contains(&(
+            vec![Action::Bet(50), Action::Call, Action::Bet(200)],
+            Action::AllIn(450),
+            Action::AllIn(550),
+        )));
+
+        // actions of different kinds are not paired
+        let line = [Action::Bet(100), Action::Call, Action::Bet(150)];
+        assert!(diff
+            .only_in_left
+            .contains(&[line.as_slice(), &[Action::AllIn(400)]].concat()));
+        assert!(diff
+            .only_in_right
+            .contains(&[line.as_slice(), &[Action::Raise(450)]].concat()));
+    }
+
+    #[test]
+    fn diff_games() {
+        let card_config = CardConfig {
+            range: ["AA,KK".parse::<Range>().unwrap(), "QQ,JJ".parse().unwrap()],
+            flop: flop_from_str("Td9d6h").unwrap(),
+            turn: card_from_str("Qc").unwrap(),
+            river: card_from_str("2s").unwrap(),
+            ..Default::default()
+        };
+
+        let tree_config = |starting_pot| TreeConfig {
+            initial_state: BoardState::River,
+            starting_pot,
+            effective_stack: 100,
+            river_bet_sizes: [("50%", "").try_into().unwrap(), Default::default()],
+            ..Default::default()
+        };
+
+        let action_tree = ActionTree::new(tree_config(100)).unwrap();
+        let left = PostFlopGame::with_config(card_config.clone(), action_tree).unwrap();
+        let action_tree = ActionTree::new(tree_config(120)).unwrap();
+        let right = PostFlopGame::with_config(card_config, action_tree).unwrap();
+
+        let diff = ActionTreeDiff::from_games(&left, &right);
+        assert!(diff.only_in_left.is_empty());
+        assert!(diff.only_in_right.is_empty());
+        assert_eq!(
+            diff.amount_mismatches,
+            [(vec![], Action::Bet(50), Action::Bet(60))]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::action_tree::*;
+use crate::error::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A serializable representation of a node of an [`ActionTree`].
+///
+/// - `player`: `"oop"`, `"ip"`, `"chance"`, or `"terminal"`.
+/// - `board_state`: `"flop"`, `"turn"`, or `"river"`.
+/// - `amount`: The amount that each player has put into the pot at this node, in addition to the
+///   starting pot.
+/// - `actions`: The available actions, e.g., `"Check"`, `"Bet:50"`, `"Raise:150"`, `"AllIn:900"`.
+///   A chance node has the single action `"Chance"`, which stands for every possible deal.
+/// - `children`: The child nodes in the same order as `actions`. This is empty if the node is
+///   terminal or the export was truncated by the depth limit.
+///
+/// The same format is accepted by [`ActionTree::from_json`] and [`ActionTree::from_data`] to build
+/// a tree with explicitly specified actions.
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let bet_sizes = BetSizeOptions::try_from(("50%", "")).unwrap();
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 100,
+///     river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+///     ..Default::default()
+/// };
+///
+/// let action_tree = ActionTree::new(tree_config).unwrap();
+/// let root = action_tree.export(None);
+/// assert_eq!(root.player, "oop");
+/// assert_eq!(root.actions, ["Check", "Bet:50"]);
+/// assert_eq!(root.children[1].amount, 0);
+/// assert_eq!(root.children[1].children[1].amount, 50);
+///
+/// let json = action_tree.to_json(Some(1));
+/// assert!(json.contains("\"Bet:50\""));
+///
+/// let dot = action_tree.to_dot(Some(1));
+/// assert!(dot.starts_with("digraph ActionTree {"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionTreeNodeData {
+    pub player: String,
+    pub board_state: String,
+    pub amount: i32,
+    pub actions: Vec<String>,
+    pub children: Vec<ActionTreeNodeData>,
+}
+
+impl ActionTree {
+    /// Exports the subtree rooted at the current node.
+    ///
+    /// If `max_depth` is `Some(depth)`, the nodes deeper than `depth` actions (including chance
+    /// actions) from the current node are omitted. Call [`back_to_root`] first to export the whole
+    /// tree.
+    ///
+    /// [`back_to_root`]: #method.back_to_root
+    #[inline]
+    pub fn export(&self, max_depth: Option<usize>) -> ActionTreeNodeData {
+        export_recursive(self.current_node(), max_depth.unwrap_or(usize::MAX))
+    }
+
+    /// Exports the subtree rooted at the current node as a pretty-printed JSON string.
+    ///
+    /// See [`ActionTreeNodeData`] for the format and [`export`] for the meaning of `max_depth`.
+    ///
+    /// [`export`]: #method.export
+    #[inline]
+    pub fn to_json(&self, max_depth: Option<usize>) -> String {
+        serde_json::to_string_pretty(&self.export(max_depth)).unwrap()
+    }
+
+    /// Creates a new [`ActionTree`] from a JSON string in the format of [`to_json`].
+    ///
+    /// See [`from_data`] for the details.
+    ///
+    /// [`to_json`]: #method.to_json
+    /// [`from_data`]: #method.from_data
+    #[inline]
+    pub fn from_json(config: TreeConfig, json: &str) -> Result<Self, Error> {
+        let root = serde_json::from_str::<ActionTreeNodeData>(json)?;
+        Self::from_data(config, &root)
+    }
+
+    /// Exports the subtree rooted at the current node as a Graphviz DOT graph.
+    ///
+    /// Each node is labeled with the player, the board state, and the pot size, and each edge is
+    /// labeled with the action. See [`export`] for the meaning of `max_depth`.
+    ///
+    /// [`export`]: #method.export
+    pub fn to_dot(&self, max_depth: Option<usize>) -> String {
+        let mut dot = String::from("digraph ActionTree {\n    node [shape=box];\n");
+        let mut next_id = 0;
+        write_dot_recursive(
+            &mut dot,
+            &self.export(max_depth),
+            self.config().starting_pot,
+            &mut next_id,
+        );
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn export_recursive(node: &ActionTreeNode, depth: usize) -> ActionTreeNodeData {
+    let children = if depth == 0 {
+        Vec::new()
+    } else {
+        node.children
+            .iter()
+            .map(|child| export_recursive(&child.lock(), depth - 1))
+            .collect()
+    };
+
+    ActionTreeNodeData {
+        player: player_to_string(node).to_string(),
+        board_state: board_state_to_string(node.board_state).to_string(),
+        amount: node.amount,
+        actions: node.actions.iter().map(|&a| action_to_string(a)).collect(),
+        children,
+    }
+}
+
+fn write_dot_recursive(
+    dot: &mut String,
+    node: &ActionTreeNodeData,
+    starting_pot: i32,
+    next_id: &mut usize,
+) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let shape = match node.player.as_str() {
+        "terminal" => ", shape=ellipse",
+        "chance" => ", shape=diamond",
+        _ => "",
+    };
+
+    writeln!(
+        dot,
+        "    n{id} [label=\"{}\\n{}\\npot {}\"{shape}];",
+        node.player.to_uppercase(),
+        node.board_state,
+        starting_pot + 2 * node.amount,
+    )
+    .unwrap();
+
+    for (action, child) in node.actions.iter().zip(&node.children) {
+        let child_id = write_dot_recursive(dot, child, starting_pot, next_id);
+        writeln!(dot, "    n{id} -> n{child_id} [label=\"{action}\"];").unwrap();
+    }
+
+    id
+}
+
+#[inline]
+pub(crate) fn player_to_string(node: &ActionTreeNode) -> &'static str {
+    if node.is_terminal() {
+        "terminal"
+    } else if node.is_chance() {
+        "chance"
+    } else if node.player == PLAYER_OOP {
+        "oop"
+    } else {
+        "ip"
+    }
+}
+
+#[inline]
+pub(crate) fn board_state_to_string(board_state: BoardState) -> &'static str {
+    match board_state {
+        BoardState::Flop => "flop",
+        BoardState::Turn => "turn",
+        BoardState::River => "river",
+    }
+}
+
+#[inline]
+fn action_to_string(action: Action) -> String {
+    match action {
+        Action::None => "None".to_string(),
+        Action::Fold => "Fold".to_string(),
+        Action::Check => "Check".to_string(),
+        Action::Call => "Call".to_string(),
+        Action::Bet(amount) => format!("Bet:{amount}"),
+        Action::Raise(amount) => format!("Raise:{amount}"),
+        Action::AllIn(amount) => format!("AllIn:{amount}"),
+        Action::Chance(_) => "Chance".to_string(),
+    }
+}
+
+#[inline]
+pub(crate) fn action_from_str(s: &str) -> Result<Action, String> {
+    let (name, amount) = match s.trim().split_once(':') {
+        Some((name, amount)) => {
+            let amount = amount
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid amount: {s}"))?;
+            (name.trim(), Some(amount))
+        }
+        None => (s.trim(), None),
+    };
+
+    match (name, amount) {
+        ("Fold", None) => Ok(Action::Fold),
+        ("Check", None) => Ok(Action::Check),
+        ("Call", None) => Ok(Action::Call),
+        ("Bet", Some(amount)) => Ok(Action::Bet(amount)),
+        ("Raise", Some(amount)) => Ok(Action::Raise(amount)),
+        ("AllIn", Some(amount)) => Ok(Action::AllIn(amount)),
+        _ => Err(format!("Invalid action: {s}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+
+    #[test]
+    fn export_tree() {
+        let bet_sizes = BetSizeOptions::try_from(("50%", "2x")).unwrap();
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 100,
+            turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            ..Default::default()
+        };
+
+        let mut action_tree = ActionTree::new(tree_config).unwrap();
+        let root = action_tree.export(None);
+        assert_eq!(root.actions, ["Check", "Bet:50"]);
+
+        // check - check leads to a chance node, then the river starts with OOP
+        let chance = &root.children[0].children[0];
+        assert_eq!(chance.player, "chance");
+        assert_eq!(chance.actions, ["Chance"]);
+        assert_eq!(chance.children[0].player, "oop");
+        assert_eq!(chance.children[0].board_state, "river");
+
+        // bet - raise (all-in) - fold
+        let raise = &root.children[1].children[2];
+        assert_eq!(root.children[1].actions, ["Fold", "Call", "AllIn:100"]);
+        assert_eq!(raise.amount, 50);
+        assert_eq!(raise.children[0].player, "terminal");
+
+        // depth limit
+        let truncated = action_tree.export(Some(1));
+        assert_eq!(truncated.children.len(), 2);
+        assert!(truncated.children.iter().all(|c| c.children.is_empty()));
+        assert_eq!(truncated.children[1].actions, root.children[1].actions);
+
+        // subtree of the current node
+        action_tree.play(Action::Bet(50)).unwrap();
+        assert_eq!(action_tree.export(None), root.children[1]);
+
+        let json = action_tree.to_json(None);
+        let parsed: ActionTreeNodeData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, root.children[1]);
+
+        let dot = action_tree.to_dot(Some(1));
+        assert!(dot.contains("n0 [label=\"IP\\nturn\\npot 100\"];"));
+        assert!(dot.contains("n0 -> n1 [label=\"Fold\"];"));
+        assert!(dot.contains("n0 -> n3 [label=\"AllIn:100\"];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn import_tree() {
+        let bet_sizes = BetSizeOptions::try_from(("50%, 100%", "2.5x")).unwrap();
+        let tree_config = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 300,
+            turn_bet_sizes: [bet_sizes.clone(), bet_sizes.clone()],
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            ..Default::default()
+        };
+
+        // round trip
+        let action_tree = ActionTree::new(tree_config.clone()).unwrap();
+        let json = action_tree.to_json(None);
+        let config_without_sizes = TreeConfig {
+            initial_state: BoardState::Turn,
+            starting_pot: 100,
+            effective_stack: 300,
+            ..Default::default()
+        };
+        let imported = ActionTree::from_json(config_without_sizes, &json).unwrap();
+        assert_eq!(imported.export(None), action_tree.export(None));
+        assert!(imported.invalid_terminals().is_empty());
+        assert!(imported.added_lines().is_empty());
+
+        // hand-written river tree: OOP checks or jams, IP checks back or bets 75
+        let json = r#"{
+            "player": "oop", "board_state": "river", "amount": 0,
+            "actions": ["Check", "Bet:300"],
+            "children": [
+                {
+                    "player": "ip", "board_state": "river", "amount": 0,
+                    "actions": ["Check", "Bet:75"],
+                    "children": [
+                        { "player": "terminal", "board_state": "river", "amount": 0,
+                          "actions": [], "children": [] },
+                        {
+                            "player": "oop", "board_state": "river", "amount": 0,
+                            "actions": ["Call", "Fold"],
+                            "children": [
+                                { "player": "terminal", "board_state": "river", "amount": 75,
+                                  "actions": [], "children": [] },
+                                { "player": "terminal", "board_state": "river", "amount": 0,
+                                  "actions": [], "children": [] }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "player": "ip", "board_state": "river", "amount": 0,
+                    "actions": ["Fold", "Call"],
+                    "children": [
+                        { "player": "terminal", "board_state": "river", "amount": 0,
+                          "actions": [], "children": [] },
+                        { "player": "terminal", "board_state": "river", "amount": 300,
+                          "actions": [], "children": [] }
+                    ]
+                }
+            ]
+        }"#;
+
+        let river_config = TreeConfig {
+            initial_state: BoardState::River,
+            ..tree_config.clone()
+        };
+
+        let mut action_tree = ActionTree::from_json(river_config.clone(), json).unwrap();
+        assert_eq!(
+            action_tree.available_actions(),
+            [Action::Check, Action::AllIn(300)]
+        );
+        action_tree.play(Action::Check).unwrap();
+        action_tree.play(Action::Bet(75)).unwrap();
+        assert_eq!(
+            action_tree.available_actions(),
+            [Action::Fold, Action::Call]
+        );
+        assert!(!action_tree.is_terminal_node());
+
+        // invalid trees
+        let invalid = [
+            json.replace("Bet:75", "Bet:0"),
+            json.replace("Bet:75", "Raise:75"),
+            json.replace(r#""amount": 75"#, r#""amount": 50"#),
+            json.replace(r#""Call", "Fold""#, r#""Call", "Check""#),
+            json.replace(r#""Call", "Fold""#, r#""Call", "Call""#),
+            json.replace(r#""Call", "Fold""#, r#""Call""#),
+            json.replace(r#""player": "oop""#, r#""player": "ip""#),
+            json.replace("Bet:300", "Bet:301"),
+            json.replace("Bet:300", "Jam"),
+        ];
+
+        for json in &invalid {
+            assert!(matches!(
+                ActionTree::from_json(river_config.clone(), json),
+                Err(Error::InvalidTreeData(_))
+            ));
+        }
+    }
+}
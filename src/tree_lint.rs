@@ -0,0 +1,181 @@
+use crate::action_tree::*;
+use std::fmt;
+
+/// An issue found by [`ActionTree::lint`].
+///
+/// # Examples
+/// ```
+/// use postflop_solver::*;
+///
+/// let bet_sizes = BetSizeOptions::try_from(("50%, 55%", "1.5x")).unwrap();
+/// let tree_config = TreeConfig {
+///     initial_state: BoardState::River,
+///     starting_pot: 100,
+///     effective_stack: 1000,
+///     river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+///     merging_threshold: 0.1,
+///     ..Default::default()
+/// };
+///
+/// let action_tree = ActionTree::new(tree_config).unwrap();
+/// let issues = action_tree.lint(None);
+///
+/// // the 50% bet is merged into the 55% bet
+/// assert!(issues.contains(&LintIssue {
+///     line: vec![],
+///     kind: LintKind::Merged {
+///         action: Action::Bet(50),
+///         into: Action::Bet(55),
+///     },
+/// }));
+///
+/// // the 1.5x raise is smaller than the minimum raise
+/// assert!(issues.contains(&LintIssue {
+///     line: vec![Action::Bet(55)],
+///     kind: LintKind::BelowMinRaise {
+///         amount: 83,
+///         min_amount: 110,
+///     },
+/// }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// The line to the node where the issue is found (chance actions are omitted). For
+    /// [`LintKind::TooManyRaises`], the line ends with the offending raise.
+    pub line: Vec<Action>,
+
+    /// The kind of the issue.
+    pub kind: LintKind,
+}
+
+/// Kinds of [`LintIssue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// A configured bet size is smaller than the minimum raise and is increased to `min_amount`.
+    BelowMinRaise { amount: i32, min_amount: i32 },
+
+    /// A configured bet size is removed because it is close to the larger bet `into`
+    /// (see [`TreeConfig::merging_threshold`]).
+    Merged { action: Action, into: Action },
+
+    /// A configured bet size is replaced by the all-in action of `allin`
+    /// (see [`TreeConfig::force_allin_threshold`]).
+    ForcedAllIn { amount: i32, allin: i32 },
+
+    /// Multiple configured bet sizes result in the same amount after rounding and clamping, so the
+    /// node has only a single bet action.
+    SameAmount { amount: i32 },
+
+    /// A single street contains more raises than the specified limit.
+    TooManyRaises { num_raises: i32 },
+
+    /// A non-terminal node has no actions (see [`ActionTree::invalid_terminals`]).
+    InvalidTerminal,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: ", self.line)?;
+        match self.kind {
+            LintKind::BelowMinRaise { amount, min_amount } => {
+                write!(f, "bet of {amount} is below the minimum raise {min_amount}")
+            }
+            LintKind::Merged { action, into } => {
+                write!(f, "{action:?} is merged into {into:?}")
+            }
+            LintKind::ForcedAllIn { amount, allin } => {
+                write!(f, "bet of {amount} is replaced by all-in of {allin}")
+            }
+            LintKind::SameAmount { amount } => {
+                write!(f, "all bet sizes result in the same amount {amount}")
+            }
+            LintKind::TooManyRaises { num_raises } => {
+                write!(f, "{num_raises} raises in a single street")
+            }
+            LintKind::InvalidTerminal => write!(f, "non-terminal node has no actions"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bet_size::*;
+
+    fn river_tree(starting_pot: i32, effective_stack: i32, sizes: (&str, &str)) -> ActionTree {
+        let bet_sizes = BetSizeOptions::try_from(sizes).unwrap();
+        ActionTree::new(TreeConfig {
+            initial_state: BoardState::River,
+            starting_pot,
+            effective_stack,
+            river_bet_sizes: [bet_sizes.clone(), bet_sizes],
+            force_allin_threshold: 0.15,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn issue(line: &[Action], kind: LintKind) -> LintIssue {
+        LintIssue {
+            line: line.to_vec(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn lint() {
+        // 10% and 20% of the pot are 0 and 1 chips, and the former is increased to 1 chip
+        let action_tree = river_tree(4, 100, ("10%, 20%", ""));
+        let issues = action_tree.lint(None);
+        let kind = LintKind::BelowMinRaise {
+            amount: 0,
+            min_amount: 1,
+        };
+        assert!(issues.contains(&issue(&[], kind)));
+        assert!(issues.contains(&issue(&[], LintKind::SameAmount { amount: 1 })));
+
+        // the pot-sized bet leaves only 20 chips behind
+        let action_tree = river_tree(100, 120, ("50%, 100%", ""));
+        let issues = action_tree.lint(None);
+        let kind = LintKind::ForcedAllIn {
+            amount: 100,
+            allin: 120,
+        };
+        assert_eq!(issues, [issue(&[], kind), issue(&[Action::Check], kind)]);
+
+        // more than one raise in a street
+        let action_tree = river_tree(100, 1000, ("50%", "2x"));
+        let too_many_raises = |max_raises| {
+            let issues = action_tree.lint(max_raises);
+            issues
+                .into_iter()
+                .filter(|issue| matches!(issue.kind, LintKind::TooManyRaises { .. }))
+                .collect::<Vec<_>>()
+        };
+        assert!(too_many_raises(None).is_empty());
+
+        let issues = too_many_raises(Some(1));
+        let line = [Action::Bet(50), Action::Raise(100), Action::Raise(200)];
+        let kind = LintKind::TooManyRaises { num_raises: 2 };
+        assert!(issues.contains(&issue(&line, kind)));
+        assert!(issues.iter().all(|issue| issue.kind == kind));
+
+        // invalid terminal
+        let mut action_tree = river_tree(100, 1000, ("50%", ""));
+        action_tree
+            .remove_line(&[Action::Bet(50), Action::Fold])
+            .unwrap();
+        action_tree
+            .remove_line(&[Action::Bet(50), Action::Call])
+            .unwrap();
+        let issues = action_tree.lint(None);
+        assert_eq!(
+            issues,
+            [issue(&[Action::Bet(50)], LintKind::InvalidTerminal)]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "[Bet(50)]: non-terminal node has no actions"
+        );
+    }
+}
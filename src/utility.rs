@@ -227,6 +227,13 @@ pub(crate) fn encode_unsigned_slice(dst: &mut [u16], slice: &[f32]) -> f32 {
     scale
 }
 
+/// Decodes the encoded `i16` slice to the `f32` slice.
+#[inline]
+pub(crate) fn decode_signed_slice(slice: &[i16], scale: f32) -> Vec<f32> {
+    let decoder = scale / i16::MAX as f32;
+    slice.iter().map(|&x| x as f32 * decoder).collect()
+}
+
 /// Applies the given swap to the given slice.
 #[inline]
 pub(crate) fn apply_swap<T>(slice: &mut [T], swap_list: &[(u16, u16)]) {
@@ -2,6 +2,7 @@ use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
 use std::path::Path;
+use std::str::FromStr;
 
 use crate::action_tree::Action;
 use crate::deal;
@@ -11,6 +12,7 @@ use crate::results::select_spot;
 use crate::save_exploration_results;
 use crate::ActionTree;
 use crate::Card;
+use crate::Error;
 use crate::GameState;
 use crate::PostFlopGame;
 use crate::Spot;
@@ -219,7 +221,7 @@ pub fn get_specific_result(
     game: &mut PostFlopGame,
     current_player: &str,
     num_actions: usize,
-) -> Result<SpecificResultData, String> {
+) -> Result<SpecificResultData, Error> {
     let buffer = get_results(game);
 
     // Save buffer to JSON file
@@ -402,7 +404,7 @@ pub fn get_specific_result(
 }
 
 /// Exécuter le scénario: OOP bet, IP call, puis turn, puis OOP bet, IP call pour arriver à la river
-pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String> {
+pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), Error> {
     // Créer l'état du jeu
     let mut state = GameState::new();
 
@@ -414,6 +416,7 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
     println!("\n=== DÉMARRAGE DU SCÉNARIO ===");
     println!("Pot initial: {:.2} bb", starting_pot);
     println!("Stack effectif: {:.2} bb", effective_stack);
+    println!("Stacks (OOP, IP): {:?}", game.tree_config().player_stacks());
     println!(
         "Board: {}",
         board
@@ -433,6 +436,7 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         cards: Vec::new(),
         pot: starting_pot,
         stack: effective_stack,
+        stacks: game.tree_config().player_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: None,
     };
@@ -449,7 +453,7 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .actions
         .iter()
         .position(|a| a.name == "Bet")
-        .ok_or_else(|| "Action Bet non trouvée pour OOP".to_string())?;
+        .ok_or_else(|| Error::InvalidAction("Bet action not found for OOP".to_string()))?;
 
     let bet_action = &state.spots[1].actions[bet_idx];
     println!(
@@ -471,7 +475,7 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .actions
         .iter()
         .position(|a| a.name == "Call")
-        .ok_or_else(|| "Action Call non trouvée pour IP".to_string())?;
+        .ok_or_else(|| Error::InvalidAction("Call action not found for IP".to_string()))?;
 
     let call_action = &state.spots[2].actions[call_idx];
     println!(
@@ -505,7 +509,9 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .collect();
 
     if available_cards.is_empty() {
-        return Err("Aucune carte disponible pour la turn!".to_string());
+        return Err(Error::InvalidState(
+            "No card available for the turn".to_string(),
+        ));
     }
 
     // Sélectionner une carte aléatoire parmi les disponibles
@@ -537,7 +543,9 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .actions
         .iter()
         .position(|a| a.name == "Bet")
-        .ok_or_else(|| "Action Bet non trouvée pour OOP sur la turn".to_string())?;
+        .ok_or_else(|| {
+            Error::InvalidAction("Bet action not found for OOP on the turn".to_string())
+        })?;
 
     let turn_bet_action = &state.spots[turn_oop_spot_idx].actions[turn_bet_idx];
     println!(
@@ -560,7 +568,9 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .actions
         .iter()
         .position(|a| a.name == "Call")
-        .ok_or_else(|| "Action Call non trouvée pour IP sur la turn".to_string())?;
+        .ok_or_else(|| {
+            Error::InvalidAction("Call action not found for IP on the turn".to_string())
+        })?;
 
     let turn_call_action = &state.spots[turn_ip_spot_idx].actions[turn_call_idx];
     println!(
@@ -593,7 +603,9 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
         .collect();
 
     if available_cards.is_empty() {
-        return Err("Aucune carte disponible pour la river!".to_string());
+        return Err(Error::InvalidState(
+            "No card available for the river".to_string(),
+        ));
     }
 
     // Sélectionner une carte aléatoire parmi les disponibles
@@ -622,7 +634,7 @@ pub fn run_bet_call_turn_scenario(game: &mut PostFlopGame) -> Result<(), String>
 }
 
 /// Explorer l'arbre des actions de manière interactive (version simplifiée)
-pub fn explore_game_tree(game: &mut PostFlopGame) -> Result<(), String> {
+pub fn explore_game_tree(game: &mut PostFlopGame) -> Result<(), Error> {
     let mut state = GameState::new();
     let mut history_stack: Vec<usize> = Vec::new(); // Indices de spots pour revenir en arrière
     let starting_pot = game.tree_config().starting_pot as f64;
@@ -651,6 +663,7 @@ pub fn explore_game_tree(game: &mut PostFlopGame) -> Result<(), String> {
         cards: Vec::new(),
         pot: starting_pot,
         stack: effective_stack,
+        stacks: game.tree_config().player_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: None,
     };
@@ -883,6 +896,10 @@ fn print_current_state(state: &GameState) {
 
         println!("Pot: {:.2} bb", spot.pot);
         println!("Stack restant: {:.2} bb", spot.stack);
+        println!(
+            "Stacks réels (OOP, IP): {:.2} bb, {:.2} bb",
+            spot.stacks[0], spot.stacks[1]
+        );
 
         match spot.spot_type {
             SpotType::Player => {
@@ -983,6 +1000,80 @@ pub fn get_current_actions_string(game: &PostFlopGame) -> String {
     }
 }
 
+/// Unit used to display the bet amounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AmountUnit {
+    /// Chips (e.g., "40").
+    #[default]
+    Chips,
+
+    /// Big blinds, using [`TreeConfig::big_blind`] (e.g., "2.5bb"). Falls back to chips if the
+    /// big blind is not specified.
+    ///
+    /// [`TreeConfig::big_blind`]: crate::TreeConfig::big_blind
+    BigBlinds,
+
+    /// Percentage of the pot, in the same convention as the "%" bet sizes (e.g., "75%").
+    PotPercent,
+}
+
+impl FromStr for AmountUnit {
+    type Err = String;
+
+    /// Parses "chips", "bb", or "pot" (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "chips" | "c" => Ok(AmountUnit::Chips),
+            "bb" => Ok(AmountUnit::BigBlinds),
+            "pot" | "%" => Ok(AmountUnit::PotPercent),
+            _ => Err(format!("Invalid amount unit: {s}")),
+        }
+    }
+}
+
+/// Formats the amount of the bet action available at the current node in the given unit.
+///
+/// Returns an empty string if `action` is not a bet action.
+pub fn format_amount(game: &PostFlopGame, action: Action, unit: AmountUnit) -> String {
+    let amount = match action {
+        Action::Bet(amount) | Action::Raise(amount) | Action::AllIn(amount) => amount,
+        _ => return String::new(),
+    };
+
+    let big_blind = game.tree_config().big_blind;
+    match unit {
+        AmountUnit::BigBlinds if big_blind > 0 => {
+            format!("{}bb", trim_number(amount as f64 / big_blind as f64))
+        }
+        AmountUnit::PotPercent => {
+            let ratio = game.bet_pot_ratio(action).unwrap();
+            format!("{}%", trim_number(ratio * 100.0))
+        }
+        _ => amount.to_string(),
+    }
+}
+
+/// Formats the action available at the current node with its amount in the given unit
+/// (e.g., "Bet 2.5bb", "Raise 75%", "Check").
+pub fn format_action(game: &PostFlopGame, action: Action, unit: AmountUnit) -> String {
+    match action {
+        Action::Bet(_) => format!("Bet {}", format_amount(game, action, unit)),
+        Action::Raise(_) => format!("Raise {}", format_amount(game, action, unit)),
+        Action::AllIn(_) => format!("AllIn {}", format_amount(game, action, unit)),
+        _ => format!("{action:?}"),
+    }
+}
+
+/// Rounds to one decimal place and removes the trailing ".0".
+fn trim_number(value: f64) -> String {
+    let rounded = (value * 10.0).round() / 10.0;
+    if rounded.fract() == 0.0 {
+        format!("{rounded:.0}")
+    } else {
+        format!("{rounded:.1}")
+    }
+}
+
 pub fn current_player_str(game: &PostFlopGame) -> &'static str {
     if game.is_terminal_node() {
         "terminal"
@@ -1021,7 +1112,7 @@ pub fn explore_recursive(
     paths_explored: &mut i32,
     terminals_reached: &mut i32,
     verbose: bool, // Paramètre pour contrôler le niveau de détail des logs
-) -> Result<(), String> {
+) -> Result<(), Error> {
     // Éviter une profondeur excessive
     if depth >= max_depth {
         if verbose {
@@ -1046,21 +1137,21 @@ pub fn explore_recursive(
 
         // Vérifier si l'index est valide
         if chance_index >= state.spots.len() {
-            return Err(format!(
-                "Index de chance invalide: {} (taille spots: {})",
+            return Err(Error::InvalidState(format!(
+                "Invalid chance index: {} (number of spots: {})",
                 chance_index,
                 state.spots.len()
-            ));
+            )));
         }
 
         let chance_spot = &state.spots[chance_index];
 
         // Vérifier que c'est bien un nœud chance
         if chance_spot.spot_type != SpotType::Chance {
-            return Err(format!(
-                "Le nœud à l'index {} n'est pas un nœud chance (type: {:?})",
+            return Err(Error::InvalidState(format!(
+                "Spot at index {} is not a chance node (type: {:?})",
                 chance_index, chance_spot.spot_type
-            ));
+            )));
         }
 
         let is_turn = chance_spot.player == "turn";
@@ -1080,7 +1171,7 @@ pub fn explore_recursive(
                 .collect();
 
             if available_cards.is_empty() {
-                return Err("Aucune carte disponible pour la distribution".to_string());
+                return Err(Error::InvalidState("No card available to deal".to_string()));
             }
 
             // Sélectionner une carte aléatoire
@@ -1139,7 +1230,12 @@ pub fn explore_recursive(
     let current_spot_index = state.selected_spot_index as usize;
     let current_spot = match state.spots.get(current_spot_index) {
         Some(spot) => spot,
-        None => return Err(format!("Spot à l'index {} non trouvé", current_spot_index)),
+        None => {
+            return Err(Error::InvalidState(format!(
+                "Spot not found at index {}",
+                current_spot_index
+            )))
+        }
     };
 
     // Gestion selon le type de nœud
@@ -1225,7 +1321,7 @@ pub fn explore_recursive(
     }
 }
 
-pub fn explore_all_paths(game: &mut PostFlopGame) -> Result<(), String> {
+pub fn explore_all_paths(game: &mut PostFlopGame) -> Result<(), Error> {
     println!("=== EXPLORATION SYSTÉMATIQUE DE L'ARBRE DE DÉCISION ===");
     println!(
         "Board initial: {}",
@@ -1250,6 +1346,7 @@ pub fn explore_all_paths(game: &mut PostFlopGame) -> Result<(), String> {
         cards: Vec::new(),
         pot: starting_pot,
         stack: effective_stack,
+        stacks: game.tree_config().player_stacks().map(|stack| stack as f64),
         equity_oop: 0.0,
         prev_player: None,
     };
@@ -1377,7 +1474,7 @@ pub fn save_spot_results(
     game: &mut PostFlopGame,
     path_id: &str,
     output_dir: &str,
-) -> Result<bool, String> {
+) -> Result<bool, Error> {
     // Create filename from path_id by replacing special chars
     let filename = path_id
         .replace(":", "_")
@@ -1395,35 +1492,27 @@ pub fn save_spot_results(
 
     // Create directory if it doesn't exist
     if !Path::new(output_dir).exists() {
-        std::fs::create_dir_all(output_dir)
-            .map_err(|e| format!("Failed to create directory {}: {}", output_dir, e))?;
+        std::fs::create_dir_all(output_dir)?;
     }
 
     // Get the solver results
     let buffer = get_results(game);
 
     // Open file for writing
-    let file = File::create(&full_path)
-        .map_err(|e| format!("Failed to create file {}: {}", full_path, e))?;
+    let file = File::create(&full_path)?;
 
     let mut writer = BufWriter::new(file);
 
     // Write length of buffer as u64
     let length = buffer.len() as u64;
-    writer
-        .write_all(&length.to_le_bytes())
-        .map_err(|e| format!("Error writing length: {}", e))?;
+    writer.write_all(&length.to_le_bytes())?;
 
     // Write buffer data
     for &value in buffer.iter() {
-        writer
-            .write_all(&value.to_le_bytes())
-            .map_err(|e| format!("Error writing data: {}", e))?;
+        writer.write_all(&value.to_le_bytes())?;
     }
 
-    writer
-        .flush()
-        .map_err(|e| format!("Error flushing data: {}", e))?;
+    writer.flush()?;
 
     Ok(true) // File was saved
 }
@@ -1433,7 +1522,7 @@ pub fn display_top_hands(
     num_hands: usize,
     stage_label: &str,
     results: &SpecificResultData,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     println!("\n--- DÉTAIL DES MEILLEURES MAINS ({}) ---", stage_label);
 
     // Pour chaque joueur (OOP=0, IP=1)
@@ -1452,16 +1541,13 @@ pub fn display_top_hands(
         } else {
             &results.ip_cards
         };
-        let hand_strings = match holes_to_strings(
+        let hand_strings = holes_to_strings(
             hands
                 .iter()
                 .map(|&(c1, c2)| (c1 as Card, c2 as Card))
                 .collect::<Vec<_>>()
                 .as_slice(),
-        ) {
-            Ok(strings) => strings,
-            Err(_) => return Err("Erreur lors de la conversion des mains en chaînes".to_string()),
-        };
+        )?;
 
         // Créer structure pour trier les mains
         struct HandData {
@@ -1638,7 +1724,7 @@ pub fn format_hand_cards(card_pair: (u8, u8)) -> String {
 }
 
 // Fonction pour parser une action individuelle
-pub fn parse_action(action_str: &str) -> Result<Action, String> {
+pub fn parse_action(action_str: &str) -> Result<Action, Error> {
     match action_str {
         "Fold" => Ok(Action::Fold),
         "Check" => Ok(Action::Check),
@@ -1647,29 +1733,32 @@ pub fn parse_action(action_str: &str) -> Result<Action, String> {
             let amount_str = &s[3..];
             let amount = amount_str
                 .parse::<i32>()
-                .map_err(|_| format!("Invalid bet amount: {}", amount_str))?;
+                .map_err(|_| Error::InvalidAction(format!("Invalid bet amount: {}", amount_str)))?;
             Ok(Action::Bet(amount))
         }
         s if s.starts_with("Raise") => {
             let amount_str = &s[5..];
-            let amount = amount_str
-                .parse::<i32>()
-                .map_err(|_| format!("Invalid raise amount: {}", amount_str))?;
+            let amount = amount_str.parse::<i32>().map_err(|_| {
+                Error::InvalidAction(format!("Invalid raise amount: {}", amount_str))
+            })?;
             Ok(Action::Raise(amount))
         }
         s if s.starts_with("AllIn") => {
             let amount_str = &s[5..];
-            let amount = amount_str
-                .parse::<i32>()
-                .map_err(|_| format!("Invalid all-in amount: {}", amount_str))?;
+            let amount = amount_str.parse::<i32>().map_err(|_| {
+                Error::InvalidAction(format!("Invalid all-in amount: {}", amount_str))
+            })?;
             Ok(Action::AllIn(amount))
         }
-        _ => Err(format!("Unknown action: {}", action_str)),
+        _ => Err(Error::InvalidAction(format!(
+            "Unknown action: {}",
+            action_str
+        ))),
     }
 }
 
 // Et modifier parse_action_line pour utiliser Action
-pub fn parse_action_line(line_str: &[String]) -> Result<Vec<Action>, String> {
+pub fn parse_action_line(line_str: &[String]) -> Result<Vec<Action>, Error> {
     let mut actions = Vec::new();
 
     for action_str in line_str {
@@ -1720,7 +1809,7 @@ pub fn add_lines_simple(action_tree: &mut ActionTree, added_lines_config: &[Vec<
 
 // Dans utils.rs
 // Dans utils.rs - Remplacer remove_all_lines par :
-pub fn remove_all_lines(action_tree: &mut ActionTree) -> Result<(), String> {
+pub fn remove_all_lines(action_tree: &mut ActionTree) -> Result<(), Error> {
     println!("\n=== SUPPRESSION DE TOUTES LES LIGNES ===");
 
     let mut removed_count = 0;